@@ -2,6 +2,25 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20240129_000001_create_auth_tables;
 mod m20240129_000002_create_delivery_table;
+mod m20240205_000003_add_user_role_and_impersonation_audit;
+mod m20240212_000004_add_user_age_columns;
+mod m20240219_000005_add_user_locale;
+mod m20240226_000006_add_user_login_tracking;
+mod m20240304_000007_migrate_user_ids_to_uuid_v7;
+mod m20240311_000008_add_user_handle;
+mod m20240318_000009_create_terms_tables;
+mod m20240325_000010_create_user_notification_settings;
+mod m20240401_000011_add_user_avatar;
+mod m20240408_000012_create_moderation_tables;
+mod m20240415_000013_create_account_status_transitions;
+mod m20240422_000014_create_referral_tables;
+mod m20240429_000015_allow_multiple_delivery_addresses;
+mod m20240506_000016_add_delivery_field_encryption;
+mod m20240513_000017_add_delivery_address_normalization;
+mod m20240520_000018_add_coordinates;
+mod m20240527_000019_create_shipment_tables;
+mod m20240603_000020_create_delivery_assignments;
+mod m20240610_000021_drop_notification_marketing_switch;
 
 pub struct Migrator;
 
@@ -11,6 +30,25 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20240129_000001_create_auth_tables::Migration),
             Box::new(m20240129_000002_create_delivery_table::Migration),
+            Box::new(m20240205_000003_add_user_role_and_impersonation_audit::Migration),
+            Box::new(m20240212_000004_add_user_age_columns::Migration),
+            Box::new(m20240219_000005_add_user_locale::Migration),
+            Box::new(m20240226_000006_add_user_login_tracking::Migration),
+            Box::new(m20240304_000007_migrate_user_ids_to_uuid_v7::Migration),
+            Box::new(m20240311_000008_add_user_handle::Migration),
+            Box::new(m20240318_000009_create_terms_tables::Migration),
+            Box::new(m20240325_000010_create_user_notification_settings::Migration),
+            Box::new(m20240401_000011_add_user_avatar::Migration),
+            Box::new(m20240408_000012_create_moderation_tables::Migration),
+            Box::new(m20240415_000013_create_account_status_transitions::Migration),
+            Box::new(m20240422_000014_create_referral_tables::Migration),
+            Box::new(m20240429_000015_allow_multiple_delivery_addresses::Migration),
+            Box::new(m20240506_000016_add_delivery_field_encryption::Migration),
+            Box::new(m20240513_000017_add_delivery_address_normalization::Migration),
+            Box::new(m20240520_000018_add_coordinates::Migration),
+            Box::new(m20240527_000019_create_shipment_tables::Migration),
+            Box::new(m20240603_000020_create_delivery_assignments::Migration),
+            Box::new(m20240610_000021_drop_notification_marketing_switch::Migration),
         ]
     }
 }
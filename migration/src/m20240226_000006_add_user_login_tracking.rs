@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::LoginCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(ColumnDef::new(Users::LastLoginProvider).string())
+                    .add_column(ColumnDef::new(Users::DormantAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        // The dormant sweep scans active users by their last activity.
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_users_account_status_last_login_at")
+                    .table(Users::Table)
+                    .col(Users::AccountStatus)
+                    .col(Users::LastLoginAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_account_status_last_login_at")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::LoginCount)
+                    .drop_column(Users::LastLoginProvider)
+                    .drop_column(Users::DormantAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    AccountStatus,
+    LastLoginAt,
+    LoginCount,
+    LastLoginProvider,
+    DormantAt,
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+/// Replaces the 128-char SHA-512 user identifiers with UUIDv7.
+///
+/// The old value moves to `legacy_uuid` so tokens and links issued before
+/// this migration keep resolving. Dropping that column ends the transition.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::LegacyUuid).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        // UUIDv7 built from created_at so backfilled ids keep sign-up order:
+        // the first 48 bits of a v4 become unix millis, and flipping bits 52-53
+        // turns version 4 (0100) into 7 (0111). The variant is already RFC 4122.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE users
+                SET legacy_uuid = uuid,
+                    uuid = encode(
+                        set_bit(
+                            set_bit(
+                                overlay(
+                                    uuid_send(gen_random_uuid())
+                                    placing substring(
+                                        int8send(floor(extract(epoch FROM created_at) * 1000)::bigint)
+                                        FROM 3
+                                    )
+                                    FROM 1 FOR 6
+                                ),
+                                52, 1
+                            ),
+                            53, 1
+                        ),
+                        'hex'
+                    )::uuid::text
+                WHERE length(uuid) = 128
+                "#,
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_users_legacy_uuid")
+                    .table(Users::Table)
+                    .col(Users::LegacyUuid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE users SET uuid = legacy_uuid WHERE legacy_uuid IS NOT NULL")
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_legacy_uuid")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::LegacyUuid)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    LegacyUuid,
+}
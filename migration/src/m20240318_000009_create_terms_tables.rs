@@ -0,0 +1,223 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Initial catalog: (kind, title, content path, required).
+const INITIAL_TERMS: &[(&str, &str, &str, bool)] = &[
+    (
+        "TERMS_OF_SERVICE",
+        "서비스 이용약관",
+        "/legal/terms-of-service/1",
+        true,
+    ),
+    (
+        "PRIVACY_POLICY",
+        "개인정보 수집 및 이용 동의",
+        "/legal/privacy-policy/1",
+        true,
+    ),
+    (
+        "LOCATION_TERMS",
+        "위치기반서비스 이용약관",
+        "/legal/location-terms/1",
+        true,
+    ),
+    (
+        "MARKETING_SMS",
+        "마케팅 정보 수신 동의 (SMS)",
+        "/legal/marketing/1",
+        false,
+    ),
+    (
+        "MARKETING_EMAIL",
+        "마케팅 정보 수신 동의 (이메일)",
+        "/legal/marketing/1",
+        false,
+    ),
+    (
+        "MARKETING_PUSH",
+        "마케팅 정보 수신 동의 (앱 푸시)",
+        "/legal/marketing/1",
+        false,
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TermsDocuments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TermsDocuments::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TermsDocuments::Kind).string().not_null())
+                    .col(ColumnDef::new(TermsDocuments::Version).integer().not_null())
+                    .col(ColumnDef::new(TermsDocuments::Title).string().not_null())
+                    .col(
+                        ColumnDef::new(TermsDocuments::ContentUrl)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TermsDocuments::Required)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TermsDocuments::EffectiveAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TermsDocuments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_terms_documents_kind_version")
+                    .table(TermsDocuments::Table)
+                    .col(TermsDocuments::Kind)
+                    .col(TermsDocuments::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserConsents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserConsents::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserConsents::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserConsents::TermsDocumentId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserConsents::Agreed).boolean().not_null())
+                    .col(
+                        ColumnDef::new(UserConsents::RecordedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_consents_user")
+                            .from(UserConsents::Table, UserConsents::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_consents_terms_document")
+                            .from(UserConsents::Table, UserConsents::TermsDocumentId)
+                            .to(TermsDocuments::Table, TermsDocuments::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_consents_user_id")
+                    .table(UserConsents::Table)
+                    .col(UserConsents::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut seed = Query::insert()
+            .into_table(TermsDocuments::Table)
+            .columns([
+                TermsDocuments::Kind,
+                TermsDocuments::Version,
+                TermsDocuments::Title,
+                TermsDocuments::ContentUrl,
+                TermsDocuments::Required,
+                TermsDocuments::EffectiveAt,
+            ])
+            .to_owned();
+        for (kind, title, content_url, required) in INITIAL_TERMS {
+            seed.values_panic([
+                (*kind).into(),
+                1.into(),
+                (*title).into(),
+                (*content_url).into(),
+                (*required).into(),
+                Expr::current_timestamp(),
+            ]);
+        }
+        manager.exec_stmt(seed).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserConsents::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TermsDocuments::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum TermsDocuments {
+    Table,
+    Id,
+    Kind,
+    Version,
+    Title,
+    ContentUrl,
+    Required,
+    EffectiveAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserConsents {
+    Table,
+    Id,
+    UserId,
+    TermsDocumentId,
+    Agreed,
+    RecordedAt,
+}
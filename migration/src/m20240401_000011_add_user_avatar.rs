@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Storage keys, not URLs, so the serving host can change without a backfill.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::AvatarKey).string())
+                    .add_column(ColumnDef::new(Users::AvatarThumbnailKey).string())
+                    .add_column(ColumnDef::new(Users::AvatarUpdatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::AvatarKey)
+                    .drop_column(Users::AvatarThumbnailKey)
+                    .drop_column(Users::AvatarUpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    AvatarKey,
+    AvatarThumbnailKey,
+    AvatarUpdatedAt,
+}
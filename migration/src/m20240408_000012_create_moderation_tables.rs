@@ -0,0 +1,199 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserBlocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserBlocks::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserBlocks::BlockerId).integer().not_null())
+                    .col(ColumnDef::new(UserBlocks::BlockedId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserBlocks::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_blocks_blocker")
+                            .from(UserBlocks::Table, UserBlocks::BlockerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_blocks_blocked")
+                            .from(UserBlocks::Table, UserBlocks::BlockedId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Also serves blocker-side lookups; the second index covers
+        // "who blocked me" for the both-ways check.
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_blocks_blocker_blocked")
+                    .table(UserBlocks::Table)
+                    .col(UserBlocks::BlockerId)
+                    .col(UserBlocks::BlockedId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_blocks_blocked_id")
+                    .table(UserBlocks::Table)
+                    .col(UserBlocks::BlockedId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserReports::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserReports::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserReports::ReporterId).integer().not_null())
+                    .col(ColumnDef::new(UserReports::ReportedId).integer().not_null())
+                    .col(ColumnDef::new(UserReports::Reason).string().not_null())
+                    .col(ColumnDef::new(UserReports::Evidence).text().not_null())
+                    .col(
+                        ColumnDef::new(UserReports::Status)
+                            .string()
+                            .not_null()
+                            .default("OPEN"),
+                    )
+                    .col(ColumnDef::new(UserReports::ResolutionNote).text())
+                    .col(ColumnDef::new(UserReports::ResolvedBy).integer())
+                    .col(ColumnDef::new(UserReports::ResolvedAt).timestamp())
+                    .col(
+                        ColumnDef::new(UserReports::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_reports_reporter")
+                            .from(UserReports::Table, UserReports::ReporterId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_reports_reported")
+                            .from(UserReports::Table, UserReports::ReportedId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_reports_resolved_by")
+                            .from(UserReports::Table, UserReports::ResolvedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_reports_status_id")
+                    .table(UserReports::Table)
+                    .col(UserReports::Status)
+                    .col(UserReports::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_reports_reporter_reported")
+                    .table(UserReports::Table)
+                    .col(UserReports::ReporterId)
+                    .col(UserReports::ReportedId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserReports::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserBlocks::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserBlocks {
+    Table,
+    Id,
+    BlockerId,
+    BlockedId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserReports {
+    Table,
+    Id,
+    ReporterId,
+    ReportedId,
+    Reason,
+    Evidence,
+    Status,
+    ResolutionNote,
+    ResolvedBy,
+    ResolvedAt,
+    CreatedAt,
+}
@@ -0,0 +1,300 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::ReferralCode).string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_users_referral_code")
+                    .table(Users::Table)
+                    .col(Users::ReferralCode)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserDevices::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserDevices::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserDevices::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserDevices::DeviceId).string().not_null())
+                    .col(
+                        ColumnDef::new(UserDevices::FirstSeenAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserDevices::LastSeenAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_devices_user")
+                            .from(UserDevices::Table, UserDevices::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_devices_user_device")
+                    .table(UserDevices::Table)
+                    .col(UserDevices::UserId)
+                    .col(UserDevices::DeviceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_devices_device_id")
+                    .table(UserDevices::Table)
+                    .col(UserDevices::DeviceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Referrals::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Referrals::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Referrals::ReferrerId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Referrals::RefereeId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Referrals::Code).string().not_null())
+                    .col(ColumnDef::new(Referrals::Status).string().not_null())
+                    .col(ColumnDef::new(Referrals::RejectionReason).string())
+                    .col(ColumnDef::new(Referrals::DeviceId).string())
+                    .col(
+                        ColumnDef::new(Referrals::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Referrals::ConvertedAt).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_referrals_referrer")
+                            .from(Referrals::Table, Referrals::ReferrerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_referrals_referee")
+                            .from(Referrals::Table, Referrals::RefereeId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_referrals_referrer_id")
+                    .table(Referrals::Table)
+                    .col(Referrals::ReferrerId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_referrals_created_at")
+                    .table(Referrals::Table)
+                    .col(Referrals::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReferralRewards::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReferralRewards::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ReferralRewards::ReferralId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReferralRewards::UserId).integer().not_null())
+                    .col(ColumnDef::new(ReferralRewards::Role).string().not_null())
+                    .col(ColumnDef::new(ReferralRewards::Points).integer().not_null())
+                    .col(
+                        ColumnDef::new(ReferralRewards::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_referral_rewards_referral")
+                            .from(ReferralRewards::Table, ReferralRewards::ReferralId)
+                            .to(Referrals::Table, Referrals::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_referral_rewards_user")
+                            .from(ReferralRewards::Table, ReferralRewards::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // A referral pays out once per party, however often conversion is reported.
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_referral_rewards_referral_role")
+                    .table(ReferralRewards::Table)
+                    .col(ReferralRewards::ReferralId)
+                    .col(ReferralRewards::Role)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_referral_rewards_user_id")
+                    .table(ReferralRewards::Table)
+                    .col(ReferralRewards::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReferralRewards::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Referrals::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserDevices::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_referral_code")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::ReferralCode)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    ReferralCode,
+}
+
+#[derive(DeriveIden)]
+enum UserDevices {
+    Table,
+    Id,
+    UserId,
+    DeviceId,
+    FirstSeenAt,
+    LastSeenAt,
+}
+
+#[derive(DeriveIden)]
+enum Referrals {
+    Table,
+    Id,
+    ReferrerId,
+    RefereeId,
+    Code,
+    Status,
+    RejectionReason,
+    DeviceId,
+    CreatedAt,
+    ConvertedAt,
+}
+
+#[derive(DeriveIden)]
+enum ReferralRewards {
+    Table,
+    Id,
+    ReferralId,
+    UserId,
+    Role,
+    Points,
+    CreatedAt,
+}
@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres' default name for the `unique_key()` from 000002.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE user_delivery_data DROP CONSTRAINT IF EXISTS user_delivery_data_user_id_key",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .add_column(ColumnDef::new(UserDeliveryData::Label).string())
+                    .add_column(
+                        ColumnDef::new(UserDeliveryData::IsDefault)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Everyone had at most one address so far, which becomes the default.
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE user_delivery_data SET is_default = TRUE")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_delivery_data_user_id")
+                    .table(UserDeliveryData::Table)
+                    .col(UserDeliveryData::UserId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_user_delivery_data_one_default \
+                 ON user_delivery_data (user_id) WHERE is_default",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_user_delivery_data_one_default")
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_delivery_data_user_id")
+                    .table(UserDeliveryData::Table)
+                    .to_owned(),
+            )
+            .await?;
+        // Only one address per user fits the old schema; the defaults stay.
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM user_delivery_data WHERE NOT is_default")
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .drop_column(UserDeliveryData::Label)
+                    .drop_column(UserDeliveryData::IsDefault)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE user_delivery_data ADD CONSTRAINT user_delivery_data_user_id_key UNIQUE (user_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    UserId,
+    Label,
+    IsDefault,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows keep NULLs and plaintext until the re-encryption job
+        // picks them up.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .add_column(ColumnDef::new(UserDeliveryData::EncryptionKeyId).string_len(32))
+                    .add_column(ColumnDef::new(UserDeliveryData::WrappedDataKey).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_delivery_data_encryption_key_id")
+                    .table(UserDeliveryData::Table)
+                    .col(UserDeliveryData::EncryptionKeyId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Encrypted values cannot be restored to plaintext here; decrypt them
+        // with the application before rolling back.
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_delivery_data_encryption_key_id")
+                    .table(UserDeliveryData::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .drop_column(UserDeliveryData::EncryptionKeyId)
+                    .drop_column(UserDeliveryData::WrappedDataKey)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    EncryptionKeyId,
+    WrappedDataKey,
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows stay as typed until the user edits the address.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .add_column(ColumnDef::new(UserDeliveryData::JibunAddress).string())
+                    .add_column(ColumnDef::new(UserDeliveryData::BuildingCode).string_len(25))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .drop_column(UserDeliveryData::JibunAddress)
+                    .drop_column(UserDeliveryData::BuildingCode)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    JibunAddress,
+    BuildingCode,
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing addresses get coordinates the next time they are edited
+        // or used for routing.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .add_column(ColumnDef::new(UserDeliveryData::Latitude).double())
+                    .add_column(ColumnDef::new(UserDeliveryData::Longitude).double())
+                    .to_owned(),
+            )
+            .await?;
+
+        // place_parent is not created by these migrations, so only touch it
+        // where it exists. The geocoding job fills the new columns.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE IF EXISTS place_parent \
+                 ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION, \
+                 ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE IF EXISTS place_parent \
+                 DROP COLUMN IF EXISTS latitude, \
+                 DROP COLUMN IF EXISTS longitude",
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserDeliveryData::Table)
+                    .drop_column(UserDeliveryData::Latitude)
+                    .drop_column(UserDeliveryData::Longitude)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    Latitude,
+    Longitude,
+}
@@ -0,0 +1,231 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Shipments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Shipments::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Shipments::UserId).integer().not_null())
+                    .col(ColumnDef::new(Shipments::DeliveryId).integer())
+                    // place_parent is not created by these migrations, so no
+                    // foreign key for the center.
+                    .col(ColumnDef::new(Shipments::PlaceId).integer())
+                    .col(ColumnDef::new(Shipments::Carrier).string().not_null())
+                    .col(
+                        ColumnDef::new(Shipments::TrackingNumber)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Shipments::Status)
+                            .string()
+                            .not_null()
+                            .default("INFO_RECEIVED"),
+                    )
+                    .col(ColumnDef::new(Shipments::LastEventAt).timestamp())
+                    .col(ColumnDef::new(Shipments::DeliveredAt).timestamp())
+                    .col(ColumnDef::new(Shipments::LastPolledAt).timestamp())
+                    .col(ColumnDef::new(Shipments::RegisteredBy).integer())
+                    .col(
+                        ColumnDef::new(Shipments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Shipments::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shipments_user")
+                            .from(Shipments::Table, Shipments::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shipments_delivery")
+                            .from(Shipments::Table, Shipments::DeliveryId)
+                            .to(UserDeliveryData::Table, UserDeliveryData::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shipments_registered_by")
+                            .from(Shipments::Table, Shipments::RegisteredBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Webhooks look parcels up by carrier and number.
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_shipments_carrier_tracking_number")
+                    .table(Shipments::Table)
+                    .col(Shipments::Carrier)
+                    .col(Shipments::TrackingNumber)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_shipments_user_id")
+                    .table(Shipments::Table)
+                    .col(Shipments::UserId)
+                    .to_owned(),
+            )
+            .await?;
+        // The poller walks undelivered parcels by id.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_shipments_pollable \
+                 ON shipments (carrier, id) WHERE status <> 'DELIVERED'",
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShipmentEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShipmentEvents::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ShipmentEvents::ShipmentId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ShipmentEvents::Status).string().not_null())
+                    .col(
+                        ColumnDef::new(ShipmentEvents::CarrierStatus)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ShipmentEvents::Location).string())
+                    .col(
+                        ColumnDef::new(ShipmentEvents::OccurredAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ShipmentEvents::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_shipment_events_shipment")
+                            .from(ShipmentEvents::Table, ShipmentEvents::ShipmentId)
+                            .to(Shipments::Table, Shipments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A scan pushed and then polled is stored once.
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_shipment_events_dedup")
+                    .table(ShipmentEvents::Table)
+                    .col(ShipmentEvents::ShipmentId)
+                    .col(ShipmentEvents::OccurredAt)
+                    .col(ShipmentEvents::CarrierStatus)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShipmentEvents::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Shipments::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Shipments {
+    Table,
+    Id,
+    UserId,
+    DeliveryId,
+    PlaceId,
+    Carrier,
+    TrackingNumber,
+    Status,
+    LastEventAt,
+    DeliveredAt,
+    LastPolledAt,
+    RegisteredBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShipmentEvents {
+    Table,
+    Id,
+    ShipmentId,
+    Status,
+    CarrierStatus,
+    Location,
+    OccurredAt,
+    CreatedAt,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeliveryAssignments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DeliveryAssignments::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DeliveryAssignments::DeliveryId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeliveryAssignments::StaffId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DeliveryAssignments::AssignedBy).integer())
+                    .col(
+                        ColumnDef::new(DeliveryAssignments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_delivery_assignments_delivery")
+                            .from(DeliveryAssignments::Table, DeliveryAssignments::DeliveryId)
+                            .to(UserDeliveryData::Table, UserDeliveryData::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_delivery_assignments_staff")
+                            .from(DeliveryAssignments::Table, DeliveryAssignments::StaffId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_delivery_assignments_assigned_by")
+                            .from(DeliveryAssignments::Table, DeliveryAssignments::AssignedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_delivery_assignments_delivery_staff")
+                    .table(DeliveryAssignments::Table)
+                    .col(DeliveryAssignments::DeliveryId)
+                    .col(DeliveryAssignments::StaffId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeliveryAssignments::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserDeliveryData {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DeliveryAssignments {
+    Table,
+    Id,
+    DeliveryId,
+    StaffId,
+    AssignedBy,
+    CreatedAt,
+}
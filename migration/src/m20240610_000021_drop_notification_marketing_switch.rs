@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Marketing permission comes from the MARKETING_* terms consents, so
+        // a second switch here could only ever disagree with them.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserNotificationSettings::Table)
+                    .drop_column(UserNotificationSettings::MarketingEnabled)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserNotificationSettings::Table)
+                    .add_column(
+                        ColumnDef::new(UserNotificationSettings::MarketingEnabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserNotificationSettings {
+    Table,
+    MarketingEnabled,
+}
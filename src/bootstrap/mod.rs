@@ -22,6 +22,19 @@ pub async fn create_app_state(config: &Config) -> AppState {
 
     let auth_registry = services::init_auth_registry(config);
     let email_provider = services::init_email_provider(config);
+    let storage = services::init_storage(config);
+    tracing::info!("File storage backend: {}", config.storage_backend);
+    let field_cipher = services::init_field_cipher(config);
+    tracing::info!(
+        "Field encryption active key: {}",
+        field_cipher.active_key_id()
+    );
+    let address_lookup = services::init_address_lookup(config);
+    tracing::info!("Address lookup backend: {}", config.address_lookup_backend);
+    let geocoder = services::init_geocoder(config);
+    tracing::info!("Geocoder backend: {}", config.geocoder_backend);
+    let carriers = services::init_carriers(config);
+    tracing::info!("Carrier backend: {}", config.carrier_backend);
 
     AppState {
         config: Arc::new(config.clone()),
@@ -29,5 +42,10 @@ pub async fn create_app_state(config: &Config) -> AppState {
         repo_manager,
         email_provider,
         redis_pool,
+        storage,
+        field_cipher,
+        address_lookup,
+        geocoder,
+        carriers,
     }
 }
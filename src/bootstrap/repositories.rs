@@ -19,9 +19,38 @@ pub async fn init_repo_manager(
         let user_repo =
             crate::modules::users::infra::persistence::InMemoryUserRepository::default();
 
+        let impersonation_audit_repo =
+            crate::modules::admin::infra::persistence::InMemoryImpersonationAuditRepository::default();
+
         manager.register::<Arc<dyn crate::modules::users::repository::UserRepository>>(Arc::new(
             user_repo,
         ));
+        manager
+            .register::<Arc<dyn crate::modules::admin::repository::ImpersonationAuditRepository>>(
+                Arc::new(impersonation_audit_repo),
+            );
+        manager.register::<Arc<dyn crate::modules::terms::repository::TermsRepository>>(Arc::new(
+            crate::modules::terms::infra::persistence::InMemoryTermsRepository::default(),
+        ));
+        manager
+            .register::<Arc<dyn crate::modules::moderation::repository::ModerationRepository>>(
+                Arc::new(
+                    crate::modules::moderation::infra::persistence::InMemoryModerationRepository::default(),
+                ),
+            );
+        manager.register::<Arc<dyn crate::modules::delivery::repository::DeliveryRepository>>(
+            Arc::new(
+                crate::modules::delivery::infra::persistence::InMemoryDeliveryRepository::default(),
+            ),
+        );
+        manager.register::<Arc<dyn crate::modules::place::repository::PlaceRepository>>(Arc::new(
+            crate::modules::place::infra::persistence::InMemoryPlaceRepository::default(),
+        ));
+        manager.register::<Arc<dyn crate::modules::shipment::repository::ShipmentRepository>>(
+            Arc::new(
+                crate::modules::shipment::infra::persistence::InMemoryShipmentRepository::default(),
+            ),
+        );
 
         Arc::new(manager) as Arc<dyn RepositoryManager>
     } else {
@@ -38,9 +67,41 @@ pub async fn init_repo_manager(
         manager.register::<Arc<dyn crate::modules::users::repository::UserRepository>>(Arc::new(
             user_repo,
         ));
+        let impersonation_audit_repo =
+            crate::modules::admin::infra::persistence::PostgresImpersonationAuditRepository::new(
+                db.clone(),
+            );
         manager.register::<Arc<dyn crate::modules::delivery::repository::DeliveryRepository>>(
             Arc::new(delivery_repo),
         );
+        manager
+            .register::<Arc<dyn crate::modules::admin::repository::ImpersonationAuditRepository>>(
+                Arc::new(impersonation_audit_repo),
+            );
+        let terms_repo =
+            crate::modules::terms::infra::persistence::PostgresTermsRepository::new(db.clone());
+        manager.register::<Arc<dyn crate::modules::terms::repository::TermsRepository>>(Arc::new(
+            terms_repo,
+        ));
+        let moderation_repo =
+            crate::modules::moderation::infra::persistence::PostgresModerationRepository::new(
+                db.clone(),
+            );
+        manager.register::<Arc<dyn crate::modules::moderation::repository::ModerationRepository>>(
+            Arc::new(moderation_repo),
+        );
+        let place_repo =
+            crate::modules::place::infra::persistence::PostgresPlaceRepository::new(db.clone());
+        manager.register::<Arc<dyn crate::modules::place::repository::PlaceRepository>>(Arc::new(
+            place_repo,
+        ));
+        let shipment_repo =
+            crate::modules::shipment::infra::persistence::PostgresShipmentRepository::new(
+                db.clone(),
+            );
+        manager.register::<Arc<dyn crate::modules::shipment::repository::ShipmentRepository>>(
+            Arc::new(shipment_repo),
+        );
 
         Arc::new(manager) as Arc<dyn RepositoryManager>
     }
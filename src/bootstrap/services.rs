@@ -1,12 +1,25 @@
 use crate::modules::auth::{
     providers::{
         email::{EmailProvider, GmailProvider},
+        http::{ProviderHttpClient, ProviderHttpConfig},
         kakao::KakaoProvider,
     },
     registry::OAuthProviderRegistry,
 };
+use crate::modules::delivery::lookup::{
+    AddressLookup, FixtureAddressLookup, JusoAddressLookup, JusoConfig,
+};
+use crate::modules::shipment::carriers::{
+    CarrierRegistry, MockCarrierAdapter, parse_webhook_secrets,
+};
+use crate::modules::shipment::entities::shipment::Carrier;
 use crate::modules::users::entities::social::SocialProvider;
 use crate::shared::config::Config;
+use crate::shared::crypto::FieldCipher;
+use crate::shared::geocoding::{FixtureGeocoder, Geocoder, KakaoGeocoder, KakaoGeocoderConfig};
+use crate::shared::storage::{
+    FileStorage, LOCAL_FILES_ROUTE, LocalFileStorage, S3Config, S3FileStorage,
+};
 use std::sync::Arc;
 
 pub fn init_auth_registry(config: &Config) -> OAuthProviderRegistry {
@@ -15,6 +28,9 @@ pub fn init_auth_registry(config: &Config) -> OAuthProviderRegistry {
         KakaoProvider::new(
             config.kakao_client_id.clone(),
             config.kakao_redirect_uri.clone(),
+            config.kakao_auth_base_url.clone(),
+            config.kakao_api_base_url.clone(),
+            ProviderHttpClient::new("kakao", ProviderHttpConfig::from_config(config)),
         ),
     )
 }
@@ -22,3 +38,64 @@ pub fn init_auth_registry(config: &Config) -> OAuthProviderRegistry {
 pub fn init_email_provider(config: &Config) -> Arc<dyn EmailProvider> {
     Arc::new(GmailProvider::new(config))
 }
+
+/// `STORAGE_BACKEND=s3` selects the S3-compatible backend, anything else the local one.
+pub fn init_storage(config: &Config) -> Arc<dyn FileStorage> {
+    match config.storage_backend.as_str() {
+        "s3" => Arc::new(S3FileStorage::new(S3Config::from_config(config))),
+        _ => Arc::new(LocalFileStorage::new(
+            config.storage_local_root.clone(),
+            format!(
+                "{}{}",
+                config.public_base_url.trim_end_matches('/'),
+                LOCAL_FILES_ROUTE
+            ),
+        )),
+    }
+}
+
+pub fn init_field_cipher(config: &Config) -> Arc<FieldCipher> {
+    Arc::new(FieldCipher::from_config(config))
+}
+
+/// `ADDRESS_LOOKUP_BACKEND=fixture` serves built-in sample addresses, anything
+/// else queries the Juso API.
+pub fn init_address_lookup(config: &Config) -> Arc<dyn AddressLookup> {
+    match config.address_lookup_backend.as_str() {
+        "fixture" => Arc::new(FixtureAddressLookup::default()),
+        _ => Arc::new(JusoAddressLookup::new(JusoConfig::from_config(config))),
+    }
+}
+
+/// `GEOCODER_BACKEND=fixture` uses built-in sample coordinates, anything else
+/// Kakao Local.
+pub fn init_geocoder(config: &Config) -> Arc<dyn Geocoder> {
+    match config.geocoder_backend.as_str() {
+        "fixture" => Arc::new(FixtureGeocoder::default()),
+        _ => Arc::new(KakaoGeocoder::new(KakaoGeocoderConfig::from_config(config))),
+    }
+}
+
+/// `CARRIER_BACKEND=mock` registers mock adapters for CJ대한통운, 한진 and
+/// 롯데, and is refused outside dev and test. Until their tracking APIs are
+/// contracted any other value registers nothing, so shipments cannot be
+/// created. A carrier with a webhook secret pushes updates; the others are
+/// polled.
+pub fn init_carriers(config: &Config) -> CarrierRegistry {
+    match config.carrier_backend.as_str() {
+        "mock" => {
+            assert!(
+                matches!(config.app_env.as_str(), "dev" | "test"),
+                "CARRIER_BACKEND=mock is only allowed in dev and test"
+            );
+            let mut secrets = parse_webhook_secrets(&config.carrier_webhook_secrets)
+                .expect("CARRIER_WEBHOOK_SECRETS is invalid");
+            [Carrier::CjLogistics, Carrier::Hanjin, Carrier::Lotte]
+                .into_iter()
+                .fold(CarrierRegistry::new(), |registry, carrier| {
+                    registry.register(MockCarrierAdapter::new(carrier, secrets.remove(&carrier)))
+                })
+        }
+        _ => CarrierRegistry::new(),
+    }
+}
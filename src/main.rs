@@ -1,8 +1,9 @@
 use axum::{Router, routing::get};
 use gimme_backend::shared::handlers::{handler_404, handler_500};
+use gimme_backend::shared::storage::LOCAL_FILES_ROUTE;
 use gimme_backend::{bootstrap, modules, shared::config::Config};
 use std::net::SocketAddr;
-use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::{catch_panic::CatchPanicLayer, services::ServeDir};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -21,12 +22,52 @@ async fn main() {
     // Bootstrap AppState
     let app_state = bootstrap::create_app_state(&config).await;
 
+    // Background jobs
+    modules::users::dormancy::DormancyService::spawn(app_state.repo_manager.clone());
+    modules::delivery::reencryption::ReencryptionService::spawn(
+        app_state.repo_manager.clone(),
+        app_state.field_cipher.clone(),
+    );
+    modules::place::distance::DistanceService::spawn(
+        app_state.repo_manager.clone(),
+        app_state.geocoder.clone(),
+    );
+    modules::shipment::tracking::TrackingPoller::spawn(
+        app_state.repo_manager.clone(),
+        app_state.carriers.clone(),
+    );
+
     // Initialize router
     // Aggregate routes from modules
     let app = Router::new()
         .route("/health", get(|| async { "OK" }))
-        .nest("/users", modules::users::router::router(app_state.clone()))
-        .nest("/auth", modules::auth::router::router(app_state))
+        .nest(
+            "/users",
+            modules::users::router::router(app_state.clone())
+                .merge(modules::profile::router::router(app_state.clone())),
+        )
+        .nest(
+            "/users/me/addresses",
+            modules::delivery::router::router(app_state.clone()),
+        )
+        .nest(
+            "/shipments",
+            modules::shipment::router::router(app_state.clone()),
+        )
+        .nest("/auth", modules::auth::router::router(app_state.clone()))
+        .nest("/terms", modules::terms::router::router(app_state.clone()))
+        .nest(
+            "/moderation",
+            modules::moderation::router::router(app_state.clone()),
+        )
+        .nest("/admin", modules::admin::router::router(app_state));
+    // With the local storage backend this process also serves the files.
+    let app = if config.storage_backend == "s3" {
+        app
+    } else {
+        app.nest_service(LOCAL_FILES_ROUTE, ServeDir::new(&config.storage_local_root))
+    };
+    let app = app
         .layer(CatchPanicLayer::custom(handler_500))
         .fallback(handler_404);
 
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One row per impersonation token issued to support staff.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "impersonation_audit_logs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub session_id: String,
+    pub actor_user_id: i32,
+    pub target_user_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub reason: String,
+    pub issued_at: DateTime,
+    pub expires_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::ActorUserId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Actor,
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::TargetUserId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Target,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,382 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use serde::Deserialize;
+
+use super::repository::ImpersonationAuditRepository;
+use super::service::ImpersonationService;
+use crate::modules::delivery::entities::delivery_assignment;
+use crate::modules::delivery::repository::DeliveryRepository;
+use crate::modules::delivery::service::DeliveryService;
+use crate::modules::moderation::entities::user_report::ReportStatus;
+use crate::modules::moderation::handlers::ReportResponse;
+use crate::modules::moderation::repository::ModerationRepository;
+use crate::modules::moderation::service::ReportService;
+use crate::modules::terms::entities::terms_document::{self, TermsKind};
+use crate::modules::terms::repository::TermsRepository;
+use crate::modules::terms::service::TermsService;
+use crate::modules::users::dtos::{
+    SortDirection, UserCursor, UserSearchFilter, UserSearchQuery, UserSortField,
+};
+use crate::modules::users::entities::status_transition;
+use crate::modules::users::entities::{enums::AccountStatus, user};
+use crate::modules::users::handlers::{UserProjection, UserResponse};
+use crate::modules::users::referral::{ReferralReport, ReferralService};
+use crate::modules::users::repository::UserRepository;
+use crate::modules::users::status::{AccountStatusService, StatusActor, StatusChange};
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct StartImpersonationRequest {
+    pub target_uuid: String,
+    pub reason: String,
+}
+
+pub async fn start_impersonation(
+    State(state): State<AppState>,
+    Extension(admin): Extension<user::Model>,
+    Json(body): Json<StartImpersonationRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let audit_repo = state
+        .repo_manager
+        .get::<Arc<dyn ImpersonationAuditRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ImpersonationAuditRepository not registered".to_string(),
+        ))?;
+
+    let (token, session) = ImpersonationService::start(
+        user_repo.as_ref(),
+        audit_repo.as_ref(),
+        &state.config,
+        &admin,
+        &body.target_uuid,
+        &body.reason,
+    )
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "token": token,
+        "token_type": "Bearer",
+        "session_id": session.session_id,
+        "expires_at": session.expires_at,
+        "read_only": true,
+    })))
+}
+
+const USER_SEARCH_DEFAULT_LIMIT: u64 = 20;
+const USER_SEARCH_MAX_LIMIT: u64 = 100;
+
+#[derive(Deserialize)]
+pub struct UserSearchParams {
+    pub account_status: Option<AccountStatus>,
+    pub provider: Option<String>,
+    pub email_verified: Option<bool>,
+    pub phone_verified: Option<bool>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub created_from: Option<chrono::NaiveDateTime>,
+    pub created_to: Option<chrono::NaiveDateTime>,
+    pub last_login_from: Option<chrono::NaiveDateTime>,
+    pub last_login_to: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub sort: UserSortField,
+    #[serde(default)]
+    pub direction: SortDirection,
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+}
+
+impl UserSearchParams {
+    fn into_query(self) -> AppResult<UserSearchQuery> {
+        let provider = self
+            .provider
+            .map(|p| p.parse())
+            .transpose()
+            .map_err(AppError::BadRequest)?;
+        let cursor = self
+            .cursor
+            .map(|c| {
+                UserCursor::decode(&c).ok_or(AppError::BadRequest("Invalid cursor".to_string()))
+            })
+            .transpose()?;
+        let non_empty = |s: Option<String>| s.filter(|s| !s.trim().is_empty());
+
+        Ok(UserSearchQuery {
+            filter: UserSearchFilter {
+                account_status: self.account_status,
+                provider,
+                email_verified: self.email_verified,
+                phone_verified: self.phone_verified,
+                email: non_empty(self.email),
+                phone_number: non_empty(self.phone_number),
+                created_from: self.created_from,
+                created_to: self.created_to,
+                last_login_from: self.last_login_from,
+                last_login_to: self.last_login_to,
+            },
+            sort: self.sort,
+            direction: self.direction,
+            limit: self
+                .limit
+                .unwrap_or(USER_SEARCH_DEFAULT_LIMIT)
+                .clamp(1, USER_SEARCH_MAX_LIMIT),
+            cursor,
+        })
+    }
+}
+
+/// Support-facing user listing. Ranges are inclusive of `*_from` and
+/// exclusive of `*_to`; `next_cursor` is `null` on the last page.
+pub async fn search_users(
+    State(state): State<AppState>,
+    Query(params): Query<UserSearchParams>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let page = user_repo.search_users(&params.into_query()?).await?;
+    let items: Vec<UserResponse> = page
+        .items
+        .into_iter()
+        .map(|u| UserResponse::project(u, UserProjection::Private, state.storage.as_ref()))
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "items": items,
+        "next_cursor": page.next_cursor,
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct PublishTermsRequest {
+    pub kind: TermsKind,
+    pub title: String,
+    pub content_url: String,
+    pub required: bool,
+    /// Defaults to now. A future date schedules the version.
+    pub effective_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn publish_terms(
+    State(state): State<AppState>,
+    Json(body): Json<PublishTermsRequest>,
+) -> AppResult<Json<terms_document::Model>> {
+    let terms_repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+
+    let document = TermsService::publish(
+        terms_repo.as_ref(),
+        body.kind,
+        &body.title,
+        &body.content_url,
+        body.required,
+        body.effective_at,
+    )
+    .await?;
+
+    Ok(Json(document))
+}
+
+#[derive(Deserialize)]
+pub struct ReportQueueParams {
+    /// Defaults to the open queue.
+    pub status: Option<ReportStatus>,
+    pub after: Option<i32>,
+    pub limit: Option<u64>,
+}
+
+/// Report triage queue, oldest first. Pass the last `id` as `after` for the next page.
+pub async fn list_reports(
+    State(state): State<AppState>,
+    Query(params): Query<ReportQueueParams>,
+) -> AppResult<Json<Vec<ReportResponse>>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let reports = ReportService::queue(
+        repo.as_ref(),
+        Some(params.status.unwrap_or(ReportStatus::Open)),
+        params.after,
+        params.limit,
+    )
+    .await?;
+    let mut items = Vec::with_capacity(reports.len());
+    for report in reports {
+        items.push(ReportResponse::build(user_repo.as_ref(), report).await?);
+    }
+
+    Ok(Json(items))
+}
+
+#[derive(Deserialize)]
+pub struct ResolveReportRequest {
+    pub status: ReportStatus,
+    pub note: Option<String>,
+}
+
+pub async fn resolve_report(
+    State(state): State<AppState>,
+    Extension(admin): Extension<user::Model>,
+    Path(id): Path<i32>,
+    Json(body): Json<ResolveReportRequest>,
+) -> AppResult<Json<ReportResponse>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let report =
+        ReportService::resolve(repo.as_ref(), id, admin.id, body.status, body.note).await?;
+
+    Ok(Json(
+        ReportResponse::build(user_repo.as_ref(), report).await?,
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct ChangeAccountStatusRequest {
+    pub status: AccountStatus,
+    pub reason: String,
+    /// Required for `BANNED`.
+    pub banned_until: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn change_account_status(
+    State(state): State<AppState>,
+    Extension(admin): Extension<user::Model>,
+    Path(uuid): Path<String>,
+    Json(body): Json<ChangeAccountStatusRequest>,
+) -> AppResult<Json<UserResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let target = user_repo
+        .find_with_details_by_uuid(&uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if target.id == admin.id {
+        return Err(AppError::BadRequest(
+            "Admins cannot change their own status".to_string(),
+        ));
+    }
+
+    let uow = state.repo_manager.begin().await?;
+    let tx_user_repo = user_repo
+        .with_transaction(&*uow)
+        .ok_or(AppError::InternalServerError(
+            "Failed to start transaction for user repo".to_string(),
+        ))?;
+    let updated = match AccountStatusService::transition(
+        tx_user_repo.as_ref(),
+        target,
+        StatusChange {
+            banned_until: body.banned_until,
+            ..StatusChange::new(body.status, StatusActor::Admin(admin.id), body.reason)
+        },
+    )
+    .await
+    {
+        Ok(u) => u,
+        Err(e) => {
+            uow.rollback().await?;
+            return Err(e);
+        }
+    };
+    uow.commit().await?;
+
+    Ok(Json(UserResponse::project(
+        updated,
+        UserProjection::Private,
+        state.storage.as_ref(),
+    )))
+}
+
+pub async fn get_status_history(
+    State(state): State<AppState>,
+    Path(uuid): Path<String>,
+) -> AppResult<Json<Vec<status_transition::Model>>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let target = user_repo
+        .find_by_uuid(&uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(user_repo.find_status_transitions(target.id).await?))
+}
+
+#[derive(Deserialize)]
+pub struct ReferralReportParams {
+    pub from: chrono::NaiveDateTime,
+    pub to: chrono::NaiveDateTime,
+}
+
+/// Conversion numbers for referrals attributed in `[from, to)`.
+pub async fn referral_report(
+    State(state): State<AppState>,
+    Query(params): Query<ReferralReportParams>,
+) -> AppResult<Json<ReferralReport>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    Ok(Json(
+        ReferralService::report(user_repo.as_ref(), params.from, params.to).await?,
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct AssignDeliveryRequest {
+    pub staff_uuid: String,
+}
+
+/// Lets a fulfillment staff member handle a delivery, including reading its
+/// decrypted address.
+pub async fn assign_delivery(
+    State(state): State<AppState>,
+    Extension(admin): Extension<user::Model>,
+    Path(delivery_id): Path<i32>,
+    Json(body): Json<AssignDeliveryRequest>,
+) -> AppResult<Json<delivery_assignment::Model>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let delivery_repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let staff = user_repo
+        .find_by_uuid(&body.staff_uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let assignment =
+        DeliveryService::assign_staff(delivery_repo.as_ref(), &admin, delivery_id, &staff).await?;
+
+    Ok(Json(assignment))
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use sea_orm::*;
+use std::sync::{Arc, Mutex};
+
+use crate::impl_sea_orm_repo;
+use crate::modules::admin::entities::impersonation_audit;
+use crate::modules::admin::repository::ImpersonationAuditRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
+use crate::shared::repository::UnitOfWork;
+
+// =========================================================================
+// Postgres Implementation
+// =========================================================================
+
+pub type PostgresImpersonationAuditRepository = SeaOrmRepository<impersonation_audit::Entity>;
+
+impl_sea_orm_repo!(
+    PostgresImpersonationAuditRepository,
+    ImpersonationAuditRepository,
+    {
+        async fn create_session(
+            &self,
+            session: impersonation_audit::ActiveModel,
+        ) -> AppResult<impersonation_audit::Model> {
+            match &self.conn {
+                DbOrTxn::Conn(c) => session.insert(c.as_ref()).await.map_err(AppError::DbError),
+                DbOrTxn::Txn(mutex) => {
+                    let lock = mutex.lock().await;
+                    let txn = lock.as_ref().expect("Active txn");
+                    session.insert(txn).await.map_err(AppError::DbError)
+                }
+            }
+        }
+
+        async fn find_by_session_id(
+            &self,
+            session_id: &str,
+        ) -> AppResult<Option<impersonation_audit::Model>> {
+            let query = impersonation_audit::Entity::find()
+                .filter(impersonation_audit::Column::SessionId.eq(session_id));
+            match &self.conn {
+                DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+                DbOrTxn::Txn(mutex) => {
+                    let lock = mutex.lock().await;
+                    let txn = lock.as_ref().expect("Active txn");
+                    query.one(txn).await.map_err(AppError::DbError)
+                }
+            }
+        }
+    }
+);
+
+// =========================================================================
+// InMemory Implementation
+// =========================================================================
+
+#[derive(Clone, Default)]
+pub struct InMemoryImpersonationAuditRepository {
+    sessions: Arc<Mutex<Vec<impersonation_audit::Model>>>,
+}
+
+#[async_trait]
+impl ImpersonationAuditRepository for InMemoryImpersonationAuditRepository {
+    async fn create_session(
+        &self,
+        session: impersonation_audit::ActiveModel,
+    ) -> AppResult<impersonation_audit::Model> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let model = impersonation_audit::Model {
+            id: sessions.len() as i32 + 1,
+            session_id: session.session_id.unwrap(),
+            actor_user_id: session.actor_user_id.unwrap(),
+            target_user_id: session.target_user_id.unwrap(),
+            reason: session.reason.unwrap(),
+            issued_at: session.issued_at.unwrap(),
+            expires_at: session.expires_at.unwrap(),
+        };
+        sessions.push(model.clone());
+        Ok(model)
+    }
+
+    async fn find_by_session_id(
+        &self,
+        session_id: &str,
+    ) -> AppResult<Option<impersonation_audit::Model>> {
+        let sessions = self.sessions.lock().unwrap();
+        Ok(sessions
+            .iter()
+            .find(|s| s.session_id == session_id)
+            .cloned())
+    }
+
+    fn with_transaction(
+        &self,
+        _uow: &dyn UnitOfWork,
+    ) -> Option<Box<dyn ImpersonationAuditRepository>> {
+        Some(Box::new(self.clone()))
+    }
+}
@@ -0,0 +1,14 @@
+use super::entities::impersonation_audit;
+use crate::shared::error::AppResult;
+
+crate::define_repo!(ImpersonationAuditRepository, {
+    async fn create_session(
+        &self,
+        session: impersonation_audit::ActiveModel,
+    ) -> AppResult<impersonation_audit::Model>;
+
+    async fn find_by_session_id(
+        &self,
+        session_id: &str,
+    ) -> AppResult<Option<impersonation_audit::Model>>;
+});
@@ -0,0 +1,26 @@
+use super::handlers;
+use crate::shared::{middleware::require_admin, state::AppState};
+use axum::{
+    Router, middleware,
+    routing::{get, patch, post},
+};
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/users", get(handlers::search_users))
+        .route("/referrals/report", get(handlers::referral_report))
+        .route(
+            "/users/:uuid/status",
+            get(handlers::get_status_history).post(handlers::change_account_status),
+        )
+        .route("/impersonations", post(handlers::start_impersonation))
+        .route("/terms", post(handlers::publish_terms))
+        .route("/reports", get(handlers::list_reports))
+        .route("/reports/:id", patch(handlers::resolve_report))
+        .route(
+            "/deliveries/:id/assignments",
+            post(handlers::assign_delivery),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+        .with_state(state)
+}
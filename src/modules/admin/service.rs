@@ -0,0 +1,82 @@
+use chrono::{Duration, Utc};
+use sea_orm::ActiveValue::Set;
+
+use super::entities::impersonation_audit;
+use super::repository::ImpersonationAuditRepository;
+use crate::modules::auth::service::{ActorClaim, AuthService};
+use crate::modules::users::entities::{enums::UserRole, user};
+use crate::modules::users::repository::UserRepository;
+use crate::shared::config::Config;
+use crate::shared::error::{AppError, AppResult};
+
+/// Impersonation tokens expire after 15 minutes.
+pub const IMPERSONATION_TTL_MINUTES: i64 = 15;
+
+pub struct ImpersonationService;
+
+impl ImpersonationService {
+    /// Records an audit row for `actor` acting as `target_uuid` and returns
+    /// the read-only token for that session.
+    pub async fn start(
+        user_repo: &dyn UserRepository,
+        audit_repo: &dyn ImpersonationAuditRepository,
+        config: &Config,
+        actor: &user::Model,
+        target_uuid: &str,
+        reason: &str,
+    ) -> AppResult<(String, impersonation_audit::Model)> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::BadRequest(
+                "Impersonation reason is required".to_string(),
+            ));
+        }
+
+        let target = user_repo
+            .find_by_uuid(target_uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if target.role == UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Admin accounts cannot be impersonated".to_string(),
+            ));
+        }
+
+        let issued_at = Utc::now();
+        let expires_at = issued_at + Duration::minutes(IMPERSONATION_TTL_MINUTES);
+        let session_id = uuid::Uuid::new_v4().to_string();
+
+        // Audit first: no token leaves the server without a matching row.
+        let session = audit_repo
+            .create_session(impersonation_audit::ActiveModel {
+                session_id: Set(session_id.clone()),
+                actor_user_id: Set(actor.id),
+                target_user_id: Set(target.id),
+                reason: Set(reason.to_string()),
+                issued_at: Set(issued_at.naive_utc()),
+                expires_at: Set(expires_at.naive_utc()),
+                ..Default::default()
+            })
+            .await?;
+
+        tracing::info!(
+            "Impersonation session {} started by {} for {}",
+            session_id,
+            actor.uuid,
+            target.uuid
+        );
+
+        let token = AuthService::generate_impersonation_jwt(
+            config,
+            &target.uuid,
+            ActorClaim {
+                sub: actor.uuid.clone(),
+                name: actor.username.clone(),
+                sid: session_id,
+            },
+            expires_at,
+        )?;
+
+        Ok((token, session))
+    }
+}
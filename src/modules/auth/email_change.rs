@@ -0,0 +1,542 @@
+use sea_orm::ActiveValue::Set;
+use serde::{Deserialize, Serialize};
+
+use super::providers::email::EmailProvider;
+use super::verification::{EMAIL_VERIFICATION_TTL_SECS, EmailVerificationService};
+use crate::modules::users::entities::{enums::AccountStatus, user, verification};
+use crate::modules::users::repository::UserRepository;
+use crate::shared::config::Config;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::kv::ExpiringStore;
+use crate::shared::repository::RepositoryManager;
+
+/// The "this wasn't me" link stays valid for 7 days.
+pub const EMAIL_CHANGE_REVERT_TTL_SECS: u64 = 60 * 60 * 24 * 7;
+/// Wrong codes allowed before the pending change is dropped and a new code
+/// has to be requested.
+pub const EMAIL_CHANGE_MAX_ATTEMPTS: u64 = 5;
+
+/// The address a user asked to move to and the code mailed to it. Kept apart
+/// from the signup `verification:{email}` codes.
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingEmailChange {
+    new_email: String,
+    code: String,
+}
+
+/// Snapshot needed to undo a confirmed email change.
+#[derive(Debug, Serialize, Deserialize)]
+struct EmailChangeRevert {
+    user_uuid: String,
+    old_email: String,
+    old_email_verified_at: Option<chrono::NaiveDateTime>,
+    new_email: String,
+}
+
+pub struct EmailChangeService;
+
+impl EmailChangeService {
+    fn pending_key(user_uuid: &str) -> String {
+        format!("email_change:{}", user_uuid)
+    }
+
+    fn attempts_key(user_uuid: &str) -> String {
+        format!("email_change_attempts:{}", user_uuid)
+    }
+
+    fn revert_key(token: &str) -> String {
+        format!("email_change_revert:{}", token)
+    }
+
+    /// Sends a code to `new_email` and remembers it as the pending address.
+    pub async fn request(
+        repo: &dyn UserRepository,
+        store: &dyn ExpiringStore,
+        email_provider: &dyn EmailProvider,
+        user_uuid: &str,
+        new_email: &str,
+    ) -> AppResult<()> {
+        let user = repo
+            .find_by_uuid(user_uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        if user.account_status != AccountStatus::Active {
+            return Err(AppError::Forbidden(
+                "Only active accounts can change email".to_string(),
+            ));
+        }
+        if user.email == new_email {
+            return Err(AppError::BadRequest(
+                "New email is the same as the current one".to_string(),
+            ));
+        }
+        if repo.find_by_email(new_email).await?.is_some() {
+            return Err(AppError::Conflict("Email already in use".to_string()));
+        }
+
+        let pending = PendingEmailChange {
+            new_email: new_email.to_string(),
+            code: EmailVerificationService::new_code(),
+        };
+        let payload = serde_json::to_string(&pending)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        store
+            .set_ex(
+                &Self::pending_key(user_uuid),
+                &payload,
+                EMAIL_VERIFICATION_TTL_SECS,
+            )
+            .await?;
+        store.del(&Self::attempts_key(user_uuid)).await?;
+
+        email_provider
+            .send_verification_code(new_email, &pending.code)
+            .await
+    }
+
+    /// Checks the code sent to the pending address, then swaps `users.email`
+    /// and `email_verified_at` in one transaction and notifies the old address.
+    /// The code is only spent once the swap has committed. After
+    /// `EMAIL_CHANGE_MAX_ATTEMPTS` wrong codes the pending change is dropped.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confirm(
+        repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        store: &dyn ExpiringStore,
+        email_provider: &dyn EmailProvider,
+        config: &Config,
+        user_uuid: &str,
+        code: &str,
+    ) -> AppResult<user::Model> {
+        let payload =
+            store
+                .get(&Self::pending_key(user_uuid))
+                .await?
+                .ok_or(AppError::BadRequest(
+                    "No pending email change (or expired)".to_string(),
+                ))?;
+        let pending: PendingEmailChange = serde_json::from_str(&payload)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        // Counted before comparing, so parallel guesses can't outrun the limit.
+        let attempts = store
+            .incr(&Self::attempts_key(user_uuid), EMAIL_VERIFICATION_TTL_SECS)
+            .await?;
+        if attempts > EMAIL_CHANGE_MAX_ATTEMPTS
+            || (pending.code != code && attempts == EMAIL_CHANGE_MAX_ATTEMPTS)
+        {
+            store.del(&Self::pending_key(user_uuid)).await?;
+            store.del(&Self::attempts_key(user_uuid)).await?;
+            return Err(AppError::TooManyRequests(
+                "Too many invalid codes, request a new one".to_string(),
+            ));
+        }
+        if pending.code != code {
+            return Err(AppError::BadRequest(
+                "Invalid verification code".to_string(),
+            ));
+        }
+        let new_email = pending.new_email;
+
+        let user = repo
+            .find_with_details_by_uuid(user_uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if user.account_status != AccountStatus::Active {
+            return Err(AppError::Forbidden(
+                "Only active accounts can change email".to_string(),
+            ));
+        }
+        // The address may have been taken while the code was outstanding.
+        if repo.find_by_email(&new_email).await?.is_some() {
+            return Err(AppError::Conflict("Email already in use".to_string()));
+        }
+
+        let revert = EmailChangeRevert {
+            user_uuid: user.uuid.clone(),
+            old_email: user.email.clone(),
+            old_email_verified_at: user.verification.as_ref().and_then(|v| v.email_verified_at),
+            new_email: new_email.clone(),
+        };
+
+        let updated = Self::apply_email(
+            repo,
+            repo_manager,
+            user,
+            new_email.clone(),
+            Some(chrono::Utc::now().naive_utc()),
+        )
+        .await?;
+
+        store.del(&Self::pending_key(user_uuid)).await?;
+        store.del(&Self::attempts_key(user_uuid)).await?;
+
+        // Notify the previous address; failing to do so must not undo the change.
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let payload = serde_json::to_string(&revert)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        store
+            .set_ex(
+                &Self::revert_key(&token),
+                &payload,
+                EMAIL_CHANGE_REVERT_TTL_SECS,
+            )
+            .await?;
+
+        let revert_url = format!(
+            "{}/auth/email-change/revert?token={}",
+            config.public_base_url.trim_end_matches('/'),
+            token
+        );
+        if let Err(e) = email_provider
+            .send_email_change_notice(&revert.old_email, &new_email, &revert_url)
+            .await
+        {
+            tracing::warn!("Failed to send email change notice: {}", e);
+        }
+
+        Ok(updated)
+    }
+
+    /// Restores the previous address from a "this wasn't me" link. The token
+    /// is spent on first use, whatever the outcome.
+    pub async fn revert(
+        repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        store: &dyn ExpiringStore,
+        token: &str,
+    ) -> AppResult<user::Model> {
+        let payload = store
+            .take(&Self::revert_key(token))
+            .await?
+            .ok_or(AppError::BadRequest(
+                "Invalid or expired revert link".to_string(),
+            ))?;
+        let revert: EmailChangeRevert = serde_json::from_str(&payload)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        let user = repo
+            .find_with_details_by_uuid(&revert.user_uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if user.email != revert.new_email {
+            return Err(AppError::Conflict(
+                "Email has changed since this link was issued".to_string(),
+            ));
+        }
+
+        Self::apply_email(
+            repo,
+            repo_manager,
+            user,
+            revert.old_email,
+            revert.old_email_verified_at,
+        )
+        .await
+    }
+
+    async fn apply_email(
+        repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        user: user::Model,
+        email: String,
+        email_verified_at: Option<chrono::NaiveDateTime>,
+    ) -> AppResult<user::Model> {
+        let verification_model = user
+            .verification
+            .clone()
+            .ok_or(AppError::InternalServerError(
+                "Verification record missing".to_string(),
+            ))?;
+        let mut verification_active: verification::ActiveModel = verification_model.into();
+        verification_active.email_verified = Set(true);
+        verification_active.email_verified_at = Set(email_verified_at);
+
+        let mut user_active: user::ActiveModel = user.into();
+        user_active.email = Set(email);
+        user_active.updated_at = Set(chrono::Utc::now().naive_utc());
+
+        let uow = repo_manager.begin().await?;
+        let tx_user_repo = repo
+            .with_transaction(&*uow)
+            .ok_or(AppError::InternalServerError(
+                "Failed to start transaction for user repo".to_string(),
+            ))?;
+
+        let updated = match tx_user_repo.update_user(user_active).await {
+            Ok(u) => u,
+            Err(e) => {
+                uow.rollback().await?;
+                return Err(e);
+            }
+        };
+        if let Err(e) = tx_user_repo.update_verification(verification_active).await {
+            uow.rollback().await?;
+            return Err(e);
+        }
+        uow.commit().await?;
+
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::modules::users::infra::fixtures::sign_up;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::modules::users::status::{AccountStatusService, StatusActor, StatusChange};
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+    use crate::shared::kv::InMemoryExpiringStore;
+
+    /// Keeps the last code and revert URL instead of mailing them.
+    #[derive(Default)]
+    struct RecordingEmailProvider {
+        code: Mutex<Option<String>>,
+        revert_url: Mutex<Option<String>>,
+    }
+
+    impl RecordingEmailProvider {
+        fn code(&self) -> String {
+            self.code.lock().unwrap().clone().unwrap()
+        }
+
+        fn revert_token(&self) -> String {
+            let url = self.revert_url.lock().unwrap().clone().unwrap();
+            url.split("token=").nth(1).unwrap().to_string()
+        }
+    }
+
+    #[async_trait]
+    impl EmailProvider for RecordingEmailProvider {
+        async fn send_verification_code(&self, _to: &str, code: &str) -> AppResult<()> {
+            *self.code.lock().unwrap() = Some(code.to_string());
+            Ok(())
+        }
+
+        async fn send_email_change_notice(
+            &self,
+            _to: &str,
+            _new_email: &str,
+            revert_url: &str,
+        ) -> AppResult<()> {
+            *self.revert_url.lock().unwrap() = Some(revert_url.to_string());
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        repo: InMemoryUserRepository,
+        manager: InMemoryRepositoryManager,
+        store: InMemoryExpiringStore,
+        mail: RecordingEmailProvider,
+        config: Config,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                repo: InMemoryUserRepository::default(),
+                manager: InMemoryRepositoryManager::new(),
+                store: InMemoryExpiringStore::default(),
+                mail: RecordingEmailProvider::default(),
+                config: Config::for_test(),
+            }
+        }
+
+        async fn active_user(&self, provider_id: &str) -> user::Model {
+            let user = sign_up(&self.repo, provider_id).await;
+            AccountStatusService::transition(
+                &self.repo,
+                user,
+                StatusChange::new(AccountStatus::Active, StatusActor::System, "verified"),
+            )
+            .await
+            .unwrap()
+        }
+
+        async fn request(&self, user: &user::Model, new_email: &str) -> AppResult<()> {
+            EmailChangeService::request(&self.repo, &self.store, &self.mail, &user.uuid, new_email)
+                .await
+        }
+
+        async fn confirm(&self, user: &user::Model, code: &str) -> AppResult<user::Model> {
+            EmailChangeService::confirm(
+                &self.repo,
+                &self.manager,
+                &self.store,
+                &self.mail,
+                &self.config,
+                &user.uuid,
+                code,
+            )
+            .await
+        }
+
+        async fn revert(&self, token: &str) -> AppResult<user::Model> {
+            EmailChangeService::revert(&self.repo, &self.manager, &self.store, token).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_swaps_email_and_revert_restores_it_once() {
+        let fx = Fixture::new();
+        let user = fx.active_user("1").await;
+
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        let changed = fx.confirm(&user, &fx.mail.code()).await.unwrap();
+        assert_eq!(changed.email, "new@gimme.com");
+        assert!(
+            fx.repo
+                .find_by_email("1@gimme.com")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // The code is spent once the change has committed.
+        assert!(matches!(
+            fx.confirm(&user, &fx.mail.code()).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        let token = fx.mail.revert_token();
+        let restored = fx.revert(&token).await.unwrap();
+        assert_eq!(restored.email, "1@gimme.com");
+        assert!(matches!(
+            fx.revert(&token).await,
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            fx.revert("unknown").await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_or_expired_code_is_rejected() {
+        let fx = Fixture::new();
+        let user = fx.active_user("1").await;
+
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        let code = fx.mail.code();
+        let wrong = if code == "100000" { "100001" } else { "100000" };
+        assert!(matches!(
+            fx.confirm(&user, wrong).await,
+            Err(AppError::BadRequest(_))
+        ));
+        // A wrong guess leaves the pending change in place.
+        assert_eq!(
+            fx.confirm(&user, &code).await.unwrap().email,
+            "new@gimme.com"
+        );
+
+        fx.request(&user, "newer@gimme.com").await.unwrap();
+        // What Redis does once the TTL lapses.
+        fx.store
+            .del(&EmailChangeService::pending_key(&user.uuid))
+            .await
+            .unwrap();
+        assert!(matches!(
+            fx.confirm(&user, &fx.mail.code()).await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_wrong_codes_drop_the_pending_change() {
+        let fx = Fixture::new();
+        let user = fx.active_user("1").await;
+
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        let code = fx.mail.code();
+        let wrong = if code == "100000" { "100001" } else { "100000" };
+        for _ in 1..EMAIL_CHANGE_MAX_ATTEMPTS {
+            assert!(matches!(
+                fx.confirm(&user, wrong).await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+        assert!(matches!(
+            fx.confirm(&user, wrong).await,
+            Err(AppError::TooManyRequests(_))
+        ));
+        // The right code no longer helps; a new one has to be requested.
+        assert!(matches!(
+            fx.confirm(&user, &code).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        // A fresh request starts the count over.
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        let code = fx.mail.code();
+        let wrong = if code == "100000" { "100001" } else { "100000" };
+        assert!(matches!(
+            fx.confirm(&user, wrong).await,
+            Err(AppError::BadRequest(_))
+        ));
+        assert_eq!(
+            fx.confirm(&user, &code).await.unwrap().email,
+            "new@gimme.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conflict_keeps_the_code_usable() {
+        let fx = Fixture::new();
+        let user = fx.active_user("1").await;
+        let other = fx.active_user("2").await;
+
+        assert!(matches!(
+            fx.request(&user, &other.email).await,
+            Err(AppError::Conflict(_))
+        ));
+
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        let code = fx.mail.code();
+        // Someone else claims the address while the code is outstanding.
+        fx.request(&other, "new@gimme.com").await.unwrap();
+        fx.confirm(&other, &fx.mail.code()).await.unwrap();
+        assert!(matches!(
+            fx.confirm(&user, &code).await,
+            Err(AppError::Conflict(_))
+        ));
+        assert!(
+            fx.store
+                .get(&EmailChangeService::pending_key(&user.uuid))
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revert_is_refused_once_the_email_moved_on() {
+        let fx = Fixture::new();
+        let user = fx.active_user("1").await;
+
+        fx.request(&user, "new@gimme.com").await.unwrap();
+        fx.confirm(&user, &fx.mail.code()).await.unwrap();
+        let first_token = fx.mail.revert_token();
+
+        fx.request(&user, "newer@gimme.com").await.unwrap();
+        fx.confirm(&user, &fx.mail.code()).await.unwrap();
+
+        assert!(matches!(
+            fx.revert(&first_token).await,
+            Err(AppError::Conflict(_))
+        ));
+        assert_eq!(
+            fx.repo
+                .find_by_uuid(&user.uuid)
+                .await
+                .unwrap()
+                .unwrap()
+                .email,
+            "newer@gimme.com"
+        );
+    }
+}
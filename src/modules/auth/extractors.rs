@@ -8,6 +8,11 @@ use jsonwebtoken::{DecodingKey, Validation, decode};
 use crate::modules::auth::service::Claims;
 use crate::shared::error::AppError;
 
+/// Request extension marking a mutating route as usable under impersonation.
+/// Inserted by `shared::middleware::allow_impersonated_writes`.
+#[derive(Clone, Copy, Debug)]
+pub struct AllowImpersonatedWrites;
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Claims
 where
@@ -40,6 +45,65 @@ where
         )
         .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))?;
 
-        Ok(token_data.claims)
+        let claims = token_data.claims;
+
+        // Impersonation tokens are read-only unless the route opted in.
+        if claims.is_impersonated()
+            && !parts.method.is_safe()
+            && parts.extensions.get::<AllowImpersonatedWrites>().is_none()
+        {
+            return Err(AppError::Forbidden(
+                "Impersonation sessions are read-only".to_string(),
+            ));
+        }
+
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::auth::service::{ActorClaim, AuthService};
+    use axum::http::{Method, Request};
+
+    fn impersonated_parts(method: Method) -> Parts {
+        let token = AuthService::generate_impersonation_jwt(
+            &crate::shared::config::Config::for_test(),
+            "target-uuid",
+            ActorClaim {
+                sub: "staff-uuid".to_string(),
+                name: "staff".to_string(),
+                sid: "session".to_string(),
+            },
+            chrono::Utc::now() + chrono::Duration::minutes(5),
+        )
+        .unwrap();
+
+        let (parts, _) = Request::builder()
+            .method(method)
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+    }
+
+    #[tokio::test]
+    async fn test_impersonation_token_is_read_only() {
+        let mut get = impersonated_parts(Method::GET);
+        let claims = Claims::from_request_parts(&mut get, &()).await.unwrap();
+        assert_eq!(claims.sub, "target-uuid");
+        assert_eq!(claims.act.unwrap().sub, "staff-uuid");
+
+        let mut patch = impersonated_parts(Method::PATCH);
+        assert!(matches!(
+            Claims::from_request_parts(&mut patch, &()).await,
+            Err(AppError::Forbidden(_))
+        ));
+
+        let mut allowed = impersonated_parts(Method::PATCH);
+        allowed.extensions.insert(AllowImpersonatedWrites);
+        assert!(Claims::from_request_parts(&mut allowed, &()).await.is_ok());
     }
 }
@@ -1,17 +1,22 @@
 use askama::Template;
 use axum::{
     Json,
-    extract::{Query, State},
+    extract::{Form, Query, State},
     response::{Html, IntoResponse, Redirect},
 };
 
-use deadpool_redis::redis::AsyncCommands;
 use serde::Deserialize;
 
+use super::email_change::EmailChangeService;
+use super::providers::error::OAuthError;
 use super::service::AuthService;
+use super::verification::EmailVerificationService;
+use crate::modules::terms::repository::TermsRepository;
+use crate::modules::users::dtos::LoginClientInfo;
 use crate::modules::users::entities::social::SocialProvider;
 // // use crate::modules::users::entities::user;
 use crate::modules::users::repository::UserRepository;
+use crate::modules::users::status::{AccountStatusService, StatusActor, StatusChange};
 use crate::shared::{
     error::{AppError, AppResult},
     state::AppState,
@@ -21,6 +26,9 @@ use std::sync::Arc;
 #[derive(Deserialize)]
 pub struct AuthCallbackParams {
     code: String,
+    /// Forwarded by the app, which receives the provider redirect itself.
+    device_id: Option<String>,
+    referral_code: Option<String>,
 }
 
 pub async fn login_kakao(State(state): State<AppState>) -> AppResult<Redirect> {
@@ -49,26 +57,56 @@ pub async fn callback_kakao(
             ))?;
 
     // 1. Get User Info from Provider
-    let user_info = kakao_provider.get_user_info(&params.code).await?;
+    // Declined scopes are a normal onboarding detour, not a failure: hand the
+    // client a URL that asks for exactly the missing agreements.
+    let user_info = match kakao_provider.get_user_info(&params.code).await {
+        Ok(info) => info,
+        Err(AppError::OAuth(OAuthError::ConsentMissing(scopes))) if !scopes.is_empty() => {
+            return Ok(Json(serde_json::json!({
+                "need_more_action": true,
+                "consent_required": {
+                    "missing_scopes": scopes,
+                    "authorization_url": kakao_provider.get_reconsent_url(&scopes),
+                },
+            })));
+        }
+        Err(e) => return Err(e),
+    };
 
     // 2. Login or Register
     let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
         AppError::InternalServerError("UserRepository not registered".to_string()),
     )?;
 
-    let (token, need_more_action) = AuthService::handle_social_login(
+    let terms_repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+
+    let outcome = AuthService::handle_social_login(
         user_repo.as_ref(),
+        state.repo_manager.as_ref(),
+        terms_repo.as_ref(),
         &state.config,
+        &state.redis_pool,
+        state.email_provider.as_ref(),
         SocialProvider::Kakao,
         user_info,
+        LoginClientInfo {
+            device_id: params.device_id,
+            referral_code: params.referral_code,
+        },
     )
     .await?;
 
     // Return JWT (In real app, maybe set cookie or redirect to frontend with token)
     Ok(Json(serde_json::json!({
-        "token": token,
+        "token": outcome.token,
         "token_type": "Bearer",
-        "need_more_action": need_more_action,
+        "need_more_action": outcome.need_more_action,
+        "onboarding": {
+            "remaining_steps": outcome.remaining_steps,
+            "verification_code_pending": outcome.verification_code_pending,
+        },
     })))
 }
 
@@ -116,29 +154,13 @@ pub async fn request_email_verification(
         ));
     }
 
-    // 5. Generate 6-digit code
-    use rand::Rng;
-    let code: u32 = rand::rng().random_range(100000..999999);
-    let code_str = code.to_string();
-
-    // 6. Store in Redis
-    let mut conn = state
-        .redis_pool
-        .get()
-        .await
-        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
-
-    let redis_key = format!("verification:{}", body.email);
-    let _: () = conn
-        .set_ex(&redis_key, &code_str, 300) // 5 minutes expiration
-        .await
-        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
-
-    // 7. Send Email
-    state
-        .email_provider
-        .send_verification_code(&body.email, &code_str)
-        .await?;
+    // 5. Generate code, store it in Redis and send it
+    EmailVerificationService::issue_code(
+        &state.redis_pool,
+        state.email_provider.as_ref(),
+        &body.email,
+    )
+    .await?;
 
     Ok(Json(serde_json::json!({
         "message": "Verification code sent",
@@ -167,6 +189,7 @@ pub async fn verify_email_code(
         .await?
         .ok_or(AppError::NotFound)?; // Unauthorized?
     let verification = user.verification.clone();
+    let user_id = user.id;
 
     // 2. Verify Code
     if let Some(v) = &verification {
@@ -176,37 +199,7 @@ pub async fn verify_email_code(
         }
 
         // Check Redis
-        let mut conn = state
-            .redis_pool
-            .get()
-            .await
-            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
-
-        let redis_key = format!("verification:{}", body.email);
-        let stored_code: Option<String> = conn.get(&redis_key).await.map_err(|e| {
-            AppError::InternalServerError("해당 이메일 인증 정보가 존재하지 않습니다.".to_string())
-        })?;
-
-        match stored_code {
-            Some(code) => {
-                if code != body.code {
-                    return Err(AppError::BadRequest(
-                        "Invalid verification code".to_string(),
-                    ));
-                }
-                // Determine if we should delete the key after successful verification?
-                // It's good practice.
-                let _: () = conn
-                    .del(&redis_key)
-                    .await
-                    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
-            }
-            None => {
-                return Err(AppError::BadRequest(
-                    "No verification code found (or expired)".to_string(),
-                ));
-            }
-        }
+        EmailVerificationService::consume_code(&state.redis_pool, &body.email, &body.code).await?;
     } else {
         return Err(AppError::InternalServerError(
             "Verification record missing".to_string(),
@@ -219,9 +212,11 @@ pub async fn verify_email_code(
     ))?;
     let mut verification_active: crate::modules::users::entities::verification::ActiveModel =
         verification_model.into();
+    // Only a pending signup is activated here; banned or dormant accounts
+    // keep their status and just get the verified email.
+    let activate =
+        user.account_status == crate::modules::users::entities::enums::AccountStatus::Pending;
     let mut user_active: crate::modules::users::entities::user::ActiveModel = user.into();
-    user_active.account_status =
-        sea_orm::ActiveValue::Set(crate::modules::users::entities::enums::AccountStatus::Active);
     user_active.email = sea_orm::ActiveValue::Set(body.email);
 
     // We don't store code in DB anymore, so no need to clear it from DB specifically,
@@ -242,7 +237,19 @@ pub async fn verify_email_code(
     tx_user_repo
         .update_verification(verification_active)
         .await?;
-    tx_user_repo.update_user(user_active).await?;
+    let updated = tx_user_repo.update_user(user_active).await?;
+    if activate {
+        AccountStatusService::transition(
+            tx_user_repo.as_ref(),
+            updated,
+            StatusChange::new(
+                crate::modules::users::entities::enums::AccountStatus::Active,
+                StatusActor::User(user_id),
+                "Email verified",
+            ),
+        )
+        .await?;
+    }
 
     uow.commit().await?;
 
@@ -253,12 +260,80 @@ pub async fn verify_email_code(
     })))
 }
 
+#[derive(Deserialize)]
+pub struct RequestEmailChangeRequest {
+    pub new_email: String,
+}
+
+pub async fn request_email_change(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<RequestEmailChangeRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    EmailChangeService::request(
+        user_repo.as_ref(),
+        &state.redis_pool,
+        state.email_provider.as_ref(),
+        &claims.sub,
+        &body.new_email,
+    )
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Verification code sent",
+        "code": "OK"
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmEmailChangeRequest {
+    pub code: String,
+}
+
+pub async fn confirm_email_change(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<ConfirmEmailChangeRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let user = EmailChangeService::confirm(
+        user_repo.as_ref(),
+        state.repo_manager.as_ref(),
+        &state.redis_pool,
+        state.email_provider.as_ref(),
+        &state.config,
+        &claims.sub,
+        &body.code,
+    )
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Email changed successfully.",
+        "email": user.email,
+        "code": "OK"
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct RevertEmailChangeParams {
+    token: String,
+}
+
 #[derive(Template)]
-#[template(path = "auth/move.html")]
-pub struct MoveKakaoTemplate;
+#[template(path = "auth/revert_email.html")]
+pub struct RevertEmailTemplate {
+    token: String,
+    reverted: bool,
+}
 
-pub async fn view_move_kakao() -> impl IntoResponse {
-    let template = MoveKakaoTemplate;
+fn render_html(template: impl Template) -> axum::response::Response {
     match template.render() {
         Ok(html) => Html(html).into_response(),
         Err(err) => {
@@ -271,3 +346,44 @@ pub async fn view_move_kakao() -> impl IntoResponse {
         }
     }
 }
+
+/// Landing page for the "this wasn't me" link. Only asks for confirmation:
+/// mail scanners and link prefetchers issue GETs, so nothing changes here.
+pub async fn view_revert_email_change(
+    Query(params): Query<RevertEmailChangeParams>,
+) -> impl IntoResponse {
+    render_html(RevertEmailTemplate {
+        token: params.token,
+        reverted: false,
+    })
+}
+
+pub async fn revert_email_change(
+    State(state): State<AppState>,
+    Form(params): Form<RevertEmailChangeParams>,
+) -> AppResult<impl IntoResponse> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    EmailChangeService::revert(
+        user_repo.as_ref(),
+        state.repo_manager.as_ref(),
+        &state.redis_pool,
+        &params.token,
+    )
+    .await?;
+
+    Ok(render_html(RevertEmailTemplate {
+        token: String::new(),
+        reverted: true,
+    }))
+}
+
+#[derive(Template)]
+#[template(path = "auth/move.html")]
+pub struct MoveKakaoTemplate;
+
+pub async fn view_move_kakao() -> impl IntoResponse {
+    render_html(MoveKakaoTemplate)
+}
@@ -1,6 +1,8 @@
+pub mod email_change;
 pub mod extractors;
 pub mod handlers;
 pub mod providers;
 pub mod registry;
 pub mod router;
 pub mod service;
+pub mod verification;
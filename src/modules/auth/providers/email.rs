@@ -8,6 +8,12 @@ use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 #[async_trait]
 pub trait EmailProvider: Send + Sync {
     async fn send_verification_code(&self, to: &str, code: &str) -> AppResult<()>;
+    async fn send_email_change_notice(
+        &self,
+        to: &str,
+        new_email: &str,
+        revert_url: &str,
+    ) -> AppResult<()>;
 }
 
 pub struct GmailProvider {
@@ -45,17 +51,12 @@ impl GmailProvider {
     }
 }
 
-#[async_trait]
-impl EmailProvider for GmailProvider {
-    async fn send_verification_code(&self, to: &str, code: &str) -> AppResult<()> {
-        if self.app_env == "dev" || self.app_env == "test" {
-            println!("--------------------------------------------------");
-            println!("[DEV] Sending Verification Code to: {}", to);
-            println!("[DEV] Code: {}", code);
-            println!("--------------------------------------------------");
-            return Ok(());
-        }
+impl GmailProvider {
+    fn is_dev(&self) -> bool {
+        self.app_env == "dev" || self.app_env == "test"
+    }
 
+    async fn send_plain(&self, to: &str, subject: &str, body: String) -> AppResult<()> {
         let email = Message::builder()
             .from(self.from.parse().map_err(|e| {
                 AppError::InternalServerError(format!("Invalid from address: {}", e))
@@ -63,9 +64,9 @@ impl EmailProvider for GmailProvider {
             .to(to
                 .parse()
                 .map_err(|e| AppError::BadRequest(format!("Invalid to address: {}", e)))?)
-            .subject("Gimme Verification Code")
+            .subject(subject)
             .header(ContentType::TEXT_PLAIN)
-            .body(format!("Your verification code is: {}", code))
+            .body(body)
             .map_err(|e| AppError::InternalServerError(format!("Failed to build email: {}", e)))?;
 
         if let Some(mailer) = &self.mailer {
@@ -82,6 +83,50 @@ impl EmailProvider for GmailProvider {
     }
 }
 
+#[async_trait]
+impl EmailProvider for GmailProvider {
+    async fn send_verification_code(&self, to: &str, code: &str) -> AppResult<()> {
+        if self.is_dev() {
+            println!("--------------------------------------------------");
+            println!("[DEV] Sending Verification Code to: {}", to);
+            println!("[DEV] Code: {}", code);
+            println!("--------------------------------------------------");
+            return Ok(());
+        }
+
+        self.send_plain(
+            to,
+            "Gimme Verification Code",
+            format!("Your verification code is: {}", code),
+        )
+        .await
+    }
+
+    async fn send_email_change_notice(
+        &self,
+        to: &str,
+        new_email: &str,
+        revert_url: &str,
+    ) -> AppResult<()> {
+        if self.is_dev() {
+            // The revert URL carries a live token, so it stays out of the logs.
+            tracing::info!(to, new_email, "[DEV] Email change notice not sent");
+            return Ok(());
+        }
+
+        self.send_plain(
+            to,
+            "Gimme Account Email Changed",
+            format!(
+                "The email address of your Gimme account was changed to {}.\n\
+                 If this wasn't you, restore your previous address here: {}",
+                new_email, revert_url
+            ),
+        )
+        .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,20 +134,8 @@ mod tests {
     #[tokio::test]
     async fn test_gmail_provider_dev_mode() {
         let config = Config {
-            database_url: "".to_string(),
-            database_max_connections: 100,
-            database_min_connections: 5,
-            database_connect_timeout: 8,
-            database_idle_timeout: 8,
-            server_host: "localhost".to_string(),
-            server_port: 3000,
-            rust_log: "info".to_string(),
             app_env: "dev".to_string(),
-            kakao_client_id: "".to_string(),
-            kakao_redirect_uri: "".to_string(),
-            gmail_user: "".to_string(),
-            gmail_app_password: "".to_string(),
-            redis_url: "".to_string(),
+            ..Config::for_test()
         };
 
         let provider = GmailProvider::new(&config);
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+/// Failures talking to an OAuth provider, surfaced through `AppError::OAuth`.
+#[derive(Error, Debug)]
+pub enum OAuthError {
+    /// The authorization code or token was rejected (expired, reused, revoked).
+    #[error("Invalid grant: {0}")]
+    InvalidGrant(String),
+
+    /// The user did not agree to scopes we require.
+    #[error("Consent missing for: {}", .0.join(", "))]
+    ConsentMissing(Vec<String>),
+
+    /// The provider answered with something we could not use.
+    #[error("Upstream error: {0}")]
+    UpstreamError(String),
+
+    /// The provider timed out, returned 5xx, or the circuit breaker is open.
+    #[error("Upstream unavailable: {0}")]
+    UpstreamUnavailable(String),
+}
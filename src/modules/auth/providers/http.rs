@@ -0,0 +1,302 @@
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::error::OAuthError;
+use crate::shared::config::Config;
+
+/// Consecutive failures before the breaker opens.
+const BREAKER_FAILURE_THRESHOLD: u32 = 5;
+/// How long an open breaker rejects calls before letting a probe through.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+/// Base delay between retries, doubled per attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Debug)]
+pub struct ProviderHttpConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub retry_backoff: Duration,
+    pub breaker_failure_threshold: u32,
+    pub breaker_cooldown: Duration,
+}
+
+impl ProviderHttpConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            timeout: Duration::from_millis(config.oauth_http_timeout_ms),
+            max_retries: config.oauth_http_max_retries,
+            retry_backoff: RETRY_BACKOFF,
+            breaker_failure_threshold: BREAKER_FAILURE_THRESHOLD,
+            breaker_cooldown: BREAKER_COOLDOWN,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakerState {
+    Closed {
+        consecutive_failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// Cooldown is over; only the probe call may run until it resolves.
+    HalfOpen {
+        probe_in_flight: bool,
+    },
+}
+
+/// Minimal consecutive-failure circuit breaker.
+/// After the cooldown exactly one probe call is let through (half-open) and
+/// everyone else is refused until it resolves: a failure re-opens the
+/// breaker, a success closes it.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    state: Mutex<BreakerState>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+/// Permission to make one call. Report its outcome through the breaker;
+/// a probe dropped without an outcome (e.g. a cancelled request) lets the
+/// next caller probe instead.
+pub struct CallPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl Drop for CallPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe {
+            let mut state = self.breaker.state.lock().unwrap();
+            if *state
+                == (BreakerState::HalfOpen {
+                    probe_in_flight: true,
+                })
+            {
+                *state = BreakerState::HalfOpen {
+                    probe_in_flight: false,
+                };
+            }
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            state: Mutex::new(BreakerState::Closed {
+                consecutive_failures: 0,
+            }),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+
+    /// Admits a call, moving an expired open breaker to half-open with
+    /// this call as its probe. `None` while open or while a probe is out.
+    pub fn try_acquire(&self) -> Option<CallPermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        let probe = match *state {
+            BreakerState::Closed { .. } => false,
+            BreakerState::Open { until } if Instant::now() < until => return None,
+            BreakerState::HalfOpen {
+                probe_in_flight: true,
+            } => return None,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                *state = BreakerState::HalfOpen {
+                    probe_in_flight: true,
+                };
+                true
+            }
+        };
+        Some(CallPermit {
+            breaker: self,
+            probe,
+        })
+    }
+
+    /// Read-only: whether the breaker is closed. A retry only goes ahead
+    /// while it is, so a failed probe is never retried.
+    pub fn is_closed(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), BreakerState::Closed { .. })
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::Closed {
+            consecutive_failures: 0,
+        };
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        *state = match *state {
+            BreakerState::Closed {
+                consecutive_failures,
+            } if consecutive_failures + 1 < self.failure_threshold => BreakerState::Closed {
+                consecutive_failures: consecutive_failures + 1,
+            },
+            _ => BreakerState::Open {
+                until: Instant::now() + self.cooldown,
+            },
+        };
+    }
+}
+
+/// HTTP client shared by OAuth providers: per-request timeout, bounded
+/// retries with exponential backoff, and a circuit breaker per provider.
+#[derive(Clone)]
+pub struct ProviderHttpClient {
+    name: &'static str,
+    client: Client,
+    config: ProviderHttpConfig,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl ProviderHttpClient {
+    pub fn new(name: &'static str, config: ProviderHttpConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .build()
+            .expect("Failed to build provider HTTP client");
+        let breaker = Arc::new(CircuitBreaker::new(
+            config.breaker_failure_threshold,
+            config.breaker_cooldown,
+        ));
+
+        Self {
+            name,
+            client,
+            config,
+            breaker,
+        }
+    }
+
+    /// Sends the request built by `build`, retrying transient failures.
+    ///
+    /// Connection failures are always retried since nothing reached the
+    /// provider. Timeouts, 429 and 5xx are retried only for idempotent
+    /// methods, so a single-use authorization code is never posted twice.
+    /// Other 4xx responses are returned as-is for the caller to interpret.
+    pub async fn send<F>(&self, build: F) -> Result<Response, OAuthError>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let Some(_permit) = self.breaker.try_acquire() else {
+            return Err(OAuthError::UpstreamUnavailable(format!(
+                "{} circuit breaker is open",
+                self.name
+            )));
+        };
+
+        let mut attempt = 0;
+        loop {
+            let request = build(&self.client)
+                .build()
+                .map_err(|e| OAuthError::UpstreamError(format!("{}: {}", self.name, e)))?;
+            let idempotent = request.method().is_idempotent();
+
+            let (retryable, error) = match self.client.execute(request).await {
+                Ok(res) if Self::is_transient_status(res.status()) => (
+                    idempotent,
+                    OAuthError::UpstreamUnavailable(format!(
+                        "{} responded {}",
+                        self.name,
+                        res.status()
+                    )),
+                ),
+                Ok(res) => {
+                    self.breaker.record_success();
+                    return Ok(res);
+                }
+                Err(e) if e.is_connect() => (
+                    true,
+                    OAuthError::UpstreamUnavailable(format!("{}: {}", self.name, e)),
+                ),
+                Err(e) if e.is_timeout() => (
+                    idempotent,
+                    OAuthError::UpstreamUnavailable(format!("{}: {}", self.name, e)),
+                ),
+                Err(e) => {
+                    return Err(OAuthError::UpstreamError(format!("{}: {}", self.name, e)));
+                }
+            };
+
+            self.breaker.record_failure();
+            if !retryable || attempt >= self.config.max_retries || !self.breaker.is_closed() {
+                return Err(error);
+            }
+
+            tracing::warn!(
+                "{} request failed (attempt {}), retrying: {}",
+                self.name,
+                attempt + 1,
+                error
+            );
+            tokio::time::sleep(self.config.retry_backoff * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn is_transient_status(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker_opens_and_half_opens() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+        assert!(breaker.try_acquire().is_some());
+
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_some());
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_none());
+        assert!(!breaker.is_closed());
+
+        std::thread::sleep(Duration::from_millis(30));
+        // One probe, and one more failure re-opens immediately.
+        let probe = breaker.try_acquire();
+        assert!(probe.is_some());
+        breaker.record_failure();
+        drop(probe);
+        assert!(breaker.try_acquire().is_none());
+
+        std::thread::sleep(Duration::from_millis(30));
+        let probe = breaker.try_acquire();
+        assert!(probe.is_some());
+        breaker.record_success();
+        drop(probe);
+        assert!(breaker.is_closed());
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_half_open_admits_a_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(30));
+
+        let probe = breaker.try_acquire();
+        assert!(probe.is_some());
+        assert!(breaker.try_acquire().is_none());
+        // Checking does not change the state.
+        assert!(!breaker.is_closed());
+        assert!(breaker.try_acquire().is_none());
+
+        // A probe abandoned without an outcome hands over to the next caller.
+        drop(probe);
+        let probe = breaker.try_acquire();
+        assert!(probe.is_some());
+        assert!(breaker.try_acquire().is_none());
+        breaker.record_success();
+        assert!(breaker.try_acquire().is_some());
+    }
+}
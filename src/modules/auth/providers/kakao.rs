@@ -1,23 +1,108 @@
+use super::error::OAuthError;
+use super::http::ProviderHttpClient;
 use super::{OAuthProvider, OAuthUserInfo};
-use crate::shared::error::{AppError, AppResult};
+use crate::shared::error::AppResult;
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::StatusCode;
 
 use serde::Deserialize;
 
+/// Scopes without which we cannot register a user.
+const REQUIRED_SCOPES: [&str; 3] = ["profile_nickname", "account_email", "phone_number"];
+
 pub struct KakaoProvider {
     client_id: String,
     redirect_uri: String,
-    client: Client,
+    auth_base_url: String,
+    api_base_url: String,
+    http: ProviderHttpClient,
 }
 
 impl KakaoProvider {
-    pub fn new(client_id: String, redirect_uri: String) -> Self {
+    pub fn new(
+        client_id: String,
+        redirect_uri: String,
+        auth_base_url: String,
+        api_base_url: String,
+        http: ProviderHttpClient,
+    ) -> Self {
         Self {
             client_id,
             redirect_uri,
-            client: Client::new(),
+            auth_base_url: auth_base_url.trim_end_matches('/').to_string(),
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<KakaoTokenResponse, OAuthError> {
+        let params = [
+            ("grant_type", "authorization_code"),
+            ("client_id", &self.client_id),
+            ("redirect_uri", &self.redirect_uri),
+            ("code", code),
+        ];
+        let url = format!("{}/oauth/token", self.auth_base_url);
+
+        let res = self
+            .http
+            .send(|client| client.post(&url).form(&params))
+            .await?;
+
+        if res.status().is_client_error() {
+            let status = res.status();
+            let err = res.json::<KakaoOAuthErrorResponse>().await.ok();
+            return Err(match err {
+                Some(e) if e.error == "invalid_grant" => {
+                    OAuthError::InvalidGrant(e.error_description.unwrap_or_else(|| e.error.clone()))
+                }
+                Some(e) => OAuthError::UpstreamError(format!(
+                    "Kakao token request rejected ({}): {}",
+                    status, e.error
+                )),
+                None => {
+                    OAuthError::UpstreamError(format!("Kakao token request rejected ({})", status))
+                }
+            });
         }
+
+        res.json::<KakaoTokenResponse>()
+            .await
+            .map_err(|e| OAuthError::UpstreamError(format!("Kakao token parse failed: {}", e)))
+    }
+
+    async fn fetch_user(&self, access_token: &str) -> Result<KakaoUserResponse, OAuthError> {
+        let url = format!("{}/v2/user/me", self.api_base_url);
+
+        let res = self
+            .http
+            .send(|client| client.get(&url).bearer_auth(access_token))
+            .await?;
+
+        match res.status() {
+            StatusCode::UNAUTHORIZED => {
+                return Err(OAuthError::InvalidGrant(
+                    "Kakao rejected the access token".to_string(),
+                ));
+            }
+            StatusCode::FORBIDDEN => {
+                let err = res.json::<KakaoApiErrorResponse>().await.ok();
+                return Err(OAuthError::ConsentMissing(
+                    err.and_then(|e| e.required_scopes).unwrap_or_default(),
+                ));
+            }
+            status if !status.is_success() => {
+                return Err(OAuthError::UpstreamError(format!(
+                    "Kakao user info request rejected ({})",
+                    status
+                )));
+            }
+            _ => {}
+        }
+
+        res.json::<KakaoUserResponse>()
+            .await
+            .map_err(|e| OAuthError::UpstreamError(format!("Kakao user info parse failed: {}", e)))
     }
 }
 
@@ -26,13 +111,57 @@ struct KakaoTokenResponse {
     access_token: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct KakaoOAuthErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KakaoApiErrorResponse {
+    required_scopes: Option<Vec<String>>,
+}
+
 #[derive(Deserialize, Debug)]
 struct KakaoUserAccount {
     email: Option<String>,
+    email_needs_agreement: Option<bool>,
     age_range: Option<String>,
-    birthyear: String,
+    birthyear: Option<String>,
     phone_number: Option<String>,
+    phone_number_needs_agreement: Option<bool>,
     profile: Option<KakaoUserProfile>,
+    profile_nickname_needs_agreement: Option<bool>,
+}
+
+impl KakaoUserAccount {
+    /// Required scopes the user has not agreed to yet, in `REQUIRED_SCOPES` order.
+    /// A field that is absent without `*_needs_agreement` is simply not on the
+    /// Kakao account, and asking again would not help.
+    fn missing_scopes(&self) -> Vec<String> {
+        let nickname = self.profile.as_ref().and_then(|p| p.nickname.as_ref());
+        [
+            (
+                "profile_nickname",
+                nickname.is_none(),
+                self.profile_nickname_needs_agreement,
+            ),
+            (
+                "account_email",
+                self.email.is_none(),
+                self.email_needs_agreement,
+            ),
+            (
+                "phone_number",
+                self.phone_number.is_none(),
+                self.phone_number_needs_agreement,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, absent, needs_agreement)| *absent && *needs_agreement == Some(true))
+        .map(|(scope, _, _)| scope.to_string())
+        .collect()
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -51,78 +180,180 @@ struct KakaoUserResponse {
 impl OAuthProvider for KakaoProvider {
     fn get_authorization_url(&self) -> String {
         format!(
-            "https://kauth.kakao.com/oauth/authorize?client_id={}&redirect_uri={}&response_type=code",
-            self.client_id, self.redirect_uri
+            "{}/oauth/authorize?client_id={}&redirect_uri={}&response_type=code",
+            self.auth_base_url, self.client_id, self.redirect_uri
+        )
+    }
+
+    fn get_reconsent_url(&self, scopes: &[String]) -> String {
+        format!(
+            "{}&scope={}&prompt=consent",
+            self.get_authorization_url(),
+            scopes.join(",")
         )
     }
 
     async fn get_user_info(&self, code: &str) -> AppResult<OAuthUserInfo> {
         // 1. Get Access Token
-        let params = [
-            ("grant_type", "authorization_code"),
-            ("client_id", &self.client_id),
-            ("redirect_uri", &self.redirect_uri),
-            ("code", code),
-        ];
-
-        let token_res = self
-            .client
-            .post("https://kauth.kakao.com/oauth/token")
-            .form(&params)
-            .send()
-            .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Kakao token request failed: {}", e))
-            })?
-            .json::<KakaoTokenResponse>()
-            .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Kakao token parse failed: {}", e))
-            })?;
+        let token_res = self.exchange_code(code).await?;
 
         // 2. Get User Info
-        let user_res = self
-            .client
-            .get("https://kapi.kakao.com/v2/user/me")
-            .bearer_auth(token_res.access_token)
-            .send()
-            .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Kakao user info request failed: {}", e))
-            })?
-            .json::<KakaoUserResponse>()
-            .await
-            .map_err(|e| {
-                AppError::InternalServerError(format!("Kakao user info parse failed: {}", e))
-            })?;
-
-        let email = user_res
-            .kakao_account
-            .as_ref()
-            .and_then(|a| a.email.clone());
-        let name = user_res
-            .kakao_account
-            .as_ref()
-            .and_then(|a| a.profile.as_ref())
-            .and_then(|p| p.nickname.clone());
-        let age_range = user_res
-            .kakao_account
-            .as_ref()
-            .and_then(|a| a.age_range.clone());
-        let birthyear = user_res.kakao_account.as_ref().unwrap().birthyear.clone();
-        let phone_number = user_res
-            .kakao_account
-            .as_ref()
-            .and_then(|a| a.phone_number.clone());
+        let user_res = self.fetch_user(&token_res.access_token).await?;
+
+        let account = user_res.kakao_account.ok_or(OAuthError::ConsentMissing(
+            REQUIRED_SCOPES.iter().map(|s| s.to_string()).collect(),
+        ))?;
+
+        let missing_scopes = account.missing_scopes();
+        if !missing_scopes.is_empty() {
+            return Err(OAuthError::ConsentMissing(missing_scopes).into());
+        }
+
+        let name = account.profile.as_ref().and_then(|p| p.nickname.clone());
 
         Ok(OAuthUserInfo {
             provider_id: user_res.id.to_string(),
-            email,
-            age_range,
-            birthyear,
-            phone_number,
+            email: account.email,
+            age_range: account.age_range,
+            birthyear: account.birthyear,
+            phone_number: account.phone_number,
             name,
             connected_at: user_res.connected_at,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::auth::providers::http::ProviderHttpConfig;
+    use crate::shared::config::Config;
+    use crate::shared::error::AppError;
+    use axum::{
+        Form, Json, Router,
+        http::StatusCode as AxumStatus,
+        response::IntoResponse,
+        routing::{get, post},
+    };
+    use std::collections::HashMap;
+
+    /// Stands in for kauth/kapi: code "good" works, "used" is an invalid grant,
+    /// "flaky" gets a token the user API answers with 503 and "declined"
+    /// belongs to a user who refused the email and phone scopes.
+    async fn spawn_mock_kakao() -> String {
+        async fn token(Form(form): Form<HashMap<String, String>>) -> axum::response::Response {
+            match form.get("code").map(String::as_str) {
+                Some("used") => (
+                    AxumStatus::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": "invalid_grant",
+                        "error_description": "authorization code not found",
+                    })),
+                )
+                    .into_response(),
+                Some(code) => Json(serde_json::json!({ "access_token": code })).into_response(),
+                None => AxumStatus::BAD_REQUEST.into_response(),
+            }
+        }
+
+        async fn me(headers: axum::http::HeaderMap) -> axum::response::Response {
+            let auth = headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default();
+            if auth == "Bearer flaky" {
+                return AxumStatus::SERVICE_UNAVAILABLE.into_response();
+            }
+            if auth == "Bearer declined" {
+                return Json(serde_json::json!({
+                    "id": 43,
+                    "kakao_account": {
+                        "email_needs_agreement": true,
+                        "phone_number_needs_agreement": true,
+                        "birthyear": "1990",
+                        "profile": { "nickname": "gimme" }
+                    }
+                }))
+                .into_response();
+            }
+            Json(serde_json::json!({
+                "id": 42,
+                "connected_at": "2024-01-29T00:00:00Z",
+                "kakao_account": {
+                    "email": "kakao@gimme.com",
+                    "phone_number": "+82 10-1234-5678",
+                    "birthyear": "1990",
+                    "profile": { "nickname": "gimme" }
+                }
+            }))
+            .into_response()
+        }
+
+        let app = Router::new()
+            .route("/oauth/token", post(token))
+            .route("/v2/user/me", get(me));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    fn provider(base_url: &str) -> KakaoProvider {
+        let http_config = ProviderHttpConfig {
+            retry_backoff: std::time::Duration::from_millis(1),
+            ..ProviderHttpConfig::from_config(&Config::for_test())
+        };
+        KakaoProvider::new(
+            "client".to_string(),
+            "http://localhost/callback".to_string(),
+            base_url.to_string(),
+            base_url.to_string(),
+            ProviderHttpClient::new("kakao", http_config),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_user_info_against_mock() {
+        let base_url = spawn_mock_kakao().await;
+        let provider = provider(&base_url);
+
+        let info = provider.get_user_info("good").await.unwrap();
+        assert_eq!(info.provider_id, "42");
+        assert_eq!(info.email.as_deref(), Some("kakao@gimme.com"));
+        assert_eq!(info.name.as_deref(), Some("gimme"));
+
+        assert!(matches!(
+            provider.get_user_info("used").await,
+            Err(AppError::OAuth(OAuthError::InvalidGrant(_)))
+        ));
+        assert!(matches!(
+            provider.get_user_info("flaky").await,
+            Err(AppError::OAuth(OAuthError::UpstreamUnavailable(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_declined_scopes_are_reported() {
+        let base_url = spawn_mock_kakao().await;
+        let provider = provider(&base_url);
+
+        match provider.get_user_info("declined").await {
+            Err(AppError::OAuth(OAuthError::ConsentMissing(scopes))) => {
+                assert_eq!(scopes, vec!["account_email", "phone_number"]);
+                let url = provider.get_reconsent_url(&scopes);
+                assert!(url.starts_with(&format!("{}/oauth/authorize?", base_url)));
+                assert!(url.ends_with("&scope=account_email,phone_number&prompt=consent"));
+            }
+            other => panic!("expected ConsentMissing, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_provider_is_unavailable() {
+        let provider = provider("http://127.0.0.1:1");
+        assert!(matches!(
+            provider.get_user_info("good").await,
+            Err(AppError::OAuth(OAuthError::UpstreamUnavailable(_)))
+        ));
+    }
+}
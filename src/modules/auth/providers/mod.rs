@@ -2,6 +2,8 @@ use crate::shared::error::AppResult;
 use async_trait::async_trait;
 
 pub mod email;
+pub mod error;
+pub mod http;
 pub mod kakao;
 
 #[derive(Debug)]
@@ -10,7 +12,7 @@ pub struct OAuthUserInfo {
     pub email: Option<String>,
     pub name: Option<String>,
     pub age_range: Option<String>,
-    pub birthyear: String,
+    pub birthyear: Option<String>,
     pub phone_number: Option<String>,
     pub connected_at: Option<String>,
 }
@@ -18,5 +20,7 @@ pub struct OAuthUserInfo {
 #[async_trait]
 pub trait OAuthProvider: Send + Sync {
     fn get_authorization_url(&self) -> String;
+    /// Authorization URL that asks the user again for just `scopes`.
+    fn get_reconsent_url(&self, scopes: &[String]) -> String;
     async fn get_user_info(&self, code: &str) -> AppResult<OAuthUserInfo>;
 }
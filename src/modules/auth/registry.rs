@@ -2,7 +2,7 @@ use super::providers::OAuthProvider;
 use crate::modules::users::entities::social::SocialProvider;
 use std::{collections::HashMap, sync::Arc};
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct OAuthProviderRegistry {
     providers: HashMap<String, Arc<dyn OAuthProvider>>,
 }
@@ -15,5 +15,17 @@ pub fn router(state: AppState) -> Router {
             "/validate-email-code",
             axum::routing::post(handlers::verify_email_code),
         )
+        .route(
+            "/email-change",
+            axum::routing::post(handlers::request_email_change),
+        )
+        .route(
+            "/email-change/confirm",
+            axum::routing::post(handlers::confirm_email_change),
+        )
+        .route(
+            "/email-change/revert",
+            get(handlers::view_revert_email_change).post(handlers::revert_email_change),
+        )
         .with_state(state)
 }
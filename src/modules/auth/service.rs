@@ -1,23 +1,56 @@
 use chrono::{Duration, Utc};
 use jsonwebtoken::{EncodingKey, Header, encode};
 
+use crate::modules::terms::{repository::TermsRepository, service::TermsService};
 use crate::modules::users::entities::enums::AccountStatus;
 use crate::modules::users::repository::UserRepository;
 // use sea_orm::ActiveModelTrait;
 use serde::{Deserialize, Serialize};
 
-use super::providers::OAuthUserInfo;
+use super::providers::{OAuthUserInfo, email::EmailProvider};
+use super::verification::EmailVerificationService;
 use crate::modules::users::{
-    dtos::SocialLoginDto, entities::social::SocialProvider, service::UserService,
+    dtos::{LoginClientInfo, SocialLoginDto},
+    entities::social::SocialProvider,
+    onboarding::OnboardingStep,
+    service::UserService,
 };
 use crate::shared::config::Config;
 use crate::shared::error::{AppError, AppResult};
+use crate::shared::repository::RepositoryManager;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // User UUID
     pub exp: usize,
     pub iat: usize,
+    /// Set only on impersonation tokens: the staff member acting as `sub`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<ActorClaim>,
+}
+
+impl Claims {
+    pub fn is_impersonated(&self) -> bool {
+        self.act.is_some()
+    }
+}
+
+/// RFC 8693 style `act` claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorClaim {
+    pub sub: String, // Staff user UUID
+    pub name: String,
+    /// Links the token to its `impersonation_audit_logs` row.
+    pub sid: String,
+}
+
+pub struct SocialLoginOutcome {
+    pub token: String,
+    pub need_more_action: bool,
+    pub remaining_steps: Vec<OnboardingStep>,
+    /// A verification code is waiting in the user's inbox, either sent by
+    /// this login or still valid from an earlier one.
+    pub verification_code_pending: bool,
 }
 
 pub struct AuthService;
@@ -37,12 +70,18 @@ impl AuthService {
         Ok(true)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_social_login(
         repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        terms_repo: &dyn TermsRepository,
         config: &Config,
+        redis_pool: &deadpool_redis::Pool,
+        email_provider: &dyn EmailProvider,
         provider: SocialProvider,
         user_info: OAuthUserInfo,
-    ) -> AppResult<(String, bool)> {
+        client: LoginClientInfo,
+    ) -> AppResult<SocialLoginOutcome> {
         let login_dto = SocialLoginDto {
             provider,
             provider_id: user_info.provider_id,
@@ -50,37 +89,94 @@ impl AuthService {
             name: user_info.name,
             phone_number: user_info.phone_number,
             connected_at: user_info.connected_at,
+            age_range: user_info.age_range,
+            birthyear: user_info.birthyear,
+            device_id: client.device_id,
+            referral_code: client.referral_code,
         };
 
         // Delegate finding/creating user to Domain Service
-        let user = UserService::handle_social_login(repo, login_dto).await?;
-        let need_more_action = !(user.account_status == AccountStatus::Active);
+        let result = UserService::handle_social_login(repo, repo_manager, login_dto).await?;
+
+        // A newly published required version sends existing users back to
+        // the agreement screen as well.
+        let terms_pending = !TermsService::pending_required(terms_repo, result.user.id)
+            .await?
+            .is_empty();
+        let remaining_steps = OnboardingStep::remaining_for(&result.user, terms_pending);
+        let need_more_action = !(result.user.account_status == AccountStatus::Active)
+            || remaining_steps.contains(&OnboardingStep::TermsAgreement);
+
+        // Resume email verification. A still-valid code is not re-sent, and a
+        // mail failure must not block the login itself.
+        let mut verification_code_pending = false;
+        if let Some(email) = &result.verification_email {
+            if EmailVerificationService::has_pending_code(redis_pool, email).await? {
+                verification_code_pending = true;
+            } else {
+                match EmailVerificationService::issue_code(redis_pool, email_provider, email).await
+                {
+                    Ok(()) => verification_code_pending = true,
+                    Err(e) => tracing::warn!("Failed to issue verification code: {}", e),
+                }
+            }
+        }
+
         // Generate JWT
-        let token = Self::generate_jwt(config, &user.uuid)?;
-        Ok((token, need_more_action))
+        let token = Self::generate_jwt(config, &result.user.uuid)?;
+        Ok(SocialLoginOutcome {
+            token,
+            need_more_action,
+            remaining_steps,
+            verification_code_pending,
+        })
+    }
+
+    pub(crate) fn generate_jwt(config: &Config, user_uuid: &str) -> AppResult<String> {
+        let now = Utc::now();
+        let expiration = now
+            .checked_add_signed(Duration::hours(24))
+            .expect("valid timestamp");
+
+        Self::encode_claims(
+            config,
+            &Claims {
+                sub: user_uuid.to_string(),
+                exp: expiration.timestamp() as usize,
+                iat: now.timestamp() as usize,
+                act: None,
+            },
+        )
+    }
+
+    /// Issues a read-only token for `target_uuid` carrying the staff member in `act`.
+    pub fn generate_impersonation_jwt(
+        config: &Config,
+        target_uuid: &str,
+        actor: ActorClaim,
+        expires_at: chrono::DateTime<Utc>,
+    ) -> AppResult<String> {
+        Self::encode_claims(
+            config,
+            &Claims {
+                sub: target_uuid.to_string(),
+                exp: expires_at.timestamp() as usize,
+                iat: Utc::now().timestamp() as usize,
+                act: Some(actor),
+            },
+        )
     }
 
-    fn generate_jwt(_config: &Config, user_uuid: &str) -> AppResult<String> {
+    fn encode_claims(_config: &Config, claims: &Claims) -> AppResult<String> {
         // Use a secret from config
         // TODO: Add JWT_SECRET to Config
         let secret = "secret_key_change_me".as_bytes();
 
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
-            .expect("valid timestamp")
-            .timestamp() as usize;
-
-        let claims = Claims {
-            sub: user_uuid.to_string(),
-            exp: expiration,
-            iat: Utc::now().timestamp() as usize,
-        };
-
         encode(
             &Header::default(),
-            &claims,
+            claims,
             &EncodingKey::from_secret(secret),
         )
-        .map_err(|e| AppError::InternalServerError("JWT generation failed".to_string()))
+        .map_err(|_| AppError::InternalServerError("JWT generation failed".to_string()))
     }
 }
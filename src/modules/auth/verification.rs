@@ -0,0 +1,96 @@
+use deadpool_redis::redis::AsyncCommands;
+use rand::Rng;
+
+use crate::modules::auth::providers::email::EmailProvider;
+use crate::shared::error::{AppError, AppResult};
+
+/// Verification codes expire after 5 minutes.
+pub const EMAIL_VERIFICATION_TTL_SECS: u64 = 300;
+
+pub struct EmailVerificationService;
+
+impl EmailVerificationService {
+    fn redis_key(email: &str) -> String {
+        format!("verification:{}", email)
+    }
+
+    /// A fresh 6-digit code.
+    pub fn new_code() -> String {
+        rand::rng().random_range(100000..999999u32).to_string()
+    }
+
+    /// Generates a 6-digit code, stores it in Redis and mails it to `email`.
+    pub async fn issue_code(
+        redis_pool: &deadpool_redis::Pool,
+        email_provider: &dyn EmailProvider,
+        email: &str,
+    ) -> AppResult<()> {
+        let code_str = Self::new_code();
+
+        let mut conn = redis_pool
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        let _: () = conn
+            .set_ex(
+                Self::redis_key(email),
+                &code_str,
+                EMAIL_VERIFICATION_TTL_SECS,
+            )
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        email_provider
+            .send_verification_code(email, &code_str)
+            .await
+    }
+
+    /// Returns true if an unexpired code was already issued for `email`.
+    pub async fn has_pending_code(
+        redis_pool: &deadpool_redis::Pool,
+        email: &str,
+    ) -> AppResult<bool> {
+        let mut conn = redis_pool
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        conn.exists(Self::redis_key(email))
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+
+    /// Checks `code` against the stored one and deletes it on success.
+    pub async fn consume_code(
+        redis_pool: &deadpool_redis::Pool,
+        email: &str,
+        code: &str,
+    ) -> AppResult<()> {
+        let mut conn = redis_pool
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        let redis_key = Self::redis_key(email);
+        let stored_code: Option<String> = conn.get(&redis_key).await.map_err(|_| {
+            AppError::InternalServerError("해당 이메일 인증 정보가 존재하지 않습니다.".to_string())
+        })?;
+
+        match stored_code {
+            Some(stored) if stored == code => {
+                let _: () = conn
+                    .del(&redis_key)
+                    .await
+                    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+                Ok(())
+            }
+            Some(_) => Err(AppError::BadRequest(
+                "Invalid verification code".to_string(),
+            )),
+            None => Err(AppError::BadRequest(
+                "No verification code found (or expired)".to_string(),
+            )),
+        }
+    }
+}
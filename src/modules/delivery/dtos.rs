@@ -0,0 +1,28 @@
+/// A new delivery address as entered by the user.
+pub struct CreateAddressDto {
+    pub label: Option<String>,
+    /// The first address a user saves becomes the default regardless.
+    pub is_default: bool,
+    pub recipient_name: String,
+    pub phone_number: String,
+    pub zip_code: String,
+    pub address: String,
+    pub detail_address: Option<String>,
+    pub entrance_password: Option<String>,
+    pub shipping_memo: Option<String>,
+}
+
+/// Fields to change on a saved address; the default is moved with
+/// `DeliveryService::set_default` instead. `None` leaves a field as is; an
+/// empty string clears one of the optional fields.
+#[derive(Default)]
+pub struct UpdateAddressDto {
+    pub label: Option<String>,
+    pub recipient_name: Option<String>,
+    pub phone_number: Option<String>,
+    pub zip_code: Option<String>,
+    pub address: Option<String>,
+    pub detail_address: Option<String>,
+    pub entrance_password: Option<String>,
+    pub shipping_memo: Option<String>,
+}
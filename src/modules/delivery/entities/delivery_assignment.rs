@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Fulfillment staff allowed to handle one delivery address, and so to see
+/// its decrypted fields. Only admins create these; `(delivery_id, staff_id)`
+/// is unique.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "delivery_assignments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub delivery_id: i32,
+    pub staff_id: i32,
+    /// The admin who made the assignment.
+    pub assigned_by: Option<i32>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::delivery_data::Entity",
+        from = "Column::DeliveryId",
+        to = "super::delivery_data::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    DeliveryData,
+}
+
+impl Related<super::delivery_data::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DeliveryData.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,20 +1,42 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "user_delivery_data")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub user_id: i32,
+    /// Free-form name such as 집 or 회사.
+    pub label: Option<String>,
+    /// Exactly one address per user is the default (partial unique index).
+    pub is_default: bool,
     pub recipient_name: String,
+    /// Encrypted, like `detail_address` and `entrance_password`; see
+    /// `DeliveryService::open`.
     pub phone_number: String,
     pub zip_code: String,
+    /// Road-name (도로명) form from the address lookup.
     pub address: String,
+    /// `None` on rows saved before addresses were normalized.
+    pub jibun_address: Option<String>,
+    /// 건물관리번호 of the matched building.
+    pub building_code: Option<String>,
+    /// WGS84, from the geocoder; `None` until the address could be located.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
     pub detail_address: Option<String>,
     pub entrance_password: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub shipping_memo: Option<String>,
+    /// KEK that wrapped `wrapped_data_key`. `None` on rows written before
+    /// encryption, which still hold plaintext until re-encrypted.
+    #[serde(skip)]
+    pub encryption_key_id: Option<String>,
+    /// This row's data key, wrapped by the KEK above.
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(skip)]
+    pub wrapped_data_key: Option<String>,
     #[serde(skip_deserializing)]
     pub created_at: DateTime,
     #[serde(skip_deserializing)]
@@ -1 +1,2 @@
+pub mod delivery_assignment;
 pub mod delivery_data;
@@ -0,0 +1,306 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use super::dtos::{CreateAddressDto, UpdateAddressDto};
+use super::entities::delivery_data;
+use super::lookup::AddressPage;
+use super::repository::DeliveryRepository;
+use super::service::DeliveryService;
+use crate::modules::place::distance::DistanceService;
+use crate::modules::place::entities::place_parent::{PlaceFulfillmentStatus, PlaceFulfillmentType};
+use crate::modules::place::repository::PlaceRepository;
+use crate::modules::users::entities::user;
+use crate::modules::users::repository::UserRepository;
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct AddressResponse {
+    pub id: i32,
+    pub label: Option<String>,
+    pub is_default: bool,
+    pub recipient_name: String,
+    pub phone_number: String,
+    pub zip_code: String,
+    pub address: String,
+    pub jibun_address: Option<String>,
+    pub building_code: Option<String>,
+    pub detail_address: Option<String>,
+    pub entrance_password: Option<String>,
+    pub shipping_memo: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl From<delivery_data::Model> for AddressResponse {
+    fn from(a: delivery_data::Model) -> Self {
+        Self {
+            id: a.id,
+            label: a.label,
+            is_default: a.is_default,
+            recipient_name: a.recipient_name,
+            phone_number: a.phone_number,
+            zip_code: a.zip_code,
+            address: a.address,
+            jibun_address: a.jibun_address,
+            building_code: a.building_code,
+            detail_address: a.detail_address,
+            entrance_password: a.entrance_password,
+            shipping_memo: a.shipping_memo,
+            created_at: a.created_at,
+            updated_at: a.updated_at,
+        }
+    }
+}
+
+async fn find_requester(user_repo: &dyn UserRepository, uuid: &str) -> AppResult<user::Model> {
+    user_repo
+        .find_by_uuid(uuid)
+        .await?
+        .ok_or(AppError::NotFound)
+}
+
+pub async fn list_my_addresses(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<Vec<AddressResponse>>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let addresses = DeliveryService::list(repo.as_ref(), &state.field_cipher, user.id).await?;
+    Ok(Json(addresses.into_iter().map(Into::into).collect()))
+}
+
+#[derive(Deserialize)]
+pub struct AddressSearchQuery {
+    pub q: String,
+    pub page: Option<u32>,
+}
+
+pub async fn search_addresses(
+    State(state): State<AppState>,
+    Query(query): Query<AddressSearchQuery>,
+) -> AppResult<Json<AddressPage>> {
+    let page =
+        DeliveryService::search_addresses(state.address_lookup.as_ref(), &query.q, query.page)
+            .await?;
+    Ok(Json(page))
+}
+
+/// `address` may be any form the search understands; it is saved in
+/// road-name form together with the matched building.
+#[derive(Deserialize)]
+pub struct CreateAddressRequest {
+    pub label: Option<String>,
+    #[serde(default)]
+    pub is_default: bool,
+    pub recipient_name: String,
+    pub phone_number: String,
+    pub zip_code: String,
+    pub address: String,
+    pub detail_address: Option<String>,
+    pub entrance_password: Option<String>,
+    pub shipping_memo: Option<String>,
+}
+
+pub async fn create_my_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<CreateAddressRequest>,
+) -> AppResult<(StatusCode, Json<AddressResponse>)> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let created = DeliveryService::create(
+        state.repo_manager.as_ref(),
+        repo.as_ref(),
+        &state.field_cipher,
+        state.address_lookup.as_ref(),
+        state.geocoder.as_ref(),
+        user.id,
+        CreateAddressDto {
+            label: body.label,
+            is_default: body.is_default,
+            recipient_name: body.recipient_name,
+            phone_number: body.phone_number,
+            zip_code: body.zip_code,
+            address: body.address,
+            detail_address: body.detail_address,
+            entrance_password: body.entrance_password,
+            shipping_memo: body.shipping_memo,
+        },
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// Omitted fields are left as is; `""` clears an optional field.
+#[derive(Deserialize)]
+pub struct UpdateAddressRequest {
+    pub label: Option<String>,
+    pub recipient_name: Option<String>,
+    pub phone_number: Option<String>,
+    pub zip_code: Option<String>,
+    pub address: Option<String>,
+    pub detail_address: Option<String>,
+    pub entrance_password: Option<String>,
+    pub shipping_memo: Option<String>,
+}
+
+pub async fn update_my_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+    Json(body): Json<UpdateAddressRequest>,
+) -> AppResult<Json<AddressResponse>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let updated = DeliveryService::update(
+        state.repo_manager.as_ref(),
+        repo.as_ref(),
+        &state.field_cipher,
+        state.address_lookup.as_ref(),
+        state.geocoder.as_ref(),
+        user.id,
+        id,
+        UpdateAddressDto {
+            label: body.label,
+            recipient_name: body.recipient_name,
+            phone_number: body.phone_number,
+            zip_code: body.zip_code,
+            address: body.address,
+            detail_address: body.detail_address,
+            entrance_password: body.entrance_password,
+            shipping_memo: body.shipping_memo,
+        },
+    )
+    .await?;
+    Ok(Json(updated.into()))
+}
+
+pub async fn delete_my_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    DeliveryService::delete(state.repo_manager.as_ref(), repo.as_ref(), user.id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn set_my_default_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AddressResponse>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let address = DeliveryService::set_default(
+        state.repo_manager.as_ref(),
+        repo.as_ref(),
+        &state.field_cipher,
+        user.id,
+        id,
+    )
+    .await?;
+    Ok(Json(address.into()))
+}
+
+#[derive(Serialize)]
+pub struct NearbyPlaceResponse {
+    pub id: i32,
+    pub place_name: String,
+    pub fulfillment_type: PlaceFulfillmentType,
+    pub fulfillment_status: PlaceFulfillmentStatus,
+    pub address: String,
+    pub distance_km: f64,
+}
+
+/// Places that can fulfil an order to this address, nearest first.
+pub async fn list_places_near_my_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<NearbyPlaceResponse>>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let place_repo = state.repo_manager.get::<Arc<dyn PlaceRepository>>().ok_or(
+        AppError::InternalServerError("PlaceRepository not registered".to_string()),
+    )?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let address = DeliveryService::find_owned(repo.as_ref(), user.id, id).await?;
+    let origin = DeliveryService::locate(repo.as_ref(), state.geocoder.as_ref(), &address).await?;
+    let nearby = DistanceService::orderable_places_near(place_repo.as_ref(), origin).await?;
+    Ok(Json(
+        nearby
+            .into_iter()
+            .map(|n| NearbyPlaceResponse {
+                id: n.place.id,
+                place_name: n.place.place_name,
+                fulfillment_type: n.place.fulfillment_type,
+                fulfillment_status: n.place.fulfillment_status,
+                address: n.place.address,
+                distance_km: n.distance_km,
+            })
+            .collect(),
+    ))
+}
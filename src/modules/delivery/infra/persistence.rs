@@ -1,8 +1,10 @@
 use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
+use std::sync::{Arc, Mutex};
 
 use crate::impl_sea_orm_repo;
-use crate::modules::delivery::entities::delivery_data;
+use crate::modules::delivery::entities::{delivery_assignment, delivery_data};
 use crate::modules::delivery::repository::DeliveryRepository;
 use crate::shared::error::{AppError, AppResult};
 use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
@@ -14,6 +16,10 @@ use crate::shared::repository::UnitOfWork;
 
 pub type PostgresDeliveryRepository = SeaOrmRepository<delivery_data::Entity>;
 
+/// First key of the two-key advisory lock taken by `lock_user`; the user id
+/// is the second.
+const ADDRESS_LOCK_NAMESPACE: i32 = 0x4144_4452; // "ADDR"
+
 impl_sea_orm_repo!(PostgresDeliveryRepository, DeliveryRepository, {
     async fn find_by_id(&self, id: i32) -> AppResult<Option<delivery_data::Model>> {
         match &self.conn {
@@ -33,6 +39,195 @@ impl_sea_orm_repo!(PostgresDeliveryRepository, DeliveryRepository, {
             }
         }
     }
+
+    async fn lock_user(&self, user_id: i32) -> AppResult<()> {
+        let DbOrTxn::Txn(mutex) = &self.conn else {
+            return Err(AppError::InternalServerError(
+                "Address lock requires a transaction".to_string(),
+            ));
+        };
+        let lock = mutex.lock().await;
+        let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+            "Transaction unavailable".to_string(),
+        ))?;
+        txn.execute_raw(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_advisory_xact_lock($1, $2)",
+            [ADDRESS_LOCK_NAMESPACE.into(), user_id.into()],
+        ))
+        .await
+        .map_err(AppError::DbError)?;
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<delivery_data::Model>> {
+        let query = delivery_data::Entity::find()
+            .filter(delivery_data::Column::UserId.eq(user_id))
+            .order_by_desc(delivery_data::Column::IsDefault)
+            .order_by_asc(delivery_data::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => address.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                address.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn update(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => address.update(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                address.update(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn clear_default(&self, user_id: i32) -> AppResult<()> {
+        let query = delivery_data::Entity::update_many()
+            .col_expr(delivery_data::Column::IsDefault, Expr::value(false))
+            .filter(delivery_data::Column::UserId.eq(user_id))
+            .filter(delivery_data::Column::IsDefault.eq(true));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.exec(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.exec(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: i32) -> AppResult<bool> {
+        let query = delivery_data::Entity::delete_by_id(id);
+        let result = match &self.conn {
+            DbOrTxn::Conn(c) => query.exec(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.exec(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn find_needing_reencryption(
+        &self,
+        active_key_id: &str,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<delivery_data::Model>> {
+        let query = delivery_data::Entity::find()
+            .filter(delivery_data::Column::Id.gt(after_id))
+            .filter(
+                Condition::any()
+                    .add(delivery_data::Column::EncryptionKeyId.is_null())
+                    .add(delivery_data::Column::EncryptionKeyId.ne(active_key_id)),
+            )
+            .order_by_asc(delivery_data::Column::Id)
+            .limit(limit);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn replace_sealed(
+        &self,
+        address: delivery_data::ActiveModel,
+        previous_wrapped_key: Option<String>,
+    ) -> AppResult<bool> {
+        let id = address.id.clone().unwrap();
+        let query = delivery_data::Entity::update_many()
+            .set(address)
+            .filter(delivery_data::Column::Id.eq(id))
+            .filter(match previous_wrapped_key {
+                Some(key) => delivery_data::Column::WrappedDataKey.eq(key),
+                None => delivery_data::Column::WrappedDataKey.is_null(),
+            });
+        let result = match &self.conn {
+            DbOrTxn::Conn(c) => query.exec(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.exec(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn create_assignment(
+        &self,
+        assignment: delivery_assignment::ActiveModel,
+    ) -> AppResult<delivery_assignment::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => assignment
+                .insert(c.as_ref())
+                .await
+                .map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                assignment.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn is_assigned(&self, delivery_id: i32, staff_id: i32) -> AppResult<bool> {
+        let query = delivery_assignment::Entity::find()
+            .filter(delivery_assignment::Column::DeliveryId.eq(delivery_id))
+            .filter(delivery_assignment::Column::StaffId.eq(staff_id));
+        let count = match &self.conn {
+            DbOrTxn::Conn(c) => query.count(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.count(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(count > 0)
+    }
 });
 
 // =========================================================================
@@ -40,12 +235,231 @@ impl_sea_orm_repo!(PostgresDeliveryRepository, DeliveryRepository, {
 // =========================================================================
 
 #[derive(Clone, Default)]
-pub struct InMemoryDeliveryRepository {}
+pub struct InMemoryDeliveryRepository {
+    addresses: Arc<Mutex<Vec<delivery_data::Model>>>,
+    counter: Arc<Mutex<i32>>,
+    assignments: Arc<Mutex<Vec<delivery_assignment::Model>>>,
+}
+
+/// Mirrors the partial unique index on `(user_id) WHERE is_default`.
+fn has_other_default(
+    addresses: &[delivery_data::Model],
+    user_id: i32,
+    except: Option<i32>,
+) -> bool {
+    addresses
+        .iter()
+        .any(|a| a.user_id == user_id && a.is_default && Some(a.id) != except)
+}
+
+fn default_conflict() -> AppError {
+    AppError::Conflict("User already has a default address".to_string())
+}
 
 #[async_trait]
 impl DeliveryRepository for InMemoryDeliveryRepository {
-    async fn find_by_id(&self, _id: i32) -> AppResult<Option<delivery_data::Model>> {
-        Ok(None) // Dummy implementation
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<delivery_data::Model>> {
+        let addresses = self.addresses.lock().unwrap();
+        Ok(addresses.iter().find(|a| a.id == id).cloned())
+    }
+
+    async fn lock_user(&self, _user_id: i32) -> AppResult<()> {
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<delivery_data::Model>> {
+        let addresses = self.addresses.lock().unwrap();
+        let mut owned: Vec<_> = addresses
+            .iter()
+            .filter(|a| a.user_id == user_id)
+            .cloned()
+            .collect();
+        owned.sort_by_key(|a| (!a.is_default, a.id));
+        Ok(owned)
+    }
+
+    async fn create(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model> {
+        let mut addresses = self.addresses.lock().unwrap();
+        let mut counter = self.counter.lock().unwrap();
+        let user_id = address.user_id.unwrap();
+        let is_default = address.is_default.unwrap();
+        if is_default && has_other_default(&addresses, user_id, None) {
+            return Err(default_conflict());
+        }
+        *counter += 1;
+        let model = delivery_data::Model {
+            id: *counter,
+            user_id,
+            label: address.label.unwrap(),
+            is_default,
+            recipient_name: address.recipient_name.unwrap(),
+            phone_number: address.phone_number.unwrap(),
+            zip_code: address.zip_code.unwrap(),
+            address: address.address.unwrap(),
+            jibun_address: address.jibun_address.unwrap(),
+            building_code: address.building_code.unwrap(),
+            latitude: address.latitude.unwrap(),
+            longitude: address.longitude.unwrap(),
+            detail_address: address.detail_address.unwrap(),
+            entrance_password: address.entrance_password.unwrap(),
+            shipping_memo: address.shipping_memo.unwrap(),
+            encryption_key_id: address.encryption_key_id.unwrap(),
+            wrapped_data_key: address.wrapped_data_key.unwrap(),
+            created_at: address.created_at.unwrap(),
+            updated_at: address.updated_at.unwrap(),
+        };
+        addresses.push(model.clone());
+        Ok(model)
+    }
+
+    async fn update(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model> {
+        let mut addresses = self.addresses.lock().unwrap();
+        let id = address.id.unwrap();
+        let user_id = addresses
+            .iter()
+            .find(|a| a.id == id)
+            .ok_or(AppError::NotFound)?
+            .user_id;
+        if address.is_default == Set(true) && has_other_default(&addresses, user_id, Some(id)) {
+            return Err(default_conflict());
+        }
+        let existing = addresses.iter_mut().find(|a| a.id == id).unwrap();
+        if let Set(v) = address.label {
+            existing.label = v;
+        }
+        if let Set(v) = address.is_default {
+            existing.is_default = v;
+        }
+        if let Set(v) = address.recipient_name {
+            existing.recipient_name = v;
+        }
+        if let Set(v) = address.phone_number {
+            existing.phone_number = v;
+        }
+        if let Set(v) = address.zip_code {
+            existing.zip_code = v;
+        }
+        if let Set(v) = address.address {
+            existing.address = v;
+        }
+        if let Set(v) = address.jibun_address {
+            existing.jibun_address = v;
+        }
+        if let Set(v) = address.building_code {
+            existing.building_code = v;
+        }
+        if let Set(v) = address.latitude {
+            existing.latitude = v;
+        }
+        if let Set(v) = address.longitude {
+            existing.longitude = v;
+        }
+        if let Set(v) = address.detail_address {
+            existing.detail_address = v;
+        }
+        if let Set(v) = address.entrance_password {
+            existing.entrance_password = v;
+        }
+        if let Set(v) = address.shipping_memo {
+            existing.shipping_memo = v;
+        }
+        if let Set(v) = address.encryption_key_id {
+            existing.encryption_key_id = v;
+        }
+        if let Set(v) = address.wrapped_data_key {
+            existing.wrapped_data_key = v;
+        }
+        if let Set(v) = address.updated_at {
+            existing.updated_at = v;
+        }
+        Ok(existing.clone())
+    }
+
+    async fn clear_default(&self, user_id: i32) -> AppResult<()> {
+        let mut addresses = self.addresses.lock().unwrap();
+        for a in addresses.iter_mut().filter(|a| a.user_id == user_id) {
+            a.is_default = false;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: i32) -> AppResult<bool> {
+        let mut addresses = self.addresses.lock().unwrap();
+        let before = addresses.len();
+        addresses.retain(|a| a.id != id);
+        Ok(addresses.len() < before)
+    }
+
+    async fn find_needing_reencryption(
+        &self,
+        active_key_id: &str,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<delivery_data::Model>> {
+        let addresses = self.addresses.lock().unwrap();
+        let mut pending: Vec<_> = addresses
+            .iter()
+            .filter(|a| a.id > after_id && a.encryption_key_id.as_deref() != Some(active_key_id))
+            .cloned()
+            .collect();
+        pending.sort_by_key(|a| a.id);
+        pending.truncate(limit as usize);
+        Ok(pending)
+    }
+
+    async fn replace_sealed(
+        &self,
+        address: delivery_data::ActiveModel,
+        previous_wrapped_key: Option<String>,
+    ) -> AppResult<bool> {
+        let id = address.id.clone().unwrap();
+        let current = self
+            .addresses
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|a| a.id == id)
+            .map(|a| a.wrapped_data_key.clone());
+        if current != Some(previous_wrapped_key) {
+            return Ok(false);
+        }
+        self.update(address).await?;
+        Ok(true)
+    }
+
+    async fn create_assignment(
+        &self,
+        assignment: delivery_assignment::ActiveModel,
+    ) -> AppResult<delivery_assignment::Model> {
+        let mut assignments = self.assignments.lock().unwrap();
+        let delivery_id = assignment.delivery_id.unwrap();
+        let staff_id = assignment.staff_id.unwrap();
+        if assignments
+            .iter()
+            .any(|a| a.delivery_id == delivery_id && a.staff_id == staff_id)
+        {
+            return Err(AppError::Conflict(
+                "Staff member is already assigned to this delivery".to_string(),
+            ));
+        }
+        let model = delivery_assignment::Model {
+            id: assignments.len() as i32 + 1,
+            delivery_id,
+            staff_id,
+            assigned_by: assignment.assigned_by.unwrap(),
+            created_at: assignment.created_at.unwrap(),
+        };
+        assignments.push(model.clone());
+        Ok(model)
+    }
+
+    async fn is_assigned(&self, delivery_id: i32, staff_id: i32) -> AppResult<bool> {
+        Ok(self
+            .assignments
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|a| a.delivery_id == delivery_id && a.staff_id == staff_id))
     }
 
     fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn DeliveryRepository>> {
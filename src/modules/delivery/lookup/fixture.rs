@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+
+use super::{AddressLookup, AddressLookupError, AddressLookupResult, AddressPage, AddressRecord};
+
+/// Serves a fixed list of addresses, for tests and for dev without a Juso key.
+/// A record matches when every word of the keyword appears in its road or
+/// jibun address or building name.
+pub struct FixtureAddressLookup {
+    records: Vec<AddressRecord>,
+}
+
+impl FixtureAddressLookup {
+    pub fn new(records: Vec<AddressRecord>) -> Self {
+        Self { records }
+    }
+}
+
+fn record(
+    road_address: &str,
+    jibun_address: &str,
+    zip_code: &str,
+    building_code: &str,
+    building_name: Option<&str>,
+) -> AddressRecord {
+    AddressRecord {
+        road_address: road_address.to_string(),
+        jibun_address: jibun_address.to_string(),
+        zip_code: zip_code.to_string(),
+        building_code: building_code.to_string(),
+        building_name: building_name.map(str::to_string),
+    }
+}
+
+impl Default for FixtureAddressLookup {
+    fn default() -> Self {
+        Self::new(vec![
+            record(
+                "서울특별시 강남구 테헤란로 152",
+                "서울특별시 강남구 역삼동 737 강남파이낸스센터",
+                "06236",
+                "1168010100107370000000001",
+                Some("강남파이낸스센터"),
+            ),
+            record(
+                "서울특별시 강남구 테헤란로 142",
+                "서울특별시 강남구 역삼동 736-1 아크플레이스",
+                "06236",
+                "1168010100107360001000001",
+                Some("아크플레이스"),
+            ),
+            record(
+                "서울특별시 중구 세종대로 110",
+                "서울특별시 중구 태평로1가 31 서울특별시청",
+                "04524",
+                "1114010300100310000000001",
+                Some("서울특별시청"),
+            ),
+            record(
+                "경기도 성남시 분당구 판교역로 235",
+                "경기도 성남시 분당구 삼평동 681 에이치스퀘어 엔동",
+                "13494",
+                "4113510900106810000000001",
+                Some("에이치스퀘어 엔동"),
+            ),
+            record(
+                "부산광역시 해운대구 해운대해변로 264",
+                "부산광역시 해운대구 우동 1411-1",
+                "48099",
+                "2635010500114110001000001",
+                None,
+            ),
+        ])
+    }
+}
+
+#[async_trait]
+impl AddressLookup for FixtureAddressLookup {
+    async fn search(
+        &self,
+        keyword: &str,
+        page: u32,
+        per_page: u32,
+    ) -> AddressLookupResult<AddressPage> {
+        if keyword.trim().chars().count() < 2 {
+            return Err(AddressLookupError::InvalidQuery(
+                "Search needs at least two characters".to_string(),
+            ));
+        }
+        let words: Vec<_> = keyword.split_whitespace().collect();
+        let matches: Vec<_> = self
+            .records
+            .iter()
+            .filter(|r| {
+                words.iter().all(|w| {
+                    r.road_address.contains(w)
+                        || r.jibun_address.contains(w)
+                        || r.building_name.as_deref().is_some_and(|n| n.contains(w))
+                })
+            })
+            .collect();
+        let skip = (page.saturating_sub(1) * per_page) as usize;
+        Ok(AddressPage {
+            total: matches.len() as u64,
+            page,
+            records: matches
+                .into_iter()
+                .skip(skip)
+                .take(per_page as usize)
+                .cloned()
+                .collect(),
+        })
+    }
+}
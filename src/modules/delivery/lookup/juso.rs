@@ -0,0 +1,258 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::{AddressLookup, AddressLookupError, AddressLookupResult, AddressPage, AddressRecord};
+use crate::shared::config::Config;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const SEARCH_PATH: &str = "/addrlink/addrLinkApi.do";
+
+#[derive(Clone, Debug)]
+pub struct JusoConfig {
+    /// e.g. `https://business.juso.go.kr`, or a local mock.
+    pub base_url: String,
+    /// 승인키 issued per service by the Juso portal.
+    pub api_key: String,
+}
+
+impl JusoConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            base_url: config.juso_api_base_url.trim_end_matches('/').to_string(),
+            api_key: config.juso_api_key.clone(),
+        }
+    }
+}
+
+/// Client for the 도로명주소 검색 API (`addrLinkApi.do`).
+pub struct JusoAddressLookup {
+    config: JusoConfig,
+    client: Client,
+}
+
+impl JusoAddressLookup {
+    pub fn new(config: JusoConfig) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build Juso HTTP client");
+        Self { config, client }
+    }
+}
+
+#[derive(Deserialize)]
+struct JusoResponse {
+    results: JusoResults,
+}
+
+#[derive(Deserialize)]
+struct JusoResults {
+    common: JusoCommon,
+    juso: Option<Vec<JusoItem>>,
+}
+
+/// Every value comes back as a string, counts included.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JusoCommon {
+    error_code: String,
+    error_message: String,
+    #[serde(default)]
+    total_count: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JusoItem {
+    road_addr_part1: String,
+    jibun_addr: String,
+    zip_no: String,
+    bd_mgt_sn: String,
+    #[serde(default)]
+    bd_nm: String,
+}
+
+impl From<JusoItem> for AddressRecord {
+    fn from(item: JusoItem) -> Self {
+        Self {
+            road_address: item.road_addr_part1.trim().to_string(),
+            jibun_address: item.jibun_addr.trim().to_string(),
+            zip_code: item.zip_no,
+            building_code: item.bd_mgt_sn,
+            building_name: Some(item.bd_nm.trim().to_string()).filter(|n| !n.is_empty()),
+        }
+    }
+}
+
+/// E0005-E0013 are problems with the keyword; -999 is their system error.
+fn map_error(code: &str, message: String) -> AddressLookupError {
+    match code {
+        "E0005" | "E0006" | "E0008" | "E0009" | "E0010" | "E0011" | "E0012" | "E0013" => {
+            AddressLookupError::InvalidQuery(message)
+        }
+        "-999" => AddressLookupError::Unavailable(format!("juso: {}", message)),
+        _ => AddressLookupError::Upstream(format!("juso {}: {}", code, message)),
+    }
+}
+
+#[async_trait]
+impl AddressLookup for JusoAddressLookup {
+    async fn search(
+        &self,
+        keyword: &str,
+        page: u32,
+        per_page: u32,
+    ) -> AddressLookupResult<AddressPage> {
+        let res = self
+            .client
+            .get(format!("{}{}", self.config.base_url, SEARCH_PATH))
+            .query(&[
+                ("confmKey", self.config.api_key.as_str()),
+                ("currentPage", &page.to_string()),
+                ("countPerPage", &per_page.to_string()),
+                ("keyword", keyword),
+                ("resultType", "json"),
+            ])
+            .send()
+            .await
+            .map_err(|e| AddressLookupError::Unavailable(format!("juso: {}", e)))?;
+        if res.status().is_server_error() {
+            return Err(AddressLookupError::Unavailable(format!(
+                "juso responded {}",
+                res.status()
+            )));
+        }
+        if !res.status().is_success() {
+            return Err(AddressLookupError::Upstream(format!(
+                "juso responded {}",
+                res.status()
+            )));
+        }
+        let body: JusoResponse = res
+            .json()
+            .await
+            .map_err(|e| AddressLookupError::Upstream(format!("juso: {}", e)))?;
+
+        let common = body.results.common;
+        if common.error_code != "0" {
+            return Err(map_error(&common.error_code, common.error_message));
+        }
+        Ok(AddressPage {
+            total: common.total_count.parse().unwrap_or_default(),
+            page,
+            records: body
+                .results
+                .juso
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, extract::Query, routing::get};
+    use std::collections::HashMap;
+
+    /// Answers like the real API: every keyword but "테헤란로 152" finds
+    /// nothing, "a" is too short, and a wrong key is rejected.
+    async fn spawn_mock_juso() -> String {
+        async fn search(Query(params): Query<HashMap<String, String>>) -> Json<serde_json::Value> {
+            let common = |code: &str, message: &str, total: &str| {
+                serde_json::json!({
+                    "errorCode": code,
+                    "errorMessage": message,
+                    "totalCount": total,
+                    "currentPage": params.get("currentPage"),
+                    "countPerPage": params.get("countPerPage"),
+                })
+            };
+            if params.get("confmKey").map(String::as_str) != Some("test-key") {
+                return Json(serde_json::json!({
+                    "results": {
+                        "common": common("E0001", "승인되지 않은 KEY 입니다.", "0"),
+                        "juso": null
+                    }
+                }));
+            }
+            let juso = match params.get("keyword").map(String::as_str) {
+                Some("a") => {
+                    return Json(serde_json::json!({
+                        "results": {
+                            "common": common("E0008", "검색어는 두글자 이상 입력되어야 합니다.", "0"),
+                            "juso": null
+                        }
+                    }));
+                }
+                Some("테헤란로 152") => serde_json::json!([{
+                    "roadAddr": "서울특별시 강남구 테헤란로 152 (역삼동, 강남파이낸스센터)",
+                    "roadAddrPart1": "서울특별시 강남구 테헤란로 152",
+                    "roadAddrPart2": " (역삼동, 강남파이낸스센터)",
+                    "jibunAddr": "서울특별시 강남구 역삼동 737 강남파이낸스센터",
+                    "zipNo": "06236",
+                    "bdMgtSn": "1168010100107370000000001",
+                    "bdNm": "강남파이낸스센터"
+                }]),
+                _ => serde_json::json!([]),
+            };
+            let total = juso.as_array().unwrap().len().to_string();
+            Json(serde_json::json!({
+                "results": { "common": common("0", "정상", &total), "juso": juso }
+            }))
+        }
+
+        let app = Router::new().route(SEARCH_PATH, get(search));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_search_against_mock_juso() {
+        let base_url = spawn_mock_juso().await;
+        let lookup = JusoAddressLookup::new(JusoConfig {
+            base_url: base_url.clone(),
+            api_key: "test-key".to_string(),
+        });
+
+        let page = lookup.search("테헤란로 152", 1, 10).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(
+            page.records,
+            vec![AddressRecord {
+                road_address: "서울특별시 강남구 테헤란로 152".to_string(),
+                jibun_address: "서울특별시 강남구 역삼동 737 강남파이낸스센터".to_string(),
+                zip_code: "06236".to_string(),
+                building_code: "1168010100107370000000001".to_string(),
+                building_name: Some("강남파이낸스센터".to_string()),
+            }]
+        );
+        assert!(
+            lookup
+                .search("없는 주소", 1, 10)
+                .await
+                .unwrap()
+                .records
+                .is_empty()
+        );
+        assert!(matches!(
+            lookup.search("a", 1, 10).await,
+            Err(AddressLookupError::InvalidQuery(_))
+        ));
+
+        let unauthorized = JusoAddressLookup::new(JusoConfig {
+            base_url,
+            api_key: "wrong".to_string(),
+        });
+        assert!(matches!(
+            unauthorized.search("테헤란로 152", 1, 10).await,
+            Err(AddressLookupError::Upstream(_))
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+pub mod fixture;
+pub mod juso;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+
+pub use fixture::FixtureAddressLookup;
+pub use juso::{JusoAddressLookup, JusoConfig};
+
+/// Failures searching the 도로명주소 database. Only `InvalidQuery` is the
+/// user's to fix; it is answered with a 400, the rest with 502/503.
+#[derive(Error, Debug)]
+pub enum AddressLookupError {
+    /// Juso refused the keyword (E0005-E0013): too short, only special
+    /// characters or SQL-like words.
+    #[error("Invalid search: {0}")]
+    InvalidQuery(String),
+
+    /// A 4xx, an error code we do not map (such as a bad `confmKey`) or a
+    /// body that is not the documented JSON. Usually our configuration.
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+
+    /// Juso could not be reached, answered 5xx or reported its own system
+    /// error (-999). Searching again later may succeed.
+    #[error("Upstream unavailable: {0}")]
+    Unavailable(String),
+}
+
+pub type AddressLookupResult<T> = Result<T, AddressLookupError>;
+
+/// One address in the national road-name address database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AddressRecord {
+    /// 도로명주소 without the reference part, e.g. `서울특별시 강남구 테헤란로 152`.
+    pub road_address: String,
+    /// 지번주소, e.g. `서울특별시 강남구 역삼동 737 강남파이낸스센터`.
+    pub jibun_address: String,
+    pub zip_code: String,
+    /// 건물관리번호, 25 digits, identifies the building across address changes.
+    pub building_code: String,
+    pub building_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressPage {
+    pub total: u64,
+    pub page: u32,
+    pub records: Vec<AddressRecord>,
+}
+
+/// Searches official addresses by free-text keyword.
+#[async_trait]
+pub trait AddressLookup: Send + Sync {
+    /// `page` starts at 1.
+    async fn search(
+        &self,
+        keyword: &str,
+        page: u32,
+        per_page: u32,
+    ) -> AddressLookupResult<AddressPage>;
+}
@@ -1,3 +1,9 @@
+pub mod dtos;
 pub mod entities;
+pub mod handlers;
 pub mod infra;
+pub mod lookup;
+pub mod reencryption;
 pub mod repository;
+pub mod router;
+pub mod service;
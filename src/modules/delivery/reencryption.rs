@@ -0,0 +1,247 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::entities::delivery_data;
+use super::repository::DeliveryRepository;
+use super::service::DeliveryService;
+use crate::shared::crypto::FieldCipher;
+use crate::shared::error::AppResult;
+use crate::shared::jobs::spawn_interval;
+use crate::shared::repository::RepositoryManager;
+
+pub const REENCRYPTION_BATCH_SIZE: u64 = 200;
+/// Rotation is rare, so an hourly check is enough. The first run happens at startup.
+pub const REENCRYPTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Moves addresses onto the active key after a rotation and encrypts rows
+/// written before encryption. Once a run reports nothing pending, the old
+/// key can be removed from `FIELD_ENCRYPTION_KEYS`.
+pub struct ReencryptionService;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReencryptionReport {
+    pub reencrypted: u64,
+    /// Rows that could not be opened, e.g. because their key was removed.
+    pub failed: u64,
+}
+
+impl ReencryptionService {
+    pub async fn run(
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+    ) -> AppResult<ReencryptionReport> {
+        let mut report = ReencryptionReport::default();
+        let mut after_id = 0;
+        loop {
+            let batch = repo
+                .find_needing_reencryption(
+                    cipher.active_key_id(),
+                    after_id,
+                    REENCRYPTION_BATCH_SIZE,
+                )
+                .await?;
+            let Some(last) = batch.last() else {
+                return Ok(report);
+            };
+            after_id = last.id;
+
+            for address in batch {
+                let id = address.id;
+                match Self::reencrypt(repo, cipher, address).await {
+                    Ok(true) => report.reencrypted += 1,
+                    // Edited meanwhile, and the edit already used the active key.
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::error!("Re-encrypting address {} failed: {}", id, e);
+                        report.failed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn reencrypt(
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        address: delivery_data::Model,
+    ) -> AppResult<bool> {
+        let previous_wrapped_key = address.wrapped_data_key.clone();
+        let plain = DeliveryService::open(cipher, address)?;
+        let mut active = delivery_data::ActiveModel {
+            id: Unchanged(plain.id),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        DeliveryService::seal(
+            cipher,
+            &mut active,
+            plain.phone_number,
+            plain.detail_address,
+            plain.entrance_password,
+        )?;
+        repo.replace_sealed(active, previous_wrapped_key).await
+    }
+
+    /// Runs on a fixed interval for the lifetime of the process.
+    pub fn spawn(
+        repo_manager: Arc<dyn RepositoryManager>,
+        cipher: Arc<FieldCipher>,
+    ) -> tokio::task::JoinHandle<()> {
+        spawn_interval(
+            "Address re-encryption",
+            REENCRYPTION_INTERVAL,
+            repo_manager,
+            move |repo: Arc<dyn DeliveryRepository>| {
+                let cipher = cipher.clone();
+                async move {
+                    let report = Self::run(repo.as_ref(), &cipher).await?;
+                    if report.reencrypted > 0 || report.failed > 0 {
+                        tracing::info!(
+                            "Re-encrypted {} addresses under key {} ({} failed)",
+                            report.reencrypted,
+                            cipher.active_key_id(),
+                            report.failed
+                        );
+                    }
+                    Ok(())
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::delivery::dtos::CreateAddressDto;
+    use crate::modules::delivery::infra::persistence::InMemoryDeliveryRepository;
+    use crate::modules::delivery::lookup::FixtureAddressLookup;
+    use crate::shared::geocoding::FixtureGeocoder;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    const K1: &str = "k1:QUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUE=";
+    const K2: &str = "k2:QkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkI=";
+
+    fn dto() -> CreateAddressDto {
+        CreateAddressDto {
+            label: None,
+            is_default: false,
+            recipient_name: "김기미".to_string(),
+            phone_number: "010-1234-5678".to_string(),
+            zip_code: "06236".to_string(),
+            address: "서울특별시 강남구 테헤란로 152".to_string(),
+            detail_address: Some("12층".to_string()),
+            entrance_password: Some("#1234*".to_string()),
+            shipping_memo: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_moves_rows_to_the_active_key() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let old = FieldCipher::new(K1, "k1").unwrap();
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+        let sealed = DeliveryService::create(&manager, &repo, &old, &lookup, &geocoder, 1, dto())
+            .await
+            .unwrap();
+
+        // A row written before encryption existed.
+        let now = chrono::Utc::now().naive_utc();
+        let legacy = repo
+            .create(delivery_data::ActiveModel {
+                user_id: Set(2),
+                label: Set(None),
+                is_default: Set(true),
+                recipient_name: Set("김기미".to_string()),
+                phone_number: Set("+821087654321".to_string()),
+                zip_code: Set("04524".to_string()),
+                address: Set("서울특별시 중구 세종대로 110".to_string()),
+                jibun_address: Set(None),
+                building_code: Set(None),
+                latitude: Set(None),
+                longitude: Set(None),
+                detail_address: Set(None),
+                entrance_password: Set(Some("0000".to_string())),
+                shipping_memo: Set(None),
+                encryption_key_id: Set(None),
+                wrapped_data_key: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let rotated = FieldCipher::new(&format!("{},{}", K1, K2), "k2").unwrap();
+        let report = ReencryptionService::run(&repo, &rotated).await.unwrap();
+        assert_eq!(
+            report,
+            ReencryptionReport {
+                reencrypted: 2,
+                failed: 0
+            }
+        );
+        assert_eq!(
+            ReencryptionService::run(&repo, &rotated).await.unwrap(),
+            ReencryptionReport::default()
+        );
+
+        // k1 can now be retired.
+        let retired = FieldCipher::new(K2, "k2").unwrap();
+        let stored = repo.find_by_id(legacy.id).await.unwrap().unwrap();
+        assert_eq!(stored.encryption_key_id.as_deref(), Some("k2"));
+        assert_ne!(stored.entrance_password.as_deref(), Some("0000"));
+        assert_eq!(
+            DeliveryService::open(&retired, stored)
+                .unwrap()
+                .entrance_password
+                .as_deref(),
+            Some("0000")
+        );
+        let mine = DeliveryService::list(&repo, &retired, 1).await.unwrap();
+        assert_eq!(mine[0].id, sealed.id);
+        assert_eq!(mine[0].phone_number, "+821012345678");
+        assert_eq!(mine[0].entrance_password.as_deref(), Some("#1234*"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_edit_wins_over_reencryption() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let old = FieldCipher::new(K1, "k1").unwrap();
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+        let created = DeliveryService::create(&manager, &repo, &old, &lookup, &geocoder, 1, dto())
+            .await
+            .unwrap();
+        let stale = repo.find_by_id(created.id).await.unwrap().unwrap();
+
+        let rotated = FieldCipher::new(&format!("{},{}", K1, K2), "k2").unwrap();
+        DeliveryService::update(
+            &manager,
+            &repo,
+            &rotated,
+            &lookup,
+            &geocoder,
+            1,
+            created.id,
+            crate::modules::delivery::dtos::UpdateAddressDto {
+                entrance_password: Some("#9999*".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            !ReencryptionService::reencrypt(&repo, &rotated, stale)
+                .await
+                .unwrap()
+        );
+        let list = DeliveryService::list(&repo, &rotated, 1).await.unwrap();
+        assert_eq!(list[0].entrance_password.as_deref(), Some("#9999*"));
+    }
+}
@@ -1,5 +1,38 @@
-use super::entities::delivery_data;
+use super::entities::{delivery_assignment, delivery_data};
 use crate::shared::error::AppResult;
 crate::define_repo!(DeliveryRepository, {
     async fn find_by_id(&self, id: i32) -> AppResult<Option<delivery_data::Model>>;
+    /// Holds off other address writes for the user until the transaction
+    /// ends, so a count read after it stays true until commit. Only valid
+    /// on a transactional repository.
+    async fn lock_user(&self, user_id: i32) -> AppResult<()>;
+    /// Default first, then oldest first.
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<delivery_data::Model>>;
+    async fn create(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model>;
+    async fn update(&self, address: delivery_data::ActiveModel) -> AppResult<delivery_data::Model>;
+    /// Unsets the user's default address. Run it in the same transaction as
+    /// setting the new one, before it, or the partial unique index trips.
+    async fn clear_default(&self, user_id: i32) -> AppResult<()>;
+    /// Returns whether a row was deleted.
+    async fn delete(&self, id: i32) -> AppResult<bool>;
+    /// Rows not sealed under `active_key_id` (including plaintext ones), by id.
+    async fn find_needing_reencryption(
+        &self,
+        active_key_id: &str,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<delivery_data::Model>>;
+    /// Writes re-encrypted fields only if the row still has
+    /// `previous_wrapped_key`, so a concurrent edit is not overwritten.
+    /// Returns whether the row was written.
+    async fn replace_sealed(
+        &self,
+        address: delivery_data::ActiveModel,
+        previous_wrapped_key: Option<String>,
+    ) -> AppResult<bool>;
+    async fn create_assignment(
+        &self,
+        assignment: delivery_assignment::ActiveModel,
+    ) -> AppResult<delivery_assignment::Model>;
+    async fn is_assigned(&self, delivery_id: i32, staff_id: i32) -> AppResult<bool>;
 });
@@ -0,0 +1,27 @@
+use super::handlers;
+use crate::shared::{middleware::require_email_verified, state::AppState};
+use axum::{Router, middleware, routing::get};
+
+/// Mounted at `/users/me/addresses`.
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::list_my_addresses).post(handlers::create_my_address),
+        )
+        .route("/search", get(handlers::search_addresses))
+        .route(
+            "/:id",
+            axum::routing::patch(handlers::update_my_address).delete(handlers::delete_my_address),
+        )
+        .route("/:id/places", get(handlers::list_places_near_my_address))
+        .route(
+            "/:id/default",
+            axum::routing::put(handlers::set_my_default_address),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_email_verified,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,997 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::SqlErr;
+
+use super::dtos::{CreateAddressDto, UpdateAddressDto};
+use super::entities::{delivery_assignment, delivery_data};
+use super::lookup::{AddressLookup, AddressPage, AddressRecord};
+use super::repository::DeliveryRepository;
+use crate::modules::users::entities::{
+    enums::{AccountStatus, UserRole},
+    user,
+};
+use crate::modules::users::utils::normalize_phone_e164;
+use crate::shared::crypto::{FieldCipher, WrappedKey};
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::geocoding::{Coordinates, Geocoder};
+use crate::shared::repository::{RepositoryManager, UnitOfWork};
+
+pub const MAX_ADDRESSES_PER_USER: usize = 10;
+pub const LABEL_MAX_CHARS: usize = 20;
+pub const RECIPIENT_NAME_MAX_CHARS: usize = 20;
+pub const ADDRESS_MAX_CHARS: usize = 200;
+pub const DETAIL_ADDRESS_MAX_CHARS: usize = 100;
+pub const ENTRANCE_PASSWORD_MAX_CHARS: usize = 30;
+pub const SHIPPING_MEMO_MAX_CHARS: usize = 200;
+pub const ADDRESS_SEARCH_PAGE_SIZE: u32 = 10;
+pub const ADDRESS_SEARCH_MAX_PAGE: u32 = 50;
+pub const ADDRESS_KEYWORD_MAX_CHARS: usize = 80;
+
+// Encrypted columns. The names are bound into each ciphertext.
+const PHONE_NUMBER: &str = "phone_number";
+const DETAIL_ADDRESS: &str = "detail_address";
+const ENTRANCE_PASSWORD: &str = "entrance_password";
+
+pub struct DeliveryService;
+
+impl DeliveryService {
+    /// The user's own addresses, decrypted.
+    pub async fn list(
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        user_id: i32,
+    ) -> AppResult<Vec<delivery_data::Model>> {
+        repo.find_by_user(user_id)
+            .await?
+            .into_iter()
+            .map(|a| Self::open(cipher, a))
+            .collect()
+    }
+
+    /// Road-name address search for the address form.
+    pub async fn search_addresses(
+        lookup: &dyn AddressLookup,
+        keyword: &str,
+        page: Option<u32>,
+    ) -> AppResult<AddressPage> {
+        let keyword = Self::clean_keyword(keyword)?;
+        let page = page.unwrap_or(1).clamp(1, ADDRESS_SEARCH_MAX_PAGE);
+        Ok(lookup
+            .search(&keyword, page, ADDRESS_SEARCH_PAGE_SIZE)
+            .await?)
+    }
+
+    /// Saves a new address. Making it the default moves the flag in the
+    /// same transaction; a user's first address is always the default.
+    pub async fn create(
+        repo_manager: &dyn RepositoryManager,
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        lookup: &dyn AddressLookup,
+        geocoder: &dyn Geocoder,
+        user_id: i32,
+        dto: CreateAddressDto,
+    ) -> AppResult<delivery_data::Model> {
+        // Checked again under the lock below; this only spares the lookup
+        // and geocoding calls when the user is already at the limit.
+        if repo.find_by_user(user_id).await?.len() >= MAX_ADDRESSES_PER_USER {
+            return Err(Self::too_many_addresses());
+        }
+        let wants_default = dto.is_default;
+        let normalized = Self::normalize_address(lookup, &dto.address, &dto.zip_code).await?;
+        let coordinates = Self::geocode(geocoder, &normalized.road_address).await;
+
+        let now = chrono::Utc::now().naive_utc();
+        let mut address = delivery_data::ActiveModel {
+            user_id: Set(user_id),
+            label: Set(Self::optional(dto.label, "Label", LABEL_MAX_CHARS)?),
+            recipient_name: Set(Self::validate_recipient_name(&dto.recipient_name)?),
+            zip_code: Set(normalized.zip_code),
+            address: Set(normalized.road_address),
+            jibun_address: Set(Some(normalized.jibun_address)),
+            building_code: Set(Some(normalized.building_code)),
+            latitude: Set(coordinates.map(|c| c.latitude)),
+            longitude: Set(coordinates.map(|c| c.longitude)),
+            shipping_memo: Set(Self::optional(
+                dto.shipping_memo,
+                "Shipping memo",
+                SHIPPING_MEMO_MAX_CHARS,
+            )?),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        Self::seal(
+            cipher,
+            &mut address,
+            Self::validate_phone(&dto.phone_number)?,
+            Self::optional(
+                dto.detail_address,
+                "Detail address",
+                DETAIL_ADDRESS_MAX_CHARS,
+            )?,
+            Self::optional(
+                dto.entrance_password,
+                "Entrance password",
+                ENTRANCE_PASSWORD_MAX_CHARS,
+            )?,
+        )?;
+
+        let uow = repo_manager.begin().await?;
+        let tx_repo = Self::tx_repo(repo, &*uow)?;
+        let result = async {
+            tx_repo.lock_user(user_id).await?;
+            let existing = tx_repo.find_by_user(user_id).await?;
+            if existing.len() >= MAX_ADDRESSES_PER_USER {
+                return Err(Self::too_many_addresses());
+            }
+            let is_default = wants_default || existing.is_empty();
+            address.is_default = Set(is_default);
+            if is_default {
+                tx_repo.clear_default(user_id).await?;
+            }
+            tx_repo.create(address).await
+        }
+        .await;
+        Self::open(cipher, Self::finish(uow, result).await?)
+    }
+
+    /// Any change re-seals the encrypted fields under a fresh data key. The
+    /// row is re-read under the per-user lock, so fields this edit leaves
+    /// alone keep whatever a concurrent edit wrote.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        repo_manager: &dyn RepositoryManager,
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        lookup: &dyn AddressLookup,
+        geocoder: &dyn Geocoder,
+        user_id: i32,
+        id: i32,
+        dto: UpdateAddressDto,
+    ) -> AppResult<delivery_data::Model> {
+        let snapshot = Self::find_owned(repo, user_id, id).await?;
+
+        let mut active = delivery_data::ActiveModel {
+            id: Unchanged(snapshot.id),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        if let Some(v) = dto.label {
+            active.label = Set(Self::optional(Some(v), "Label", LABEL_MAX_CHARS)?);
+        }
+        if let Some(v) = dto.recipient_name {
+            active.recipient_name = Set(Self::validate_recipient_name(&v)?);
+        }
+        // A partial address change fills the other half from the snapshot;
+        // that half is checked again under the lock.
+        let address_basis = dto.address.is_none() || dto.zip_code.is_none();
+        if dto.address.is_some() || dto.zip_code.is_some() {
+            let normalized = Self::normalize_address(
+                lookup,
+                dto.address.as_deref().unwrap_or(&snapshot.address),
+                dto.zip_code.as_deref().unwrap_or(&snapshot.zip_code),
+            )
+            .await?;
+            let coordinates = Self::geocode(geocoder, &normalized.road_address).await;
+            active.latitude = Set(coordinates.map(|c| c.latitude));
+            active.longitude = Set(coordinates.map(|c| c.longitude));
+            active.zip_code = Set(normalized.zip_code);
+            active.address = Set(normalized.road_address);
+            active.jibun_address = Set(Some(normalized.jibun_address));
+            active.building_code = Set(Some(normalized.building_code));
+        }
+        if let Some(v) = dto.shipping_memo {
+            active.shipping_memo = Set(Self::optional(
+                Some(v),
+                "Shipping memo",
+                SHIPPING_MEMO_MAX_CHARS,
+            )?);
+        }
+
+        let phone_number = dto
+            .phone_number
+            .map(|v| Self::validate_phone(&v))
+            .transpose()?;
+        let detail_address = dto
+            .detail_address
+            .map(|v| Self::optional(Some(v), "Detail address", DETAIL_ADDRESS_MAX_CHARS))
+            .transpose()?;
+        let entrance_password = dto
+            .entrance_password
+            .map(|v| Self::optional(Some(v), "Entrance password", ENTRANCE_PASSWORD_MAX_CHARS))
+            .transpose()?;
+
+        let uow = repo_manager.begin().await?;
+        let tx_repo = Self::tx_repo(repo, &*uow)?;
+        let result = async {
+            tx_repo.lock_user(user_id).await?;
+            let existing = Self::open(cipher, Self::find_owned(&*tx_repo, user_id, id).await?)?;
+            if address_basis
+                && active.address.is_set()
+                && (existing.address != snapshot.address || existing.zip_code != snapshot.zip_code)
+            {
+                return Err(AppError::Conflict(
+                    "Address was changed concurrently".to_string(),
+                ));
+            }
+            Self::seal(
+                cipher,
+                &mut active,
+                phone_number.unwrap_or(existing.phone_number),
+                detail_address.unwrap_or(existing.detail_address),
+                entrance_password.unwrap_or(existing.entrance_password),
+            )?;
+            tx_repo.update(active).await
+        }
+        .await;
+        Self::open(cipher, Self::finish(uow, result).await?)
+    }
+
+    /// Moves the default flag to `id` atomically.
+    pub async fn set_default(
+        repo_manager: &dyn RepositoryManager,
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        user_id: i32,
+        id: i32,
+    ) -> AppResult<delivery_data::Model> {
+        let uow = repo_manager.begin().await?;
+        let tx_repo = Self::tx_repo(repo, &*uow)?;
+        let result = async {
+            tx_repo.lock_user(user_id).await?;
+            let existing = Self::find_owned(&*tx_repo, user_id, id).await?;
+            if existing.is_default {
+                return Ok(existing);
+            }
+            tx_repo.clear_default(user_id).await?;
+            tx_repo
+                .update(delivery_data::ActiveModel {
+                    id: Unchanged(existing.id),
+                    is_default: Set(true),
+                    updated_at: Set(chrono::Utc::now().naive_utc()),
+                    ..Default::default()
+                })
+                .await
+        }
+        .await;
+        Self::open(cipher, Self::finish(uow, result).await?)
+    }
+
+    /// Deleting the default hands the flag to the oldest remaining address.
+    pub async fn delete(
+        repo_manager: &dyn RepositoryManager,
+        repo: &dyn DeliveryRepository,
+        user_id: i32,
+        id: i32,
+    ) -> AppResult<()> {
+        let uow = repo_manager.begin().await?;
+        let tx_repo = Self::tx_repo(repo, &*uow)?;
+        let result = async {
+            tx_repo.lock_user(user_id).await?;
+            let existing = Self::find_owned(&*tx_repo, user_id, id).await?;
+            tx_repo.delete(existing.id).await?;
+            if existing.is_default
+                && let Some(next) = tx_repo
+                    .find_by_user(user_id)
+                    .await?
+                    .into_iter()
+                    .min_by_key(|a| a.id)
+            {
+                tx_repo
+                    .update(delivery_data::ActiveModel {
+                        id: Unchanged(next.id),
+                        is_default: Set(true),
+                        updated_at: Set(chrono::Utc::now().naive_utc()),
+                        ..Default::default()
+                    })
+                    .await?;
+            }
+            Ok(())
+        }
+        .await;
+        Self::finish(uow, result).await
+    }
+
+    /// Someone else's address is reported as missing, not forbidden,
+    /// so ids cannot be probed.
+    pub async fn find_owned(
+        repo: &dyn DeliveryRepository,
+        user_id: i32,
+        id: i32,
+    ) -> AppResult<delivery_data::Model> {
+        repo.find_by_id(id)
+            .await?
+            .filter(|a| a.user_id == user_id)
+            .ok_or(AppError::NotFound)
+    }
+
+    /// Coordinates for routing. Addresses saved before geocoding, or while
+    /// the geocoder was down, are located now and the result is stored.
+    pub async fn locate(
+        repo: &dyn DeliveryRepository,
+        geocoder: &dyn Geocoder,
+        address: &delivery_data::Model,
+    ) -> AppResult<Coordinates> {
+        if let (Some(latitude), Some(longitude)) = (address.latitude, address.longitude) {
+            return Ok(Coordinates::new(latitude, longitude));
+        }
+        let coordinates = geocoder
+            .geocode(&address.address)
+            .await?
+            .ok_or(AppError::BadRequest(
+                "This address could not be located".to_string(),
+            ))?;
+        repo.update(delivery_data::ActiveModel {
+            id: Unchanged(address.id),
+            latitude: Set(Some(coordinates.latitude)),
+            longitude: Set(Some(coordinates.longitude)),
+            ..Default::default()
+        })
+        .await?;
+        Ok(coordinates)
+    }
+
+    /// Lets an active fulfillment staff member handle `delivery_id`. Only
+    /// admins assign; staff cannot grant themselves access.
+    pub async fn assign_staff(
+        repo: &dyn DeliveryRepository,
+        admin: &user::Model,
+        delivery_id: i32,
+        staff: &user::Model,
+    ) -> AppResult<delivery_assignment::Model> {
+        if admin.role != UserRole::Admin {
+            return Err(AppError::Forbidden(
+                "Only admins assign deliveries".to_string(),
+            ));
+        }
+        if staff.role != UserRole::Fulfillment || staff.account_status != AccountStatus::Active {
+            return Err(AppError::BadRequest(
+                "Deliveries can only be assigned to active fulfillment staff".to_string(),
+            ));
+        }
+        repo.find_by_id(delivery_id)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let assignment = repo
+            .create_assignment(delivery_assignment::ActiveModel {
+                delivery_id: Set(delivery_id),
+                staff_id: Set(staff.id),
+                assigned_by: Set(Some(admin.id)),
+                created_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| match e {
+                AppError::DbError(db)
+                    if matches!(db.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+                {
+                    AppError::Conflict(
+                        "Staff member is already assigned to this delivery".to_string(),
+                    )
+                }
+                e => e,
+            })?;
+        tracing::info!(
+            "Delivery {} assigned to staff {} by {}",
+            delivery_id,
+            staff.uuid,
+            admin.uuid
+        );
+        Ok(assignment)
+    }
+
+    /// Whether `staff` is an active fulfillment member with an assignment
+    /// for `delivery_id`.
+    pub async fn is_assigned_staff(
+        repo: &dyn DeliveryRepository,
+        delivery_id: i32,
+        staff: &user::Model,
+    ) -> AppResult<bool> {
+        Ok(staff.role == UserRole::Fulfillment
+            && staff.account_status == AccountStatus::Active
+            && repo.is_assigned(delivery_id, staff.id).await?)
+    }
+
+    /// Decrypted address for fulfillment. Besides the owner, only a
+    /// fulfillment staff member an admin assigned to the delivery may
+    /// read it.
+    pub async fn reveal(
+        repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        id: i32,
+        reader: &user::Model,
+    ) -> AppResult<delivery_data::Model> {
+        let address = repo.find_by_id(id).await?.ok_or(AppError::NotFound)?;
+        if address.user_id != reader.id {
+            if reader.role != UserRole::Fulfillment {
+                return Err(AppError::NotFound);
+            }
+            if !Self::is_assigned_staff(repo, address.id, reader).await? {
+                return Err(AppError::Forbidden(
+                    "Not assigned to this delivery".to_string(),
+                ));
+            }
+            tracing::info!("Address {} revealed to staff {}", address.id, reader.uuid);
+        }
+        Self::open(cipher, address)
+    }
+
+    /// Decrypts the sensitive fields. Rows from before encryption are
+    /// returned as stored.
+    pub fn open(
+        cipher: &FieldCipher,
+        address: delivery_data::Model,
+    ) -> AppResult<delivery_data::Model> {
+        let (Some(key_id), Some(wrapped)) = (
+            address.encryption_key_id.clone(),
+            address.wrapped_data_key.clone(),
+        ) else {
+            return Ok(address);
+        };
+        let key = cipher.open_data_key(&WrappedKey { key_id, wrapped })?;
+        let decrypt =
+            |field, value: Option<String>| value.map(|v| key.decrypt(field, &v)).transpose();
+        Ok(delivery_data::Model {
+            phone_number: key.decrypt(PHONE_NUMBER, &address.phone_number)?,
+            detail_address: decrypt(DETAIL_ADDRESS, address.detail_address.clone())?,
+            entrance_password: decrypt(ENTRANCE_PASSWORD, address.entrance_password.clone())?,
+            ..address
+        })
+    }
+
+    /// Encrypts the sensitive fields into `active` under a new data key
+    /// wrapped by the active KEK.
+    pub(crate) fn seal(
+        cipher: &FieldCipher,
+        active: &mut delivery_data::ActiveModel,
+        phone_number: String,
+        detail_address: Option<String>,
+        entrance_password: Option<String>,
+    ) -> AppResult<()> {
+        let (key, wrapped) = cipher.new_data_key()?;
+        let encrypt =
+            |field, value: Option<String>| value.map(|v| key.encrypt(field, &v)).transpose();
+        active.phone_number = Set(key.encrypt(PHONE_NUMBER, &phone_number)?);
+        active.detail_address = Set(encrypt(DETAIL_ADDRESS, detail_address)?);
+        active.entrance_password = Set(encrypt(ENTRANCE_PASSWORD, entrance_password)?);
+        active.encryption_key_id = Set(Some(wrapped.key_id));
+        active.wrapped_data_key = Set(Some(wrapped.wrapped));
+        Ok(())
+    }
+
+    fn too_many_addresses() -> AppError {
+        AppError::BadRequest(format!(
+            "At most {} addresses can be saved",
+            MAX_ADDRESSES_PER_USER
+        ))
+    }
+
+    fn tx_repo(
+        repo: &dyn DeliveryRepository,
+        uow: &dyn UnitOfWork,
+    ) -> AppResult<Box<dyn DeliveryRepository>> {
+        repo.with_transaction(uow)
+            .ok_or(AppError::InternalServerError(
+                "Failed to start transaction for delivery repo".to_string(),
+            ))
+    }
+
+    /// Commits on success. A concurrent default change trips the partial
+    /// unique index and is reported as a conflict.
+    async fn finish<T>(uow: Box<dyn UnitOfWork>, result: AppResult<T>) -> AppResult<T> {
+        match result {
+            Ok(value) => {
+                uow.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                uow.rollback().await?;
+                match e {
+                    AppError::DbError(db)
+                        if matches!(db.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+                    {
+                        Err(AppError::Conflict(
+                            "The default address was changed concurrently, please retry"
+                                .to_string(),
+                        ))
+                    }
+                    e => Err(e),
+                }
+            }
+        }
+    }
+
+    fn validate_recipient_name(raw: &str) -> AppResult<String> {
+        let name = raw.trim();
+        let len = name.chars().count();
+        if !(2..=RECIPIENT_NAME_MAX_CHARS).contains(&len) || name.chars().any(char::is_control) {
+            return Err(AppError::BadRequest(format!(
+                "Recipient name must be 2-{} characters",
+                RECIPIENT_NAME_MAX_CHARS
+            )));
+        }
+        Ok(name.to_string())
+    }
+
+    /// Stored in E.164 like user phone numbers.
+    fn validate_phone(raw: &str) -> AppResult<String> {
+        normalize_phone_e164(raw).ok_or(AppError::BadRequest("Invalid phone number".to_string()))
+    }
+
+    /// Korean postal codes (국가기초구역번호) have been 5 digits since 2015.
+    fn validate_zip_code(raw: &str) -> AppResult<String> {
+        let zip = raw.trim();
+        if zip.len() != 5 || !zip.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AppError::BadRequest(
+                "Zip code must be 5 digits".to_string(),
+            ));
+        }
+        Ok(zip.to_string())
+    }
+
+    /// Resolves what the user entered to one official address with the
+    /// given zip code. The address may be in road-name or jibun form; a
+    /// vague one has to be picked from the search instead.
+    async fn normalize_address(
+        lookup: &dyn AddressLookup,
+        raw_address: &str,
+        raw_zip_code: &str,
+    ) -> AppResult<AddressRecord> {
+        let address = Self::validate_address(raw_address)?;
+        let zip_code = Self::validate_zip_code(raw_zip_code)?;
+        let keyword = Self::clean_keyword(&address)?;
+        let mut candidates: Vec<_> = lookup
+            .search(&keyword, 1, ADDRESS_SEARCH_PAGE_SIZE)
+            .await?
+            .records
+            .into_iter()
+            .filter(|r| r.zip_code == zip_code)
+            .collect();
+
+        if let Some(i) = candidates
+            .iter()
+            .position(|r| r.road_address == address || r.jibun_address == address)
+        {
+            return Ok(candidates.swap_remove(i));
+        }
+        match candidates.len() {
+            1 => Ok(candidates.remove(0)),
+            0 => Err(AppError::BadRequest(
+                "Address not found for this zip code".to_string(),
+            )),
+            _ => Err(AppError::BadRequest(
+                "Address matches several buildings, please pick one from the search".to_string(),
+            )),
+        }
+    }
+
+    /// Saving never fails on geocoding; `locate` retries later.
+    async fn geocode(geocoder: &dyn Geocoder, road_address: &str) -> Option<Coordinates> {
+        match geocoder.geocode(road_address).await {
+            Ok(Some(coordinates)) => Some(coordinates),
+            Ok(None) => {
+                tracing::warn!("No coordinates for {:?}", road_address);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Geocoding {:?} failed: {}", road_address, e);
+                None
+            }
+        }
+    }
+
+    /// Juso rejects keywords containing these characters outright.
+    fn clean_keyword(raw: &str) -> AppResult<String> {
+        let keyword = raw
+            .split(|c: char| c.is_whitespace() || "%=<>[]".contains(c))
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let len = keyword.chars().count();
+        if !(2..=ADDRESS_KEYWORD_MAX_CHARS).contains(&len) {
+            return Err(AppError::BadRequest(format!(
+                "Search must be 2-{} characters",
+                ADDRESS_KEYWORD_MAX_CHARS
+            )));
+        }
+        Ok(keyword)
+    }
+
+    fn validate_address(raw: &str) -> AppResult<String> {
+        let address = raw.trim();
+        if address.is_empty() || address.chars().count() > ADDRESS_MAX_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "Address must be 1-{} characters",
+                ADDRESS_MAX_CHARS
+            )));
+        }
+        Ok(address.to_string())
+    }
+
+    /// Blank input clears the field.
+    fn optional(raw: Option<String>, field: &str, max_chars: usize) -> AppResult<Option<String>> {
+        let Some(value) = raw.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+        if value.chars().count() > max_chars {
+            return Err(AppError::BadRequest(format!(
+                "{} must be at most {} characters",
+                field, max_chars
+            )));
+        }
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::delivery::infra::persistence::InMemoryDeliveryRepository;
+    use crate::modules::delivery::lookup::FixtureAddressLookup;
+    use crate::modules::users::infra::fixtures::detached_user;
+    use crate::shared::config::Config;
+    use crate::shared::geocoding::FixtureGeocoder;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    fn home() -> CreateAddressDto {
+        CreateAddressDto {
+            label: Some("집".to_string()),
+            is_default: false,
+            recipient_name: " 김기미 ".to_string(),
+            phone_number: "010-1234-5678".to_string(),
+            zip_code: "06236".to_string(),
+            address: "서울특별시 강남구 테헤란로 152".to_string(),
+            detail_address: Some("12층".to_string()),
+            entrance_password: Some("".to_string()),
+            shipping_memo: None,
+        }
+    }
+
+    fn office() -> CreateAddressDto {
+        CreateAddressDto {
+            label: Some("회사".to_string()),
+            zip_code: "04524".to_string(),
+            address: "서울특별시 중구 세종대로 110".to_string(),
+            ..home()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_and_normalizes() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+
+        let created =
+            DeliveryService::create(&manager, &repo, &cipher, &lookup, &geocoder, 1, home())
+                .await
+                .unwrap();
+        assert_eq!(created.recipient_name, "김기미");
+        assert_eq!(created.phone_number, "+821012345678");
+        assert_eq!(created.entrance_password, None);
+        assert_eq!(
+            created.building_code.as_deref(),
+            Some("1168010100107370000000001")
+        );
+        assert_eq!(
+            (created.latitude, created.longitude),
+            (Some(37.5001), Some(127.0365))
+        );
+
+        // Jibun input is stored in road-name form.
+        let from_jibun = DeliveryService::create(
+            &manager,
+            &repo,
+            &cipher,
+            &lookup,
+            &geocoder,
+            1,
+            CreateAddressDto {
+                address: "서울특별시 중구 태평로1가 31".to_string(),
+                zip_code: "04524".to_string(),
+                ..home()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(from_jibun.address, "서울특별시 중구 세종대로 110");
+
+        for dto in [
+            // Both 테헤란로 142 and 152 share this zip code.
+            CreateAddressDto {
+                address: "강남구 테헤란로".to_string(),
+                ..home()
+            },
+            CreateAddressDto {
+                zip_code: "04524".to_string(),
+                ..home()
+            },
+            CreateAddressDto {
+                zip_code: "1234".to_string(),
+                ..home()
+            },
+            CreateAddressDto {
+                zip_code: "135-08".to_string(),
+                ..home()
+            },
+            CreateAddressDto {
+                phone_number: "call me".to_string(),
+                ..home()
+            },
+            CreateAddressDto {
+                recipient_name: " ".to_string(),
+                ..home()
+            },
+        ] {
+            assert!(matches!(
+                DeliveryService::create(&manager, &repo, &cipher, &lookup, &geocoder, 2, dto).await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_other_users_addresses_are_not_found() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+        let created =
+            DeliveryService::create(&manager, &repo, &cipher, &lookup, &geocoder, 1, home())
+                .await
+                .unwrap();
+
+        assert!(matches!(
+            DeliveryService::update(
+                &manager,
+                &repo,
+                &cipher,
+                &lookup,
+                &geocoder,
+                2,
+                created.id,
+                UpdateAddressDto::default()
+            )
+            .await,
+            Err(AppError::NotFound)
+        ));
+        assert!(matches!(
+            DeliveryService::set_default(&manager, &repo, &cipher, 2, created.id).await,
+            Err(AppError::NotFound)
+        ));
+        assert!(matches!(
+            DeliveryService::delete(&manager, &repo, 2, created.id).await,
+            Err(AppError::NotFound)
+        ));
+
+        let updated = DeliveryService::update(
+            &manager,
+            &repo,
+            &cipher,
+            &lookup,
+            &geocoder,
+            1,
+            created.id,
+            UpdateAddressDto {
+                zip_code: Some("04524".to_string()),
+                address: Some("서울특별시 중구 세종대로 110".to_string()),
+                detail_address: Some("".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.zip_code, "04524");
+        assert_eq!(
+            updated.jibun_address.as_deref(),
+            Some("서울특별시 중구 태평로1가 31 서울특별시청")
+        );
+        assert_eq!(updated.detail_address, None);
+        assert_eq!(updated.recipient_name, "김기미");
+
+        DeliveryService::delete(&manager, &repo, 1, created.id)
+            .await
+            .unwrap();
+        assert!(
+            DeliveryService::list(&repo, &cipher, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exactly_one_default() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+        let defaults = |list: Vec<delivery_data::Model>| {
+            list.iter()
+                .filter(|a| a.is_default)
+                .map(|a| a.id)
+                .collect::<Vec<_>>()
+        };
+
+        let first =
+            DeliveryService::create(&manager, &repo, &cipher, &lookup, &geocoder, 1, home())
+                .await
+                .unwrap();
+        assert!(first.is_default);
+        let second =
+            DeliveryService::create(&manager, &repo, &cipher, &lookup, &geocoder, 1, office())
+                .await
+                .unwrap();
+        assert!(!second.is_default);
+
+        DeliveryService::set_default(&manager, &repo, &cipher, 1, second.id)
+            .await
+            .unwrap();
+        let list = DeliveryService::list(&repo, &cipher, 1).await.unwrap();
+        assert_eq!(list[0].id, second.id);
+        assert_eq!(defaults(list), vec![second.id]);
+
+        let third = DeliveryService::create(
+            &manager,
+            &repo,
+            &cipher,
+            &lookup,
+            &geocoder,
+            1,
+            CreateAddressDto {
+                is_default: true,
+                label: None,
+                ..home()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            defaults(DeliveryService::list(&repo, &cipher, 1).await.unwrap()),
+            vec![third.id]
+        );
+
+        // The oldest remaining address takes over.
+        DeliveryService::delete(&manager, &repo, 1, third.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            defaults(DeliveryService::list(&repo, &cipher, 1).await.unwrap()),
+            vec![first.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sensitive_fields_are_encrypted_and_revealed_selectively() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+        let created = DeliveryService::create(
+            &manager,
+            &repo,
+            &cipher,
+            &lookup,
+            &geocoder,
+            1,
+            CreateAddressDto {
+                entrance_password: Some("#1234*".to_string()),
+                ..home()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.entrance_password.as_deref(), Some("#1234*"));
+
+        let stored = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(stored.encryption_key_id.as_deref(), Some("test"));
+        assert_ne!(stored.phone_number, "+821012345678");
+        assert_ne!(stored.detail_address.as_deref(), Some("12층"));
+        assert_ne!(stored.entrance_password.as_deref(), Some("#1234*"));
+
+        let owner = detached_user(1, UserRole::User);
+        let staff = detached_user(7, UserRole::Fulfillment);
+        let admin = detached_user(9, UserRole::Admin);
+        let revealed = DeliveryService::reveal(&repo, &cipher, created.id, &owner)
+            .await
+            .unwrap();
+        assert_eq!(revealed.entrance_password.as_deref(), Some("#1234*"));
+        assert!(matches!(
+            DeliveryService::reveal(&repo, &cipher, created.id, &staff).await,
+            Err(AppError::Forbidden(_))
+        ));
+
+        // Staff cannot assign themselves.
+        assert!(matches!(
+            DeliveryService::assign_staff(&repo, &staff, created.id, &staff).await,
+            Err(AppError::Forbidden(_))
+        ));
+        DeliveryService::assign_staff(&repo, &admin, created.id, &staff)
+            .await
+            .unwrap();
+        assert!(matches!(
+            DeliveryService::assign_staff(&repo, &admin, created.id, &staff).await,
+            Err(AppError::Conflict(_))
+        ));
+        let revealed = DeliveryService::reveal(&repo, &cipher, created.id, &staff)
+            .await
+            .unwrap();
+        assert_eq!(revealed.detail_address.as_deref(), Some("12층"));
+
+        assert!(matches!(
+            DeliveryService::reveal(
+                &repo,
+                &cipher,
+                created.id,
+                &detached_user(8, UserRole::Fulfillment)
+            )
+            .await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            DeliveryService::reveal(&repo, &cipher, created.id, &admin).await,
+            Err(AppError::NotFound)
+        ));
+        let suspended = user::Model {
+            account_status: AccountStatus::Banned,
+            ..staff
+        };
+        assert!(matches!(
+            DeliveryService::reveal(&repo, &cipher, created.id, &suspended).await,
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_cleans_keyword_and_pages() {
+        let lookup = FixtureAddressLookup::default();
+
+        let page = DeliveryService::search_addresses(&lookup, " 테헤란로 [강남] ", None)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.page, 1);
+        let page = DeliveryService::search_addresses(&lookup, "서울특별시", Some(2))
+            .await
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert!(page.records.is_empty());
+
+        assert!(matches!(
+            DeliveryService::search_addresses(&lookup, "%=", None).await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_locate_fills_missing_coordinates() {
+        let manager = InMemoryRepositoryManager::new();
+        let repo = InMemoryDeliveryRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        // The geocoder was down when the address was saved.
+        let down = FixtureGeocoder::new([]);
+        let created = DeliveryService::create(&manager, &repo, &cipher, &lookup, &down, 1, home())
+            .await
+            .unwrap();
+        assert_eq!(created.latitude, None);
+
+        assert!(matches!(
+            DeliveryService::locate(&repo, &down, &created).await,
+            Err(AppError::BadRequest(_))
+        ));
+        let geocoder = FixtureGeocoder::default();
+        let located = DeliveryService::locate(&repo, &geocoder, &created)
+            .await
+            .unwrap();
+        assert_eq!(located, Coordinates::new(37.5001, 127.0365));
+        let stored = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(stored.latitude, Some(37.5001));
+        assert_eq!(stored.updated_at, created.updated_at);
+    }
+}
@@ -1,4 +1,9 @@
+pub mod moderation;
+pub mod terms;
+pub mod admin;
 pub mod auth;
 pub mod delivery;
 pub mod users;
-pub mod place;
\ No newline at end of file
+pub mod place;
+pub mod profile;
+pub mod shipment;
\ No newline at end of file
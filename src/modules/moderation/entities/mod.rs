@@ -0,0 +1,2 @@
+pub mod user_block;
+pub mod user_report;
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// `blocker_id` blocked `blocked_id`. Blocks are mutual in effect: features
+/// checking them treat either direction as a block.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user_blocks")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub blocker_id: i32,
+    pub blocked_id: i32,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::BlockerId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Blocker,
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::BlockedId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Blocked,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,81 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReportReason {
+    #[sea_orm(string_value = "SPAM")]
+    #[serde(rename = "SPAM")]
+    Spam,
+    #[sea_orm(string_value = "ABUSE")]
+    #[serde(rename = "ABUSE")]
+    Abuse,
+    /// 사기 (e.g. off-platform payment requests)
+    #[sea_orm(string_value = "FRAUD")]
+    #[serde(rename = "FRAUD")]
+    Fraud,
+    #[sea_orm(string_value = "INAPPROPRIATE_CONTENT")]
+    #[serde(rename = "INAPPROPRIATE_CONTENT")]
+    InappropriateContent,
+    #[sea_orm(string_value = "IMPERSONATION")]
+    #[serde(rename = "IMPERSONATION")]
+    Impersonation,
+    #[sea_orm(string_value = "OTHER")]
+    #[serde(rename = "OTHER")]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReportStatus {
+    #[sea_orm(string_value = "OPEN")]
+    #[serde(rename = "OPEN")]
+    Open,
+    /// Support took action against the reported user.
+    #[sea_orm(string_value = "ACTIONED")]
+    #[serde(rename = "ACTIONED")]
+    Actioned,
+    #[sea_orm(string_value = "DISMISSED")]
+    #[serde(rename = "DISMISSED")]
+    Dismissed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub reporter_id: i32,
+    pub reported_id: i32,
+    pub reason: ReportReason,
+    /// Free text from the reporter: what happened, links, order numbers.
+    pub evidence: String,
+    pub status: ReportStatus,
+    /// Set by the admin who closed the report.
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::ReporterId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Reporter,
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::ReportedId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Reported,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
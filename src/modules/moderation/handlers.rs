@@ -0,0 +1,200 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use super::entities::user_report::{self, ReportReason, ReportStatus};
+use super::repository::ModerationRepository;
+use super::service::{BlockService, ReportService};
+use crate::modules::users::entities::user;
+use crate::modules::users::repository::UserRepository;
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct BlockedUserResponse {
+    pub uuid: String,
+    pub username: String,
+    pub handle: Option<String>,
+    pub blocked_at: chrono::NaiveDateTime,
+}
+
+/// A report with users referenced by their public UUIDs.
+#[derive(Serialize)]
+pub struct ReportResponse {
+    pub id: i32,
+    pub reporter_uuid: Option<String>,
+    pub reported_uuid: Option<String>,
+    pub reason: ReportReason,
+    pub evidence: String,
+    pub status: ReportStatus,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl ReportResponse {
+    /// Resolves internal user ids; users deleted since show up as `null`.
+    pub async fn build(
+        user_repo: &dyn UserRepository,
+        report: user_report::Model,
+    ) -> AppResult<Self> {
+        let reporter = user_repo.find_by_id(report.reporter_id).await?;
+        let reported = user_repo.find_by_id(report.reported_id).await?;
+        Ok(Self {
+            id: report.id,
+            reporter_uuid: reporter.map(|u| u.uuid),
+            reported_uuid: reported.map(|u| u.uuid),
+            reason: report.reason,
+            evidence: report.evidence,
+            status: report.status,
+            resolution_note: report.resolution_note,
+            resolved_at: report.resolved_at,
+            created_at: report.created_at,
+        })
+    }
+}
+
+async fn find_requester_and_target(
+    user_repo: &dyn UserRepository,
+    requester_uuid: &str,
+    target_uuid: &str,
+) -> AppResult<(user::Model, user::Model)> {
+    let requester = user_repo
+        .find_by_uuid(requester_uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let target = user_repo
+        .find_by_uuid(target_uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok((requester, target))
+}
+
+pub async fn list_my_blocks(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<Vec<BlockedUserResponse>>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = user_repo
+        .find_by_uuid(&claims.sub)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let mut blocked = Vec::new();
+    for block in repo.find_blocks_by_blocker(user.id).await? {
+        if let Some(target) = user_repo.find_by_id(block.blocked_id).await? {
+            blocked.push(BlockedUserResponse {
+                uuid: target.uuid,
+                username: target.username,
+                handle: target.handle,
+                blocked_at: block.created_at,
+            });
+        }
+    }
+
+    Ok(Json(blocked))
+}
+
+pub async fn block_user(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(uuid): Path<String>,
+) -> AppResult<StatusCode> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let (requester, target) =
+        find_requester_and_target(user_repo.as_ref(), &claims.sub, &uuid).await?;
+
+    BlockService::block(repo.as_ref(), requester.id, target.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn unblock_user(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(uuid): Path<String>,
+) -> AppResult<StatusCode> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let (requester, target) =
+        find_requester_and_target(user_repo.as_ref(), &claims.sub, &uuid).await?;
+
+    if !BlockService::unblock(repo.as_ref(), requester.id, target.id).await? {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct SubmitReportRequest {
+    pub target_uuid: String,
+    pub reason: ReportReason,
+    #[serde(default)]
+    pub evidence: String,
+    /// Also block the reported user in the same step.
+    #[serde(default)]
+    pub block: bool,
+}
+
+pub async fn submit_report(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<SubmitReportRequest>,
+) -> AppResult<(StatusCode, Json<ReportResponse>)> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ModerationRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ModerationRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let (requester, target) =
+        find_requester_and_target(user_repo.as_ref(), &claims.sub, &body.target_uuid).await?;
+
+    let report = ReportService::submit(
+        repo.as_ref(),
+        requester.id,
+        target.id,
+        body.reason,
+        &body.evidence,
+    )
+    .await?;
+    if body.block {
+        BlockService::block(repo.as_ref(), requester.id, target.id).await?;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ReportResponse::build(user_repo.as_ref(), report).await?),
+    ))
+}
@@ -0,0 +1,388 @@
+use async_trait::async_trait;
+use sea_orm::*;
+use std::sync::{Arc, Mutex};
+
+use crate::impl_sea_orm_repo;
+use crate::modules::moderation::entities::{
+    user_block,
+    user_report::{self, ReportStatus},
+};
+use crate::modules::moderation::repository::ModerationRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
+use crate::shared::repository::UnitOfWork;
+
+fn counterpart(block: &user_block::Model, user_id: i32) -> i32 {
+    if block.blocker_id == user_id {
+        block.blocked_id
+    } else {
+        block.blocker_id
+    }
+}
+
+// =========================================================================
+// Postgres Implementation
+// =========================================================================
+
+pub type PostgresModerationRepository = SeaOrmRepository<user_report::Entity>;
+
+impl_sea_orm_repo!(PostgresModerationRepository, ModerationRepository, {
+    async fn find_block(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> AppResult<Option<user_block::Model>> {
+        let query = user_block::Entity::find()
+            .filter(user_block::Column::BlockerId.eq(blocker_id))
+            .filter(user_block::Column::BlockedId.eq(blocked_id));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn exists_block_between(&self, a: i32, b: i32) -> AppResult<bool> {
+        let query = user_block::Entity::find().filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(user_block::Column::BlockerId.eq(a))
+                        .add(user_block::Column::BlockedId.eq(b)),
+                )
+                .add(
+                    Condition::all()
+                        .add(user_block::Column::BlockerId.eq(b))
+                        .add(user_block::Column::BlockedId.eq(a)),
+                ),
+        );
+        let count = match &self.conn {
+            DbOrTxn::Conn(c) => query.count(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.count(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+
+        Ok(count > 0)
+    }
+
+    async fn find_blocks_by_blocker(&self, blocker_id: i32) -> AppResult<Vec<user_block::Model>> {
+        let query = user_block::Entity::find()
+            .filter(user_block::Column::BlockerId.eq(blocker_id))
+            .order_by_desc(user_block::Column::CreatedAt)
+            .order_by_desc(user_block::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_block_counterpart_ids(&self, user_id: i32) -> AppResult<Vec<i32>> {
+        let query = user_block::Entity::find().filter(
+            Condition::any()
+                .add(user_block::Column::BlockerId.eq(user_id))
+                .add(user_block::Column::BlockedId.eq(user_id)),
+        );
+        let blocks = match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+
+        Ok(blocks.iter().map(|b| counterpart(b, user_id)).collect())
+    }
+
+    async fn create_block(&self, block: user_block::ActiveModel) -> AppResult<user_block::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => block.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                block.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn delete_block(&self, blocker_id: i32, blocked_id: i32) -> AppResult<bool> {
+        let query = user_block::Entity::delete_many()
+            .filter(user_block::Column::BlockerId.eq(blocker_id))
+            .filter(user_block::Column::BlockedId.eq(blocked_id));
+        let result = match &self.conn {
+            DbOrTxn::Conn(c) => query.exec(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.exec(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn find_report(&self, id: i32) -> AppResult<Option<user_report::Model>> {
+        let query = user_report::Entity::find_by_id(id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_open_report(
+        &self,
+        reporter_id: i32,
+        reported_id: i32,
+    ) -> AppResult<Option<user_report::Model>> {
+        let query = user_report::Entity::find()
+            .filter(user_report::Column::ReporterId.eq(reporter_id))
+            .filter(user_report::Column::ReportedId.eq(reported_id))
+            .filter(user_report::Column::Status.eq(ReportStatus::Open));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_reports(
+        &self,
+        status: Option<ReportStatus>,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> AppResult<Vec<user_report::Model>> {
+        let mut query = user_report::Entity::find()
+            .order_by_asc(user_report::Column::Id)
+            .limit(limit);
+        if let Some(status) = status {
+            query = query.filter(user_report::Column::Status.eq(status));
+        }
+        if let Some(after_id) = after_id {
+            query = query.filter(user_report::Column::Id.gt(after_id));
+        }
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => report.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                report.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn update_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => report.update(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                report.update(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+});
+
+// =========================================================================
+// InMemory Implementation
+// =========================================================================
+
+#[derive(Clone, Default)]
+pub struct InMemoryModerationRepository {
+    blocks: Arc<Mutex<Vec<user_block::Model>>>,
+    reports: Arc<Mutex<Vec<user_report::Model>>>,
+    counter: Arc<Mutex<i32>>,
+}
+
+impl InMemoryModerationRepository {
+    fn next_id(&self) -> i32 {
+        let mut counter = self.counter.lock().unwrap();
+        *counter += 1;
+        *counter
+    }
+}
+
+#[async_trait]
+impl ModerationRepository for InMemoryModerationRepository {
+    async fn find_block(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> AppResult<Option<user_block::Model>> {
+        let blocks = self.blocks.lock().unwrap();
+        Ok(blocks
+            .iter()
+            .find(|b| b.blocker_id == blocker_id && b.blocked_id == blocked_id)
+            .cloned())
+    }
+
+    async fn exists_block_between(&self, a: i32, b: i32) -> AppResult<bool> {
+        let blocks = self.blocks.lock().unwrap();
+        Ok(blocks.iter().any(|blk| {
+            (blk.blocker_id == a && blk.blocked_id == b)
+                || (blk.blocker_id == b && blk.blocked_id == a)
+        }))
+    }
+
+    async fn find_blocks_by_blocker(&self, blocker_id: i32) -> AppResult<Vec<user_block::Model>> {
+        let blocks = self.blocks.lock().unwrap();
+        let mut found: Vec<user_block::Model> = blocks
+            .iter()
+            .filter(|b| b.blocker_id == blocker_id)
+            .cloned()
+            .collect();
+        found.sort_by_key(|b| std::cmp::Reverse((b.created_at, b.id)));
+        Ok(found)
+    }
+
+    async fn find_block_counterpart_ids(&self, user_id: i32) -> AppResult<Vec<i32>> {
+        let blocks = self.blocks.lock().unwrap();
+        Ok(blocks
+            .iter()
+            .filter(|b| b.blocker_id == user_id || b.blocked_id == user_id)
+            .map(|b| counterpart(b, user_id))
+            .collect())
+    }
+
+    async fn create_block(&self, block: user_block::ActiveModel) -> AppResult<user_block::Model> {
+        let model = user_block::Model {
+            id: self.next_id(),
+            blocker_id: block.blocker_id.unwrap(),
+            blocked_id: block.blocked_id.unwrap(),
+            created_at: block.created_at.unwrap(),
+        };
+        self.blocks.lock().unwrap().push(model.clone());
+        Ok(model)
+    }
+
+    async fn delete_block(&self, blocker_id: i32, blocked_id: i32) -> AppResult<bool> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let before = blocks.len();
+        blocks.retain(|b| !(b.blocker_id == blocker_id && b.blocked_id == blocked_id));
+        Ok(blocks.len() < before)
+    }
+
+    async fn find_report(&self, id: i32) -> AppResult<Option<user_report::Model>> {
+        let reports = self.reports.lock().unwrap();
+        Ok(reports.iter().find(|r| r.id == id).cloned())
+    }
+
+    async fn find_open_report(
+        &self,
+        reporter_id: i32,
+        reported_id: i32,
+    ) -> AppResult<Option<user_report::Model>> {
+        let reports = self.reports.lock().unwrap();
+        Ok(reports
+            .iter()
+            .find(|r| {
+                r.reporter_id == reporter_id
+                    && r.reported_id == reported_id
+                    && r.status == ReportStatus::Open
+            })
+            .cloned())
+    }
+
+    async fn find_reports(
+        &self,
+        status: Option<ReportStatus>,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> AppResult<Vec<user_report::Model>> {
+        let reports = self.reports.lock().unwrap();
+        let mut found: Vec<user_report::Model> = reports
+            .iter()
+            .filter(|r| status.is_none_or(|s| r.status == s))
+            .filter(|r| after_id.is_none_or(|after| r.id > after))
+            .cloned()
+            .collect();
+        found.sort_by_key(|r| r.id);
+        found.truncate(limit as usize);
+        Ok(found)
+    }
+
+    async fn create_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model> {
+        let model = user_report::Model {
+            id: self.next_id(),
+            reporter_id: report.reporter_id.unwrap(),
+            reported_id: report.reported_id.unwrap(),
+            reason: report.reason.unwrap(),
+            evidence: report.evidence.unwrap(),
+            status: report.status.unwrap(),
+            resolution_note: report.resolution_note.unwrap(),
+            resolved_by: report.resolved_by.unwrap(),
+            resolved_at: report.resolved_at.unwrap(),
+            created_at: report.created_at.unwrap(),
+        };
+        self.reports.lock().unwrap().push(model.clone());
+        Ok(model)
+    }
+
+    async fn update_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model> {
+        let mut reports = self.reports.lock().unwrap();
+        let id = report.id.unwrap();
+        let existing = reports
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or(AppError::NotFound)?;
+        if let ActiveValue::Set(v) = report.status {
+            existing.status = v;
+        }
+        if let ActiveValue::Set(v) = report.resolution_note {
+            existing.resolution_note = v;
+        }
+        if let ActiveValue::Set(v) = report.resolved_by {
+            existing.resolved_by = v;
+        }
+        if let ActiveValue::Set(v) = report.resolved_at {
+            existing.resolved_at = v;
+        }
+        Ok(existing.clone())
+    }
+
+    fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn ModerationRepository>> {
+        Some(Box::new(self.clone()))
+    }
+}
@@ -0,0 +1,53 @@
+use super::entities::{
+    user_block,
+    user_report::{self, ReportStatus},
+};
+use crate::shared::error::AppResult;
+
+crate::define_repo!(ModerationRepository, {
+    async fn find_block(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> AppResult<Option<user_block::Model>>;
+
+    /// Whether either user has blocked the other.
+    async fn exists_block_between(&self, a: i32, b: i32) -> AppResult<bool>;
+
+    /// Newest first.
+    async fn find_blocks_by_blocker(&self, blocker_id: i32) -> AppResult<Vec<user_block::Model>>;
+
+    /// Users `user_id` blocked plus users who blocked `user_id`.
+    async fn find_block_counterpart_ids(&self, user_id: i32) -> AppResult<Vec<i32>>;
+
+    async fn create_block(&self, block: user_block::ActiveModel) -> AppResult<user_block::Model>;
+
+    /// Returns whether a block was removed.
+    async fn delete_block(&self, blocker_id: i32, blocked_id: i32) -> AppResult<bool>;
+
+    async fn find_report(&self, id: i32) -> AppResult<Option<user_report::Model>>;
+
+    async fn find_open_report(
+        &self,
+        reporter_id: i32,
+        reported_id: i32,
+    ) -> AppResult<Option<user_report::Model>>;
+
+    /// Triage queue, oldest first, resuming after `after_id`.
+    async fn find_reports(
+        &self,
+        status: Option<ReportStatus>,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> AppResult<Vec<user_report::Model>>;
+
+    async fn create_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model>;
+
+    async fn update_report(
+        &self,
+        report: user_report::ActiveModel,
+    ) -> AppResult<user_report::Model>;
+});
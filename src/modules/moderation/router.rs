@@ -0,0 +1,21 @@
+use super::handlers;
+use crate::shared::{middleware::require_email_verified, state::AppState};
+use axum::{
+    Router, middleware,
+    routing::{get, post, put},
+};
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/blocks", get(handlers::list_my_blocks))
+        .route(
+            "/blocks/:uuid",
+            put(handlers::block_user).delete(handlers::unblock_user),
+        )
+        .route("/reports", post(handlers::submit_report))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_email_verified,
+        ))
+        .with_state(state)
+}
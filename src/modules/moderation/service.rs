@@ -0,0 +1,291 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::SqlErr;
+use std::collections::HashSet;
+
+use super::entities::{
+    user_block,
+    user_report::{self, ReportReason, ReportStatus},
+};
+use super::repository::ModerationRepository;
+use crate::shared::error::{AppError, AppResult};
+
+pub const EVIDENCE_MAX_CHARS: usize = 2000;
+/// `OTHER` says nothing on its own, so it needs at least this much explanation.
+pub const OTHER_EVIDENCE_MIN_CHARS: usize = 10;
+pub const REPORT_QUEUE_DEFAULT_LIMIT: u64 = 50;
+pub const REPORT_QUEUE_MAX_LIMIT: u64 = 200;
+
+/// Block checks for other features (reviews, gifting, profiles).
+/// Blocks apply in both directions regardless of who created them.
+pub struct BlockService;
+
+impl BlockService {
+    /// Blocks `blocked_id`. Blocking someone already blocked is a no-op.
+    pub async fn block(
+        repo: &dyn ModerationRepository,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> AppResult<user_block::Model> {
+        if blocker_id == blocked_id {
+            return Err(AppError::BadRequest(
+                "You cannot block yourself".to_string(),
+            ));
+        }
+        if let Some(existing) = repo.find_block(blocker_id, blocked_id).await? {
+            return Ok(existing);
+        }
+
+        let created = repo
+            .create_block(user_block::ActiveModel {
+                blocker_id: Set(blocker_id),
+                blocked_id: Set(blocked_id),
+                created_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            })
+            .await;
+        match created {
+            // A concurrent request created the same block first.
+            Err(AppError::DbError(e))
+                if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+            {
+                repo.find_block(blocker_id, blocked_id)
+                    .await?
+                    .ok_or(AppError::InternalServerError(
+                        "Block vanished after conflict".to_string(),
+                    ))
+            }
+            other => other,
+        }
+    }
+
+    /// Returns whether there was a block to remove.
+    pub async fn unblock(
+        repo: &dyn ModerationRepository,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> AppResult<bool> {
+        repo.delete_block(blocker_id, blocked_id).await
+    }
+
+    pub async fn is_blocked(repo: &dyn ModerationRepository, a: i32, b: i32) -> AppResult<bool> {
+        if a == b {
+            return Ok(false);
+        }
+        repo.exists_block_between(a, b).await
+    }
+
+    /// Guard for interactions between two users, e.g. sending a gift.
+    pub async fn ensure_not_blocked(
+        repo: &dyn ModerationRepository,
+        a: i32,
+        b: i32,
+    ) -> AppResult<()> {
+        if Self::is_blocked(repo, a, b).await? {
+            return Err(AppError::Forbidden(
+                "Interaction with this user is blocked".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Users whose content `user_id` should not see, for filtering lists.
+    pub async fn hidden_user_ids(
+        repo: &dyn ModerationRepository,
+        user_id: i32,
+    ) -> AppResult<HashSet<i32>> {
+        Ok(repo
+            .find_block_counterpart_ids(user_id)
+            .await?
+            .into_iter()
+            .collect())
+    }
+}
+
+pub struct ReportService;
+
+impl ReportService {
+    /// Files a report. A reporter can have one open report per user; further
+    /// details belong in that one until support closes it.
+    pub async fn submit(
+        repo: &dyn ModerationRepository,
+        reporter_id: i32,
+        reported_id: i32,
+        reason: ReportReason,
+        evidence: &str,
+    ) -> AppResult<user_report::Model> {
+        if reporter_id == reported_id {
+            return Err(AppError::BadRequest(
+                "You cannot report yourself".to_string(),
+            ));
+        }
+        let evidence = evidence.trim();
+        let len = evidence.chars().count();
+        if len > EVIDENCE_MAX_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "Evidence must be at most {} characters",
+                EVIDENCE_MAX_CHARS
+            )));
+        }
+        if reason == ReportReason::Other && len < OTHER_EVIDENCE_MIN_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "Please describe the problem in at least {} characters",
+                OTHER_EVIDENCE_MIN_CHARS
+            )));
+        }
+        if repo
+            .find_open_report(reporter_id, reported_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(
+                "You already have an open report for this user".to_string(),
+            ));
+        }
+
+        repo.create_report(user_report::ActiveModel {
+            reporter_id: Set(reporter_id),
+            reported_id: Set(reported_id),
+            reason: Set(reason),
+            evidence: Set(evidence.to_string()),
+            status: Set(ReportStatus::Open),
+            resolution_note: Set(None),
+            resolved_by: Set(None),
+            resolved_at: Set(None),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .await
+    }
+
+    pub async fn queue(
+        repo: &dyn ModerationRepository,
+        status: Option<ReportStatus>,
+        after_id: Option<i32>,
+        limit: Option<u64>,
+    ) -> AppResult<Vec<user_report::Model>> {
+        let limit = limit
+            .unwrap_or(REPORT_QUEUE_DEFAULT_LIMIT)
+            .clamp(1, REPORT_QUEUE_MAX_LIMIT);
+        repo.find_reports(status, after_id, limit).await
+    }
+
+    /// Closes an open report as actioned or dismissed. Closed reports are final.
+    pub async fn resolve(
+        repo: &dyn ModerationRepository,
+        report_id: i32,
+        admin_id: i32,
+        status: ReportStatus,
+        note: Option<String>,
+    ) -> AppResult<user_report::Model> {
+        if status == ReportStatus::Open {
+            return Err(AppError::BadRequest(
+                "Reports can only be resolved as ACTIONED or DISMISSED".to_string(),
+            ));
+        }
+        let report = repo
+            .find_report(report_id)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if report.status != ReportStatus::Open {
+            return Err(AppError::Conflict(format!(
+                "Report is already {:?}",
+                report.status
+            )));
+        }
+
+        repo.update_report(user_report::ActiveModel {
+            id: Unchanged(report.id),
+            status: Set(status),
+            resolution_note: Set(note.filter(|n| !n.trim().is_empty())),
+            resolved_by: Set(Some(admin_id)),
+            resolved_at: Set(Some(chrono::Utc::now().naive_utc())),
+            ..Default::default()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::moderation::infra::persistence::InMemoryModerationRepository;
+
+    #[tokio::test]
+    async fn test_blocks_apply_both_ways() {
+        let repo = InMemoryModerationRepository::default();
+
+        let first = BlockService::block(&repo, 1, 2).await.unwrap();
+        let again = BlockService::block(&repo, 1, 2).await.unwrap();
+        assert_eq!(first.id, again.id);
+        assert!(BlockService::block(&repo, 1, 1).await.is_err());
+
+        assert!(BlockService::is_blocked(&repo, 2, 1).await.unwrap());
+        assert!(matches!(
+            BlockService::ensure_not_blocked(&repo, 2, 1).await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(!BlockService::is_blocked(&repo, 1, 3).await.unwrap());
+        assert_eq!(
+            BlockService::hidden_user_ids(&repo, 2).await.unwrap(),
+            HashSet::from([1])
+        );
+
+        // Only the blocker can lift a block.
+        assert!(!BlockService::unblock(&repo, 2, 1).await.unwrap());
+        assert!(BlockService::unblock(&repo, 1, 2).await.unwrap());
+        assert!(!BlockService::is_blocked(&repo, 1, 2).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_report_lifecycle() {
+        let repo = InMemoryModerationRepository::default();
+
+        assert!(
+            ReportService::submit(&repo, 1, 2, ReportReason::Other, "bad")
+                .await
+                .is_err()
+        );
+        let report = ReportService::submit(&repo, 1, 2, ReportReason::Fraud, "asked for cash")
+            .await
+            .unwrap();
+        assert_eq!(report.status, ReportStatus::Open);
+        assert!(matches!(
+            ReportService::submit(&repo, 1, 2, ReportReason::Spam, "").await,
+            Err(AppError::Conflict(_))
+        ));
+
+        let open = ReportService::queue(&repo, Some(ReportStatus::Open), None, None)
+            .await
+            .unwrap();
+        assert_eq!(open.len(), 1);
+
+        let resolved = ReportService::resolve(
+            &repo,
+            report.id,
+            99,
+            ReportStatus::Actioned,
+            Some("suspended".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resolved.status, ReportStatus::Actioned);
+        assert_eq!(resolved.resolved_by, Some(99));
+        assert!(matches!(
+            ReportService::resolve(&repo, report.id, 99, ReportStatus::Dismissed, None).await,
+            Err(AppError::Conflict(_))
+        ));
+        assert!(
+            ReportService::queue(&repo, Some(ReportStatus::Open), None, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Once closed, the same pair can report again.
+        assert!(
+            ReportService::submit(&repo, 1, 2, ReportReason::Spam, "")
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::domain::place::PlaceDomain;
+use super::repository::PlaceRepository;
+use crate::shared::error::AppResult;
+use crate::shared::geocoding::{Coordinates, Geocoder};
+use crate::shared::jobs::spawn_interval;
+use crate::shared::repository::RepositoryManager;
+
+pub const GEOCODING_BATCH_SIZE: u64 = 100;
+/// Places change rarely; new ones get coordinates within the hour.
+pub const GEOCODING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct NearbyPlace {
+    pub place: PlaceDomain,
+    pub distance_km: f64,
+}
+
+/// Picks fulfillment places by straight-line distance.
+pub struct DistanceService;
+
+impl DistanceService {
+    /// Places that can take orders right now, nearest first. Places without
+    /// coordinates yet are left out.
+    pub async fn orderable_places_near(
+        repo: &dyn PlaceRepository,
+        origin: Coordinates,
+    ) -> AppResult<Vec<NearbyPlace>> {
+        let mut nearby: Vec<_> = repo
+            .find_geocoded()
+            .await?
+            .into_iter()
+            .map(PlaceDomain::from)
+            .filter(PlaceDomain::can_order)
+            .filter_map(|place| {
+                let distance_km = place.coordinates()?.distance_km(&origin);
+                Some(NearbyPlace { place, distance_km })
+            })
+            .collect();
+        nearby.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        Ok(nearby)
+    }
+
+    /// Geocodes places that have no coordinates yet. Places the geocoder
+    /// does not know are logged and retried on the next run.
+    pub async fn geocode_missing(
+        repo: &dyn PlaceRepository,
+        geocoder: &dyn Geocoder,
+    ) -> AppResult<u64> {
+        let mut located = 0;
+        let mut after_id = 0;
+        loop {
+            let batch = repo
+                .find_missing_coordinates(after_id, GEOCODING_BATCH_SIZE)
+                .await?;
+            let Some(last) = batch.last() else {
+                return Ok(located);
+            };
+            after_id = last.id;
+
+            for place in batch {
+                match geocoder.geocode(&place.address).await {
+                    Ok(Some(coordinates)) => {
+                        repo.update_coordinates(place.id, coordinates).await?;
+                        located += 1;
+                    }
+                    Ok(None) => tracing::warn!(
+                        "Place {} address {:?} could not be geocoded",
+                        place.id,
+                        place.address
+                    ),
+                    Err(e) => tracing::warn!("Geocoding place {} failed: {}", place.id, e),
+                }
+            }
+        }
+    }
+
+    /// Runs `geocode_missing` on a fixed interval for the lifetime of the process.
+    pub fn spawn(
+        repo_manager: Arc<dyn RepositoryManager>,
+        geocoder: Arc<dyn Geocoder>,
+    ) -> tokio::task::JoinHandle<()> {
+        spawn_interval(
+            "Place geocoding",
+            GEOCODING_INTERVAL,
+            repo_manager,
+            move |repo: Arc<dyn PlaceRepository>| {
+                let geocoder = geocoder.clone();
+                async move {
+                    let located = Self::geocode_missing(repo.as_ref(), geocoder.as_ref()).await?;
+                    if located > 0 {
+                        tracing::info!("Geocoded {} places", located);
+                    }
+                    Ok(())
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::place::entities::place_parent::{
+        self, PlaceFulfillmentStatus, PlaceFulfillmentType,
+    };
+    use crate::modules::place::infra::persistence::InMemoryPlaceRepository;
+    use crate::shared::geocoding::FixtureGeocoder;
+
+    fn place(id: i32, address: &str, status: PlaceFulfillmentStatus) -> place_parent::Model {
+        place_parent::Model {
+            id,
+            place_name: format!("FC {}", id),
+            fulfillment_type: PlaceFulfillmentType::Distribution,
+            fulfillment_status: status,
+            open_time: vec![9, 0],
+            close_time: vec![18, 0],
+            is_public: true,
+            fc_able_split_shipping: false,
+            min_shipping_amount_krw: None,
+            base_currency_code: None,
+            base_currency_rate: None,
+            post_code: "".to_string(),
+            address: address.to_string(),
+            address_detail: "".to_string(),
+            sub: None,
+            live_detail_id: "".to_string(),
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_orderable_places_sorted_by_distance() {
+        let repo = InMemoryPlaceRepository::new(vec![
+            place(
+                1,
+                "부산광역시 해운대구 해운대해변로 264",
+                PlaceFulfillmentStatus::Active,
+            ),
+            place(
+                2,
+                "경기도 성남시 분당구 판교역로 235",
+                PlaceFulfillmentStatus::Delayed,
+            ),
+            place(
+                3,
+                "서울특별시 중구 세종대로 110",
+                PlaceFulfillmentStatus::Active,
+            ),
+            // Nearest, but cannot take orders.
+            place(
+                4,
+                "서울특별시 강남구 테헤란로 142",
+                PlaceFulfillmentStatus::Suspended,
+            ),
+            place_parent::Model {
+                is_public: false,
+                ..place(
+                    5,
+                    "서울특별시 강남구 테헤란로 142",
+                    PlaceFulfillmentStatus::Active,
+                )
+            },
+            // Unknown to the geocoder.
+            place(6, "어딘가 1", PlaceFulfillmentStatus::Active),
+        ]);
+        let geocoder = FixtureGeocoder::default();
+
+        assert_eq!(
+            DistanceService::geocode_missing(&repo, &geocoder)
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            DistanceService::geocode_missing(&repo, &geocoder)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let gangnam = Coordinates::new(37.5001, 127.0365);
+        let nearby = DistanceService::orderable_places_near(&repo, gangnam)
+            .await
+            .unwrap();
+        let ids: Vec<_> = nearby.iter().map(|n| n.place.id).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+        assert!(
+            nearby
+                .windows(2)
+                .all(|w| w[0].distance_km <= w[1].distance_km)
+        );
+    }
+}
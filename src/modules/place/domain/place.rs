@@ -1,5 +1,7 @@
+use crate::modules::place::entities::place_parent;
 use crate::modules::place::entities::place_parent::PlaceFulfillmentStatus;
 use crate::modules::place::entities::place_parent::PlaceFulfillmentType;
+use crate::shared::geocoding::Coordinates;
 
 pub struct PlaceDomain {
     pub id: i32,
@@ -17,6 +19,32 @@ pub struct PlaceDomain {
     pub address: String,
     pub address_detail: String,
     pub sub: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl From<place_parent::Model> for PlaceDomain {
+    fn from(model: place_parent::Model) -> Self {
+        Self {
+            id: model.id,
+            place_name: model.place_name,
+            fulfillment_type: model.fulfillment_type,
+            fulfillment_status: model.fulfillment_status,
+            open_time: model.open_time,
+            close_time: model.close_time,
+            is_public: model.is_public,
+            fc_able_split_shipping: model.fc_able_split_shipping,
+            min_shipping_amount_krw: model.min_shipping_amount_krw,
+            base_currency_code: model.base_currency_code,
+            base_currency_rate: model.base_currency_rate,
+            post_code: model.post_code,
+            address: model.address,
+            address_detail: model.address_detail,
+            sub: model.sub,
+            latitude: model.latitude,
+            longitude: model.longitude,
+        }
+    }
 }
 
 impl PlaceDomain {
@@ -29,19 +57,23 @@ impl PlaceDomain {
             && self.fulfillment_type != PlaceFulfillmentType::Customer
     }
 
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        Some(Coordinates::new(self.latitude?, self.longitude?))
+    }
+
     pub fn set_amount(&self, amount: i32) -> Result<(), String> {
         if amount < 0 {
             return Err("Amount must be greater than 0".to_string());
         }
 
-        let mut amount: f64 = amount as f64;
+        let _amount: f64 = amount as f64;
         if self.base_currency_code.is_some() && self.base_currency_code.as_ref().unwrap() != "KRW" {
             // krw기반계산이 아니면 별도 처리를 해줘야한다.
             // Ex) 1$ = 1,300이면 base_currency_rate = 1300
             // amount는 해당 currency의 금액으로 들어오기 때문에
             // min_currency_krw = 1300이면 min_currency의 실제 코드는
             // min_curreny_real = min_currency_krw / base_currency_rate
-            let min_currency_real =
+            let _min_currency_real =
                 self.min_shipping_amount_krw.unwrap() as f64 / self.base_currency_rate.unwrap();
         }
 
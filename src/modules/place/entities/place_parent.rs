@@ -2,7 +2,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[sea_orm::model]
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, DeriveEntityModel)]
 #[sea_orm(table_name = "place_parent", rename_all = "camelCase")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -24,6 +24,11 @@ pub struct Model {
     pub post_code: String,
     pub address: String,
     pub address_detail: String,
+    /// WGS84, filled in by the geocoding job.
+    #[sea_orm(default = None)]
+    pub latitude: Option<f64>,
+    #[sea_orm(default = None)]
+    pub longitude: Option<f64>,
     #[sea_orm(default = None)]
     pub sub: Option<String>,
     pub live_detail_id: String,
@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::{Arc, Mutex};
+
+use crate::impl_sea_orm_repo;
+use crate::modules::place::entities::place_parent;
+use crate::modules::place::repository::PlaceRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::geocoding::Coordinates;
+use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
+use crate::shared::repository::UnitOfWork;
+
+// =========================================================================
+// Postgres Implementation
+// =========================================================================
+
+pub type PostgresPlaceRepository = SeaOrmRepository<place_parent::Entity>;
+
+impl_sea_orm_repo!(PostgresPlaceRepository, PlaceRepository, {
+    async fn find_geocoded(&self) -> AppResult<Vec<place_parent::Model>> {
+        let query = place_parent::Entity::find()
+            .filter(place_parent::Column::Latitude.is_not_null())
+            .filter(place_parent::Column::Longitude.is_not_null());
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_missing_coordinates(
+        &self,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<place_parent::Model>> {
+        let query = place_parent::Entity::find()
+            .filter(place_parent::Column::Id.gt(after_id))
+            .filter(
+                Condition::any()
+                    .add(place_parent::Column::Latitude.is_null())
+                    .add(place_parent::Column::Longitude.is_null()),
+            )
+            .order_by_asc(place_parent::Column::Id)
+            .limit(limit);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn update_coordinates(&self, id: i32, coordinates: Coordinates) -> AppResult<()> {
+        let query = place_parent::Entity::update_many()
+            .col_expr(
+                place_parent::Column::Latitude,
+                Expr::value(coordinates.latitude),
+            )
+            .col_expr(
+                place_parent::Column::Longitude,
+                Expr::value(coordinates.longitude),
+            )
+            .filter(place_parent::Column::Id.eq(id));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.exec(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.exec(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(())
+    }
+});
+
+// =========================================================================
+// InMemory Implementation
+// =========================================================================
+
+/// Places are managed outside this service, so the in-memory store is
+/// seeded up front instead of through the repository.
+#[derive(Clone, Default)]
+pub struct InMemoryPlaceRepository {
+    places: Arc<Mutex<Vec<place_parent::Model>>>,
+}
+
+impl InMemoryPlaceRepository {
+    pub fn new(places: Vec<place_parent::Model>) -> Self {
+        Self {
+            places: Arc::new(Mutex::new(places)),
+        }
+    }
+}
+
+#[async_trait]
+impl PlaceRepository for InMemoryPlaceRepository {
+    async fn find_geocoded(&self) -> AppResult<Vec<place_parent::Model>> {
+        let places = self.places.lock().unwrap();
+        Ok(places
+            .iter()
+            .filter(|p| p.latitude.is_some() && p.longitude.is_some())
+            .cloned()
+            .collect())
+    }
+
+    async fn find_missing_coordinates(
+        &self,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<place_parent::Model>> {
+        let places = self.places.lock().unwrap();
+        let mut pending: Vec<_> = places
+            .iter()
+            .filter(|p| p.id > after_id && (p.latitude.is_none() || p.longitude.is_none()))
+            .cloned()
+            .collect();
+        pending.sort_by_key(|p| p.id);
+        pending.truncate(limit as usize);
+        Ok(pending)
+    }
+
+    async fn update_coordinates(&self, id: i32, coordinates: Coordinates) -> AppResult<()> {
+        let mut places = self.places.lock().unwrap();
+        if let Some(place) = places.iter_mut().find(|p| p.id == id) {
+            place.latitude = Some(coordinates.latitude);
+            place.longitude = Some(coordinates.longitude);
+        }
+        Ok(())
+    }
+
+    fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn PlaceRepository>> {
+        Some(Box::new(self.clone()))
+    }
+}
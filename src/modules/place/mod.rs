@@ -1,2 +1,5 @@
+pub mod distance;
 pub mod domain;
 pub mod entities;
+pub mod infra;
+pub mod repository;
@@ -0,0 +1,14 @@
+use super::entities::place_parent;
+use crate::shared::error::AppResult;
+use crate::shared::geocoding::Coordinates;
+crate::define_repo!(PlaceRepository, {
+    /// Places with coordinates, in no particular order.
+    async fn find_geocoded(&self) -> AppResult<Vec<place_parent::Model>>;
+    /// Places still waiting for coordinates, by id.
+    async fn find_missing_coordinates(
+        &self,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<place_parent::Model>>;
+    async fn update_coordinates(&self, id: i32, coordinates: Coordinates) -> AppResult<()>;
+});
@@ -0,0 +1,54 @@
+use axum::{Json, extract::State};
+use serde::Serialize;
+
+use super::service::ProfileQueryService;
+use crate::modules::delivery::handlers::AddressResponse;
+use crate::modules::delivery::repository::DeliveryRepository;
+use crate::modules::users::handlers::{UserProjection, UserResponse};
+use crate::modules::users::repository::UserRepository;
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct MeResponse {
+    #[serde(flatten)]
+    pub user: UserResponse,
+    /// Default address first, then oldest first.
+    pub addresses: Vec<AddressResponse>,
+}
+
+pub async fn get_me(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<MeResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let delivery_repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+
+    let profile = ProfileQueryService::load(
+        user_repo.as_ref(),
+        delivery_repo.as_ref(),
+        &state.field_cipher,
+        &claims.sub,
+    )
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(MeResponse {
+        user: UserResponse::project(
+            profile.user,
+            UserProjection::Private,
+            state.storage.as_ref(),
+        ),
+        addresses: profile.addresses.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,127 @@
+use crate::modules::delivery::entities::delivery_data;
+use crate::modules::delivery::repository::DeliveryRepository;
+use crate::modules::delivery::service::DeliveryService;
+use crate::modules::users::entities::user;
+use crate::modules::users::repository::UserRepository;
+use crate::shared::crypto::FieldCipher;
+use crate::shared::error::AppResult;
+
+/// Everything the owner sees about their own account.
+pub struct UserProfileView {
+    /// Loaded with its verification and social accounts.
+    pub user: user::Model,
+    /// Decrypted, default first.
+    pub addresses: Vec<delivery_data::Model>,
+}
+
+/// Assembles profile read models across the users and delivery modules, so
+/// neither has to know about the other's tables. A profile takes three
+/// queries however many socials or addresses the user has: user joined with
+/// verification, socials, and addresses.
+pub struct ProfileQueryService;
+
+impl ProfileQueryService {
+    pub async fn load(
+        users: &dyn UserRepository,
+        addresses: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        uuid: &str,
+    ) -> AppResult<Option<UserProfileView>> {
+        let Some(user) = users.find_with_details_by_uuid(uuid).await? else {
+            return Ok(None);
+        };
+        let addresses = DeliveryService::list(addresses, cipher, user.id).await?;
+        Ok(Some(UserProfileView { user, addresses }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::delivery::dtos::CreateAddressDto;
+    use crate::modules::delivery::infra::persistence::InMemoryDeliveryRepository;
+    use crate::modules::delivery::lookup::FixtureAddressLookup;
+    use crate::modules::users::infra::fixtures::sign_up;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::shared::config::Config;
+    use crate::shared::geocoding::FixtureGeocoder;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    fn address(zip_code: &str, address: &str, is_default: bool) -> CreateAddressDto {
+        CreateAddressDto {
+            label: None,
+            is_default,
+            recipient_name: "김기미".to_string(),
+            phone_number: "010-1234-5678".to_string(),
+            zip_code: zip_code.to_string(),
+            address: address.to_string(),
+            detail_address: Some("12층".to_string()),
+            entrance_password: Some("#1234*".to_string()),
+            shipping_memo: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_profile_includes_decrypted_addresses() {
+        let users = InMemoryUserRepository::default();
+        let addresses = InMemoryDeliveryRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let lookup = FixtureAddressLookup::default();
+        let geocoder = FixtureGeocoder::default();
+
+        let owner = sign_up(&users, "owner").await;
+        let other = sign_up(&users, "other").await;
+        for (user_id, dto) in [
+            (
+                owner.id,
+                address("06236", "서울특별시 강남구 테헤란로 152", false),
+            ),
+            (
+                owner.id,
+                address("04524", "서울특별시 중구 세종대로 110", true),
+            ),
+            (
+                other.id,
+                address("13494", "경기도 성남시 분당구 판교역로 235", false),
+            ),
+        ] {
+            DeliveryService::create(
+                &manager, &addresses, &cipher, &lookup, &geocoder, user_id, dto,
+            )
+            .await
+            .unwrap();
+        }
+
+        let profile = ProfileQueryService::load(&users, &addresses, &cipher, &owner.uuid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.user.id, owner.id);
+        assert!(profile.user.verification.is_some());
+        assert_eq!(profile.user.socials.len(), 1);
+        let listed: Vec<_> = profile
+            .addresses
+            .iter()
+            .map(|a| (a.address.as_str(), a.is_default))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("서울특별시 중구 세종대로 110", true),
+                ("서울특별시 강남구 테헤란로 152", false),
+            ]
+        );
+        assert_eq!(
+            profile.addresses[0].entrance_password.as_deref(),
+            Some("#1234*")
+        );
+
+        assert!(
+            ProfileQueryService::load(&users, &addresses, &cipher, "missing")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
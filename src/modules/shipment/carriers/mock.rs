@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{
+    CarrierAdapter, CarrierError, CarrierResult, TrackingEvent, TrackingUpdate, normalize_status,
+    verify_webhook_signature,
+};
+use crate::modules::shipment::entities::shipment::Carrier;
+
+/// Stands in for a carrier's tracking API, for tests and until the carrier
+/// contracts are in place. Scans are recorded with `record_scan` and come
+/// back from `track` in the carrier's own labels, normalized like the real
+/// thing. With a webhook secret the carrier counts as pushing updates, in
+/// the JSON shape `{"tracking_number", "scans": [{"status", "location",
+/// "occurred_at"}]}`.
+pub struct MockCarrierAdapter {
+    carrier: Carrier,
+    webhook_secret: Option<String>,
+    scans: Mutex<HashMap<String, Vec<MockScan>>>,
+}
+
+#[derive(Clone, Deserialize)]
+struct MockScan {
+    status: String,
+    location: Option<String>,
+    occurred_at: chrono::NaiveDateTime,
+}
+
+#[derive(Deserialize)]
+struct MockPush {
+    tracking_number: String,
+    scans: Vec<MockScan>,
+}
+
+impl MockCarrierAdapter {
+    pub fn new(carrier: Carrier, webhook_secret: Option<String>) -> Self {
+        Self {
+            carrier,
+            webhook_secret,
+            scans: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn record_scan(
+        &self,
+        tracking_number: &str,
+        label: &str,
+        location: Option<&str>,
+        occurred_at: chrono::NaiveDateTime,
+    ) {
+        self.scans
+            .lock()
+            .unwrap()
+            .entry(tracking_number.to_string())
+            .or_default()
+            .push(MockScan {
+                status: label.to_string(),
+                location: location.map(str::to_string),
+                occurred_at,
+            });
+    }
+
+    fn normalize(&self, scan: MockScan) -> TrackingEvent {
+        TrackingEvent {
+            status: normalize_status(self.carrier, &scan.status),
+            carrier_status: scan.status,
+            location: scan.location,
+            occurred_at: scan.occurred_at,
+        }
+    }
+}
+
+#[async_trait]
+impl CarrierAdapter for MockCarrierAdapter {
+    fn carrier(&self) -> Carrier {
+        self.carrier
+    }
+
+    fn pushes_updates(&self) -> bool {
+        self.webhook_secret.is_some()
+    }
+
+    async fn track(&self, tracking_number: &str) -> CarrierResult<Vec<TrackingEvent>> {
+        let mut scans = self
+            .scans
+            .lock()
+            .unwrap()
+            .get(tracking_number)
+            .cloned()
+            .unwrap_or_default();
+        scans.sort_by_key(|s| s.occurred_at);
+        Ok(scans.into_iter().map(|s| self.normalize(s)).collect())
+    }
+
+    fn receive_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: chrono::NaiveDateTime,
+    ) -> CarrierResult<Vec<TrackingUpdate>> {
+        let secret = self
+            .webhook_secret
+            .as_deref()
+            .ok_or(CarrierError::WebhooksDisabled(self.carrier))?;
+        verify_webhook_signature(secret, headers, body, now)?;
+        let push: MockPush = serde_json::from_slice(body)
+            .map_err(|e| CarrierError::MalformedPayload(e.to_string()))?;
+        Ok(vec![TrackingUpdate {
+            tracking_number: push.tracking_number,
+            events: push.scans.into_iter().map(|s| self.normalize(s)).collect(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::shipment::carriers::{SIGNATURE_HEADER, TIMESTAMP_HEADER, sign_webhook};
+    use crate::modules::shipment::entities::shipment::ShipmentStatus;
+
+    fn at(hour: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 27)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_track_returns_normalized_timeline() {
+        let lotte = MockCarrierAdapter::new(Carrier::Lotte, None);
+        lotte.record_scan("123456789012", "배송출발", Some("강남"), at(9));
+        lotte.record_scan("123456789012", "상품접수", None, at(7));
+        assert!(!lotte.pushes_updates());
+
+        let events = lotte.track("123456789012").await.unwrap();
+        let statuses: Vec<_> = events.iter().map(|e| e.status).collect();
+        assert_eq!(
+            statuses,
+            vec![ShipmentStatus::InfoReceived, ShipmentStatus::OutForDelivery]
+        );
+        assert_eq!(events[1].carrier_status, "배송출발");
+        assert!(lotte.track("000000000000").await.unwrap().is_empty());
+
+        assert!(matches!(
+            lotte.receive_webhook(&HeaderMap::new(), b"{}", at(9)),
+            Err(CarrierError::WebhooksDisabled(Carrier::Lotte))
+        ));
+    }
+
+    #[test]
+    fn test_receive_webhook_verifies_before_parsing() {
+        let cj = MockCarrierAdapter::new(Carrier::CjLogistics, Some("cj-secret".to_string()));
+        let now = at(10);
+        let ts = now.and_utc().timestamp();
+        let body = serde_json::json!({
+            "tracking_number": "123456789012",
+            "scans": [{ "status": "배달완료", "location": "역삼", "occurred_at": "2024-05-27T09:30:00" }]
+        })
+        .to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, ts.to_string().parse().unwrap());
+        headers.insert(
+            SIGNATURE_HEADER,
+            sign_webhook("cj-secret", ts, body.as_bytes())
+                .parse()
+                .unwrap(),
+        );
+
+        let updates = cj.receive_webhook(&headers, body.as_bytes(), now).unwrap();
+        assert_eq!(updates[0].tracking_number, "123456789012");
+        assert_eq!(updates[0].events[0].status, ShipmentStatus::Delivered);
+
+        headers.insert(
+            SIGNATURE_HEADER,
+            sign_webhook("cj-secret", ts, b"not json").parse().unwrap(),
+        );
+        assert!(matches!(
+            cj.receive_webhook(&headers, body.as_bytes(), now),
+            Err(CarrierError::InvalidSignature)
+        ));
+        assert!(matches!(
+            cj.receive_webhook(&headers, b"not json", now),
+            Err(CarrierError::MalformedPayload(_))
+        ));
+    }
+}
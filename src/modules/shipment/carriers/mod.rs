@@ -0,0 +1,293 @@
+//! Courier integrations. Each carrier sits behind `CarrierAdapter`, which
+//! maps its status vocabulary onto `ShipmentStatus`. Carriers that push
+//! updates call our webhook; the rest are polled by `TrackingPoller`.
+
+pub mod mock;
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use sea_orm::ActiveEnum;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+use super::entities::shipment::{Carrier, ShipmentStatus};
+
+pub use mock::MockCarrierAdapter;
+
+pub const SIGNATURE_HEADER: &str = "x-carrier-signature";
+pub const TIMESTAMP_HEADER: &str = "x-carrier-timestamp";
+/// How far a webhook timestamp may be from our clock, against replays.
+pub const WEBHOOK_TOLERANCE_SECS: i64 = 5 * 60;
+
+/// Failures on either side of a carrier integration: webhooks they send us
+/// and tracking queries we send them. Failed queries are counted by the
+/// poller and tried again on its next run.
+#[derive(Error, Debug)]
+pub enum CarrierError {
+    /// Missing, stale or wrong webhook signature.
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    /// The carrier is not set up to push updates to us.
+    #[error("Webhooks are not enabled for {0:?}")]
+    WebhooksDisabled(Carrier),
+
+    #[error("Malformed payload: {0}")]
+    MalformedPayload(String),
+
+    /// A tracking query was refused, e.g. an unknown 운송장 번호 or expired
+    /// API credentials.
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+
+    /// The tracking API did not answer in time or returned a server error.
+    #[error("Upstream unavailable: {0}")]
+    Unavailable(String),
+}
+
+pub type CarrierResult<T> = Result<T, CarrierError>;
+
+/// One scan, already normalized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackingEvent {
+    pub status: ShipmentStatus,
+    /// The carrier's own label, kept for support.
+    pub carrier_status: String,
+    pub location: Option<String>,
+    pub occurred_at: chrono::NaiveDateTime,
+}
+
+/// New scans for one parcel, as pushed by a carrier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackingUpdate {
+    pub tracking_number: String,
+    pub events: Vec<TrackingEvent>,
+}
+
+#[async_trait]
+pub trait CarrierAdapter: Send + Sync {
+    fn carrier(&self) -> Carrier;
+
+    /// Whether this carrier pushes updates to the webhook. Parcels with
+    /// carriers that do not are polled.
+    fn pushes_updates(&self) -> bool;
+
+    /// Everything the carrier knows about a parcel, oldest first. Empty when
+    /// the number is not known to the carrier yet.
+    async fn track(&self, tracking_number: &str) -> CarrierResult<Vec<TrackingEvent>>;
+
+    /// Verifies a webhook call and parses its body. Nothing in the body may
+    /// be trusted before this returns `Ok`.
+    fn receive_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: chrono::NaiveDateTime,
+    ) -> CarrierResult<Vec<TrackingUpdate>>;
+}
+
+/// The configured adapters, one per carrier.
+#[derive(Clone, Default)]
+pub struct CarrierRegistry {
+    adapters: HashMap<Carrier, Arc<dyn CarrierAdapter>>,
+}
+
+impl CarrierRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<A: CarrierAdapter + 'static>(mut self, adapter: A) -> Self {
+        self.adapters.insert(adapter.carrier(), Arc::new(adapter));
+        self
+    }
+
+    pub fn get(&self, carrier: Carrier) -> Option<Arc<dyn CarrierAdapter>> {
+        self.adapters.get(&carrier).cloned()
+    }
+
+    /// Carriers whose parcels have to be polled.
+    pub fn polled_carriers(&self) -> Vec<Carrier> {
+        self.adapters
+            .values()
+            .filter(|a| !a.pushes_updates())
+            .map(|a| a.carrier())
+            .collect()
+    }
+}
+
+/// Parses `CARRIER_WEBHOOK_SECRETS` (`CJ:secret,HANJIN:secret`).
+pub fn parse_webhook_secrets(value: &str) -> Result<HashMap<Carrier, String>, String> {
+    let mut secrets = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (code, secret) = entry
+            .split_once(':')
+            .ok_or("Expected entries of the form CARRIER:secret")?;
+        let carrier = Carrier::try_from_value(&code.trim().to_string())
+            .map_err(|_| format!("Unknown carrier {}", code.trim()))?;
+        if secret.trim().is_empty() {
+            return Err(format!("Empty webhook secret for {}", code.trim()));
+        }
+        secrets.insert(carrier, secret.trim().to_string());
+    }
+    Ok(secrets)
+}
+
+/// `sha256=<hex>` HMAC over `{timestamp}.{body}`, as carriers sign pushes.
+pub fn sign_webhook(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mac = webhook_mac(secret, timestamp, body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Checks `SIGNATURE_HEADER` against the body and that `TIMESTAMP_HEADER`
+/// (unix seconds) is within `WEBHOOK_TOLERANCE_SECS` of `now`.
+pub fn verify_webhook_signature(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    now: chrono::NaiveDateTime,
+) -> CarrierResult<()> {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .ok_or(CarrierError::InvalidSignature)
+    };
+    let timestamp: i64 = header(TIMESTAMP_HEADER)?
+        .parse()
+        .map_err(|_| CarrierError::InvalidSignature)?;
+    if (now.and_utc().timestamp() - timestamp).abs() > WEBHOOK_TOLERANCE_SECS {
+        return Err(CarrierError::InvalidSignature);
+    }
+    let signature = header(SIGNATURE_HEADER)?
+        .strip_prefix("sha256=")
+        .and_then(|s| hex::decode(s).ok())
+        .ok_or(CarrierError::InvalidSignature)?;
+    webhook_mac(secret, timestamp, body)
+        .verify_slice(&signature)
+        .map_err(|_| CarrierError::InvalidSignature)
+}
+
+fn webhook_mac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// Maps a carrier's status label onto `ShipmentStatus`. Labels we do not
+/// know yet count as in transit, which is what new hub scans usually are.
+pub fn normalize_status(carrier: Carrier, label: &str) -> ShipmentStatus {
+    let label: String = label.split_whitespace().collect();
+    let status = match (carrier, label.as_str()) {
+        (Carrier::CjLogistics, "집화예정" | "접수")
+        | (Carrier::Hanjin, "접수" | "예약접수")
+        | (Carrier::Lotte, "상품접수" | "접수") => Some(ShipmentStatus::InfoReceived),
+        (Carrier::CjLogistics, "집화처리" | "상품인수")
+        | (Carrier::Hanjin, "집하")
+        | (Carrier::Lotte, "상품집하" | "집하") => Some(ShipmentStatus::PickedUp),
+        (Carrier::CjLogistics, "간선상차" | "간선하차" | "SM입고")
+        | (Carrier::Hanjin, "입고" | "출고" | "이동중" | "배송입고")
+        | (Carrier::Lotte, "상품이동중" | "배송지도착") => {
+            Some(ShipmentStatus::InTransit)
+        }
+        (Carrier::CjLogistics, "배송출발" | "배달출발")
+        | (Carrier::Hanjin, "배송출발")
+        | (Carrier::Lotte, "배송출발") => Some(ShipmentStatus::OutForDelivery),
+        (Carrier::CjLogistics, "배달완료" | "배송완료")
+        | (Carrier::Hanjin, "배송완료")
+        | (Carrier::Lotte, "배달완료" | "배송완료") => Some(ShipmentStatus::Delivered),
+        (Carrier::CjLogistics, "미배달" | "반품" | "반송")
+        | (Carrier::Hanjin, "배송불가" | "반송")
+        | (Carrier::Lotte, "미배달" | "반송") => Some(ShipmentStatus::Exception),
+        _ => None,
+    };
+    status.unwrap_or_else(|| {
+        tracing::warn!("Unknown {:?} status label {:?}", carrier, label);
+        ShipmentStatus::InTransit
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(timestamp: i64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_webhook_signature() {
+        let now = chrono::Utc::now().naive_utc();
+        let ts = now.and_utc().timestamp();
+        let body = br#"{"tracking_number":"123456789012"}"#;
+        let signature = sign_webhook("secret", ts, body);
+
+        assert!(verify_webhook_signature("secret", &headers(ts, &signature), body, now).is_ok());
+        // Wrong secret, altered body, replayed later, or unsigned.
+        for (secret, headers, body) in [
+            ("other", headers(ts, &signature), &body[..]),
+            ("secret", headers(ts, &signature), &b"{}"[..]),
+            (
+                "secret",
+                headers(ts - 600, &sign_webhook("secret", ts - 600, body)),
+                &body[..],
+            ),
+            ("secret", HeaderMap::new(), &body[..]),
+        ] {
+            assert!(matches!(
+                verify_webhook_signature(secret, &headers, body, now),
+                Err(CarrierError::InvalidSignature)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_normalize_status_per_carrier() {
+        assert_eq!(
+            normalize_status(Carrier::CjLogistics, "간선 상차"),
+            ShipmentStatus::InTransit
+        );
+        assert_eq!(
+            normalize_status(Carrier::Hanjin, "집하"),
+            ShipmentStatus::PickedUp
+        );
+        assert_eq!(
+            normalize_status(Carrier::Lotte, "배달 완료"),
+            ShipmentStatus::Delivered
+        );
+        assert_eq!(
+            normalize_status(Carrier::CjLogistics, "미배달"),
+            ShipmentStatus::Exception
+        );
+        assert_eq!(
+            normalize_status(Carrier::Hanjin, "새로운 상태"),
+            ShipmentStatus::InTransit
+        );
+    }
+
+    #[test]
+    fn test_parse_webhook_secrets() {
+        let secrets = parse_webhook_secrets("CJ:abc, HANJIN:def").unwrap();
+        assert_eq!(
+            secrets.get(&Carrier::CjLogistics).map(String::as_str),
+            Some("abc")
+        );
+        assert_eq!(
+            secrets.get(&Carrier::Hanjin).map(String::as_str),
+            Some("def")
+        );
+        assert!(!secrets.contains_key(&Carrier::Lotte));
+        assert!(parse_webhook_secrets("").unwrap().is_empty());
+        assert!(parse_webhook_secrets("DHL:abc").is_err());
+        assert!(parse_webhook_secrets("CJ").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use super::entities::shipment::Carrier;
+
+/// A parcel handed to a carrier, as registered by fulfillment staff.
+pub struct RegisterShipmentDto {
+    /// Destination address; its owner becomes the recipient.
+    pub delivery_id: i32,
+    /// Fulfillment center the parcel left from.
+    pub place_id: Option<i32>,
+    pub carrier: Carrier,
+    /// As printed on the label; hyphens and spaces are dropped.
+    pub tracking_number: String,
+}
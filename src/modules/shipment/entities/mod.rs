@@ -0,0 +1,2 @@
+pub mod shipment;
+pub mod shipment_event;
@@ -0,0 +1,107 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Carrier {
+    /// CJ대한통운
+    #[sea_orm(string_value = "CJ")]
+    #[serde(rename = "CJ")]
+    CjLogistics,
+    /// 한진택배
+    #[sea_orm(string_value = "HANJIN")]
+    #[serde(rename = "HANJIN")]
+    Hanjin,
+    /// 롯데택배
+    #[sea_orm(string_value = "LOTTE")]
+    #[serde(rename = "LOTTE")]
+    Lotte,
+}
+
+/// Carrier-neutral progress of a parcel. Each adapter maps its carrier's own
+/// status vocabulary onto these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ShipmentStatus {
+    /// Registered with the carrier, not yet handed over.
+    #[sea_orm(string_value = "INFO_RECEIVED")]
+    #[serde(rename = "INFO_RECEIVED")]
+    InfoReceived,
+    /// 집화: the carrier has the parcel.
+    #[sea_orm(string_value = "PICKED_UP")]
+    #[serde(rename = "PICKED_UP")]
+    PickedUp,
+    /// Moving between hubs.
+    #[sea_orm(string_value = "IN_TRANSIT")]
+    #[serde(rename = "IN_TRANSIT")]
+    InTransit,
+    /// 배송출발: on the last-mile vehicle.
+    #[sea_orm(string_value = "OUT_FOR_DELIVERY")]
+    #[serde(rename = "OUT_FOR_DELIVERY")]
+    OutForDelivery,
+    #[sea_orm(string_value = "DELIVERED")]
+    #[serde(rename = "DELIVERED")]
+    Delivered,
+    /// Failed attempt, return to sender or anything else needing attention.
+    #[sea_orm(string_value = "EXCEPTION")]
+    #[serde(rename = "EXCEPTION")]
+    Exception,
+}
+
+impl ShipmentStatus {
+    /// Delivered parcels get no further updates and are no longer polled.
+    pub fn is_final(&self) -> bool {
+        *self == Self::Delivered
+    }
+}
+
+/// A parcel handed to a carrier. The timeline lives in `shipment_event`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "shipments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// The recipient, who may track it.
+    pub user_id: i32,
+    /// Destination address; `None` once the user deletes it.
+    pub delivery_id: Option<i32>,
+    /// Fulfillment center the parcel left from.
+    pub place_id: Option<i32>,
+    pub carrier: Carrier,
+    /// 운송장 번호, digits only. Unique per carrier.
+    pub tracking_number: String,
+    /// Status of the latest event, `InfoReceived` until the first one.
+    pub status: ShipmentStatus,
+    pub last_event_at: Option<DateTime>,
+    pub delivered_at: Option<DateTime>,
+    /// Last time the tracking poller asked the carrier.
+    pub last_polled_at: Option<DateTime>,
+    /// Staff member who registered the parcel.
+    pub registered_by: Option<i32>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::UserId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(has_many = "super::shipment_event::Entity")]
+    Events,
+}
+
+impl Related<super::shipment_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Events.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
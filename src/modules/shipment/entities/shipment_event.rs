@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::shipment::ShipmentStatus;
+
+/// One scan in a shipment's timeline. The same scan may arrive by webhook
+/// and by polling; `(shipment_id, occurred_at, carrier_status)` is unique so
+/// it is stored once.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "shipment_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub shipment_id: i32,
+    pub status: ShipmentStatus,
+    /// The carrier's own status label, e.g. `간선상차`.
+    pub carrier_status: String,
+    /// Branch or hub where the scan happened.
+    pub location: Option<String>,
+    /// Carrier-local time (KST) of the scan.
+    pub occurred_at: DateTime,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::shipment::Entity",
+        from = "Column::ShipmentId",
+        to = "super::shipment::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Shipment,
+}
+
+impl Related<super::shipment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shipment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,230 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+
+use super::dtos::RegisterShipmentDto;
+use super::entities::shipment::{self, Carrier, ShipmentStatus};
+use super::entities::shipment_event;
+use super::repository::ShipmentRepository;
+use super::service::{ShipmentService, ShipmentView};
+use crate::modules::delivery::handlers::AddressResponse;
+use crate::modules::delivery::repository::DeliveryRepository;
+use crate::modules::users::entities::user;
+use crate::modules::users::repository::UserRepository;
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct ShipmentResponse {
+    pub id: i32,
+    pub carrier: Carrier,
+    pub tracking_number: String,
+    pub status: ShipmentStatus,
+    pub last_event_at: Option<chrono::NaiveDateTime>,
+    pub delivered_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl From<shipment::Model> for ShipmentResponse {
+    fn from(s: shipment::Model) -> Self {
+        Self {
+            id: s.id,
+            carrier: s.carrier,
+            tracking_number: s.tracking_number,
+            status: s.status,
+            last_event_at: s.last_event_at,
+            delivered_at: s.delivered_at,
+            created_at: s.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ShipmentEventResponse {
+    pub status: ShipmentStatus,
+    pub carrier_status: String,
+    pub location: Option<String>,
+    pub occurred_at: chrono::NaiveDateTime,
+}
+
+impl From<shipment_event::Model> for ShipmentEventResponse {
+    fn from(e: shipment_event::Model) -> Self {
+        Self {
+            status: e.status,
+            carrier_status: e.carrier_status,
+            location: e.location,
+            occurred_at: e.occurred_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ShipmentDetailResponse {
+    #[serde(flatten)]
+    pub shipment: ShipmentResponse,
+    /// Oldest first.
+    pub events: Vec<ShipmentEventResponse>,
+}
+
+impl From<ShipmentView> for ShipmentDetailResponse {
+    fn from(view: ShipmentView) -> Self {
+        Self {
+            shipment: view.shipment.into(),
+            events: view.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+async fn find_requester(user_repo: &dyn UserRepository, uuid: &str) -> AppResult<user::Model> {
+    user_repo
+        .find_by_uuid(uuid)
+        .await?
+        .ok_or(AppError::NotFound)
+}
+
+pub async fn list_my_shipments(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<Vec<ShipmentResponse>>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ShipmentRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ShipmentRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let shipments = ShipmentService::list(repo.as_ref(), user.id).await?;
+    Ok(Json(shipments.into_iter().map(Into::into).collect()))
+}
+
+pub async fn get_my_shipment(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+) -> AppResult<Json<ShipmentDetailResponse>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ShipmentRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ShipmentRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let view = ShipmentService::find_for_user(repo.as_ref(), user.id, id).await?;
+    Ok(Json(view.into()))
+}
+
+/// The decrypted destination, for the recipient or staff assigned to the
+/// delivery.
+pub async fn get_shipment_address(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AddressResponse>> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ShipmentRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ShipmentRepository not registered".to_string(),
+        ))?;
+    let delivery_repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let reader = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let address = ShipmentService::reveal_address(
+        repo.as_ref(),
+        delivery_repo.as_ref(),
+        &state.field_cipher,
+        &reader,
+        id,
+    )
+    .await?;
+    Ok(Json(address.into()))
+}
+
+#[derive(Deserialize)]
+pub struct RegisterShipmentRequest {
+    pub delivery_id: i32,
+    pub place_id: Option<i32>,
+    pub carrier: Carrier,
+    pub tracking_number: String,
+}
+
+/// For fulfillment staff once a parcel has left the center.
+pub async fn register_shipment(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<RegisterShipmentRequest>,
+) -> AppResult<(StatusCode, Json<ShipmentResponse>)> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ShipmentRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ShipmentRepository not registered".to_string(),
+        ))?;
+    let delivery_repo = state
+        .repo_manager
+        .get::<Arc<dyn DeliveryRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "DeliveryRepository not registered".to_string(),
+        ))?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let staff = find_requester(user_repo.as_ref(), &claims.sub).await?;
+
+    let created = ShipmentService::register(
+        repo.as_ref(),
+        delivery_repo.as_ref(),
+        &state.carriers,
+        &staff,
+        RegisterShipmentDto {
+            delivery_id: body.delivery_id,
+            place_id: body.place_id,
+            carrier: body.carrier,
+            tracking_number: body.tracking_number,
+        },
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// Called by carriers, not users: authenticated by the body signature.
+pub async fn receive_carrier_webhook(
+    State(state): State<AppState>,
+    Path(carrier): Path<Carrier>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<StatusCode> {
+    let repo = state
+        .repo_manager
+        .get::<Arc<dyn ShipmentRepository>>()
+        .ok_or(AppError::InternalServerError(
+            "ShipmentRepository not registered".to_string(),
+        ))?;
+
+    ShipmentService::receive_webhook(repo.as_ref(), &state.carriers, carrier, &headers, &body)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
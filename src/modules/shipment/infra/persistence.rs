@@ -0,0 +1,332 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use std::sync::{Arc, Mutex};
+
+use crate::impl_sea_orm_repo;
+use crate::modules::shipment::entities::shipment::{self, Carrier, ShipmentStatus};
+use crate::modules::shipment::entities::shipment_event;
+use crate::modules::shipment::repository::ShipmentRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
+use crate::shared::repository::UnitOfWork;
+
+// =========================================================================
+// Postgres Implementation
+// =========================================================================
+
+pub type PostgresShipmentRepository = SeaOrmRepository<shipment::Entity>;
+
+impl_sea_orm_repo!(PostgresShipmentRepository, ShipmentRepository, {
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<shipment::Model>> {
+        let query = shipment::Entity::find_by_id(id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_by_tracking_number(
+        &self,
+        carrier: Carrier,
+        tracking_number: &str,
+    ) -> AppResult<Option<shipment::Model>> {
+        let query = shipment::Entity::find()
+            .filter(shipment::Column::Carrier.eq(carrier))
+            .filter(shipment::Column::TrackingNumber.eq(tracking_number));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<shipment::Model>> {
+        let query = shipment::Entity::find()
+            .filter(shipment::Column::UserId.eq(user_id))
+            .order_by_desc(shipment::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => shipment.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                shipment.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn update(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => shipment.update(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                shipment.update(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_events(&self, shipment_id: i32) -> AppResult<Vec<shipment_event::Model>> {
+        let query = shipment_event::Entity::find()
+            .filter(shipment_event::Column::ShipmentId.eq(shipment_id))
+            .order_by_asc(shipment_event::Column::OccurredAt)
+            .order_by_asc(shipment_event::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn add_events(&self, events: Vec<shipment_event::ActiveModel>) -> AppResult<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+        let insert = shipment_event::Entity::insert_many(events).on_conflict(
+            OnConflict::columns([
+                shipment_event::Column::ShipmentId,
+                shipment_event::Column::OccurredAt,
+                shipment_event::Column::CarrierStatus,
+            ])
+            .do_nothing()
+            .to_owned(),
+        );
+        match &self.conn {
+            DbOrTxn::Conn(c) => insert.exec_without_returning(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                insert.exec_without_returning(txn).await
+            }
+        }
+        .map_err(AppError::DbError)
+    }
+
+    async fn find_pollable(
+        &self,
+        carriers: Vec<Carrier>,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<shipment::Model>> {
+        let query = shipment::Entity::find()
+            .filter(shipment::Column::Id.gt(after_id))
+            .filter(shipment::Column::Carrier.is_in(carriers))
+            .filter(shipment::Column::Status.ne(ShipmentStatus::Delivered))
+            .order_by_asc(shipment::Column::Id)
+            .limit(limit);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().ok_or(AppError::InternalServerError(
+                    "Transaction unavailable".to_string(),
+                ))?;
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+});
+
+// =========================================================================
+// InMemory Implementation
+// =========================================================================
+
+#[derive(Clone, Default)]
+pub struct InMemoryShipmentRepository {
+    shipments: Arc<Mutex<Vec<shipment::Model>>>,
+    events: Arc<Mutex<Vec<shipment_event::Model>>>,
+    counter: Arc<Mutex<i32>>,
+}
+
+impl InMemoryShipmentRepository {
+    fn next_id(&self) -> i32 {
+        let mut counter = self.counter.lock().unwrap();
+        *counter += 1;
+        *counter
+    }
+}
+
+#[async_trait]
+impl ShipmentRepository for InMemoryShipmentRepository {
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<shipment::Model>> {
+        let shipments = self.shipments.lock().unwrap();
+        Ok(shipments.iter().find(|s| s.id == id).cloned())
+    }
+
+    async fn find_by_tracking_number(
+        &self,
+        carrier: Carrier,
+        tracking_number: &str,
+    ) -> AppResult<Option<shipment::Model>> {
+        let shipments = self.shipments.lock().unwrap();
+        Ok(shipments
+            .iter()
+            .find(|s| s.carrier == carrier && s.tracking_number == tracking_number)
+            .cloned())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<shipment::Model>> {
+        let shipments = self.shipments.lock().unwrap();
+        let mut owned: Vec<_> = shipments
+            .iter()
+            .filter(|s| s.user_id == user_id)
+            .cloned()
+            .collect();
+        owned.sort_by_key(|s| std::cmp::Reverse(s.id));
+        Ok(owned)
+    }
+
+    async fn create(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model> {
+        let carrier = shipment.carrier.clone().unwrap();
+        let tracking_number = shipment.tracking_number.clone().unwrap();
+        // Mirrors the unique index on (carrier, tracking_number).
+        if self
+            .find_by_tracking_number(carrier, &tracking_number)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(
+                "Tracking number already registered".to_string(),
+            ));
+        }
+        let model = shipment::Model {
+            id: self.next_id(),
+            user_id: shipment.user_id.unwrap(),
+            delivery_id: shipment.delivery_id.unwrap(),
+            place_id: shipment.place_id.unwrap(),
+            carrier,
+            tracking_number,
+            status: shipment.status.unwrap(),
+            last_event_at: shipment.last_event_at.unwrap(),
+            delivered_at: shipment.delivered_at.unwrap(),
+            last_polled_at: shipment.last_polled_at.unwrap(),
+            registered_by: shipment.registered_by.unwrap(),
+            created_at: shipment.created_at.unwrap(),
+            updated_at: shipment.updated_at.unwrap(),
+        };
+        self.shipments.lock().unwrap().push(model.clone());
+        Ok(model)
+    }
+
+    async fn update(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model> {
+        let mut shipments = self.shipments.lock().unwrap();
+        let id = shipment.id.unwrap();
+        let existing = shipments
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or(AppError::NotFound)?;
+        if let Set(v) = shipment.status {
+            existing.status = v;
+        }
+        if let Set(v) = shipment.last_event_at {
+            existing.last_event_at = v;
+        }
+        if let Set(v) = shipment.delivered_at {
+            existing.delivered_at = v;
+        }
+        if let Set(v) = shipment.last_polled_at {
+            existing.last_polled_at = v;
+        }
+        if let Set(v) = shipment.updated_at {
+            existing.updated_at = v;
+        }
+        Ok(existing.clone())
+    }
+
+    async fn find_events(&self, shipment_id: i32) -> AppResult<Vec<shipment_event::Model>> {
+        let events = self.events.lock().unwrap();
+        let mut timeline: Vec<_> = events
+            .iter()
+            .filter(|e| e.shipment_id == shipment_id)
+            .cloned()
+            .collect();
+        timeline.sort_by_key(|e| (e.occurred_at, e.id));
+        Ok(timeline)
+    }
+
+    async fn add_events(&self, events: Vec<shipment_event::ActiveModel>) -> AppResult<u64> {
+        let mut added = 0;
+        for event in events {
+            let model = shipment_event::Model {
+                id: 0,
+                shipment_id: event.shipment_id.unwrap(),
+                status: event.status.unwrap(),
+                carrier_status: event.carrier_status.unwrap(),
+                location: event.location.unwrap(),
+                occurred_at: event.occurred_at.unwrap(),
+                created_at: event.created_at.unwrap(),
+            };
+            let mut stored = self.events.lock().unwrap();
+            // Mirrors the unique index on (shipment_id, occurred_at, carrier_status).
+            if stored.iter().any(|e| {
+                e.shipment_id == model.shipment_id
+                    && e.occurred_at == model.occurred_at
+                    && e.carrier_status == model.carrier_status
+            }) {
+                continue;
+            }
+            stored.push(shipment_event::Model {
+                id: self.next_id(),
+                ..model
+            });
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    async fn find_pollable(
+        &self,
+        carriers: Vec<Carrier>,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<shipment::Model>> {
+        let shipments = self.shipments.lock().unwrap();
+        let mut pending: Vec<_> = shipments
+            .iter()
+            .filter(|s| s.id > after_id && carriers.contains(&s.carrier) && !s.status.is_final())
+            .cloned()
+            .collect();
+        pending.sort_by_key(|s| s.id);
+        pending.truncate(limit as usize);
+        Ok(pending)
+    }
+
+    fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn ShipmentRepository>> {
+        Some(Box::new(self.clone()))
+    }
+}
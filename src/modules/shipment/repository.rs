@@ -0,0 +1,27 @@
+use super::entities::shipment::{self, Carrier};
+use super::entities::shipment_event;
+use crate::shared::error::AppResult;
+crate::define_repo!(ShipmentRepository, {
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<shipment::Model>>;
+    async fn find_by_tracking_number(
+        &self,
+        carrier: Carrier,
+        tracking_number: &str,
+    ) -> AppResult<Option<shipment::Model>>;
+    /// Newest first.
+    async fn find_by_user(&self, user_id: i32) -> AppResult<Vec<shipment::Model>>;
+    async fn create(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model>;
+    async fn update(&self, shipment: shipment::ActiveModel) -> AppResult<shipment::Model>;
+    /// The timeline, oldest first.
+    async fn find_events(&self, shipment_id: i32) -> AppResult<Vec<shipment_event::Model>>;
+    /// Stores events, skipping ones already stored for the shipment.
+    /// Returns how many were new.
+    async fn add_events(&self, events: Vec<shipment_event::ActiveModel>) -> AppResult<u64>;
+    /// Undelivered shipments with one of `carriers`, by id.
+    async fn find_pollable(
+        &self,
+        carriers: Vec<Carrier>,
+        after_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<shipment::Model>>;
+});
@@ -0,0 +1,27 @@
+use super::handlers;
+use crate::shared::{middleware::require_email_verified, state::AppState};
+use axum::{
+    Router, middleware,
+    routing::{get, post},
+};
+
+/// Mounted at `/shipments`. The webhook sits outside the login check;
+/// carriers authenticate by signature.
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::list_my_shipments).post(handlers::register_shipment),
+        )
+        .route("/:id", get(handlers::get_my_shipment))
+        .route("/:id/address", get(handlers::get_shipment_address))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_email_verified,
+        ))
+        .route(
+            "/webhooks/:carrier",
+            post(handlers::receive_carrier_webhook),
+        )
+        .with_state(state)
+}
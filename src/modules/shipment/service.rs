@@ -0,0 +1,661 @@
+use axum::http::HeaderMap;
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::SqlErr;
+
+use super::carriers::{CarrierRegistry, TrackingEvent};
+use super::dtos::RegisterShipmentDto;
+use super::entities::shipment::{self, Carrier, ShipmentStatus};
+use super::entities::shipment_event;
+use super::repository::ShipmentRepository;
+use crate::modules::delivery::entities::delivery_data;
+use crate::modules::delivery::repository::DeliveryRepository;
+use crate::modules::delivery::service::DeliveryService;
+use crate::modules::users::entities::{
+    enums::{AccountStatus, UserRole},
+    user,
+};
+use crate::shared::crypto::FieldCipher;
+use crate::shared::error::{AppError, AppResult};
+
+pub const TRACKING_NUMBER_MIN_DIGITS: usize = 10;
+pub const TRACKING_NUMBER_MAX_DIGITS: usize = 14;
+
+/// A shipment with its timeline, oldest event first.
+pub struct ShipmentView {
+    pub shipment: shipment::Model,
+    pub events: Vec<shipment_event::Model>,
+}
+
+pub struct ShipmentService;
+
+impl ShipmentService {
+    /// Starts tracking a parcel. Only admins and active fulfillment staff
+    /// assigned to the delivery register shipments.
+    pub async fn register(
+        repo: &dyn ShipmentRepository,
+        delivery_repo: &dyn DeliveryRepository,
+        carriers: &CarrierRegistry,
+        staff: &user::Model,
+        dto: RegisterShipmentDto,
+    ) -> AppResult<shipment::Model> {
+        if !matches!(staff.role, UserRole::Fulfillment | UserRole::Admin)
+            || staff.account_status != AccountStatus::Active
+        {
+            return Err(AppError::Forbidden(
+                "Only fulfillment staff can register shipments".to_string(),
+            ));
+        }
+        if carriers.get(dto.carrier).is_none() {
+            return Err(AppError::BadRequest(format!(
+                "Carrier {:?} is not supported",
+                dto.carrier
+            )));
+        }
+        let tracking_number = Self::clean_tracking_number(&dto.tracking_number)?;
+        let address = delivery_repo
+            .find_by_id(dto.delivery_id)
+            .await?
+            .ok_or(AppError::BadRequest("Unknown delivery address".to_string()))?;
+        if staff.role == UserRole::Fulfillment
+            && !DeliveryService::is_assigned_staff(delivery_repo, address.id, staff).await?
+        {
+            return Err(AppError::Forbidden(
+                "Not assigned to this delivery".to_string(),
+            ));
+        }
+        if repo
+            .find_by_tracking_number(dto.carrier, &tracking_number)
+            .await?
+            .is_some()
+        {
+            return Err(already_registered());
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let created = repo
+            .create(shipment::ActiveModel {
+                user_id: Set(address.user_id),
+                delivery_id: Set(Some(address.id)),
+                place_id: Set(dto.place_id),
+                carrier: Set(dto.carrier),
+                tracking_number: Set(tracking_number),
+                status: Set(ShipmentStatus::InfoReceived),
+                last_event_at: Set(None),
+                delivered_at: Set(None),
+                last_polled_at: Set(None),
+                registered_by: Set(Some(staff.id)),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| match e {
+                AppError::DbError(db)
+                    if matches!(db.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+                {
+                    already_registered()
+                }
+                e => e,
+            })?;
+        tracing::info!(
+            "Shipment {} ({:?} {}) registered by {}",
+            created.id,
+            created.carrier,
+            created.tracking_number,
+            staff.uuid
+        );
+        Ok(created)
+    }
+
+    /// Shipments addressed to the user, newest first.
+    pub async fn list(
+        repo: &dyn ShipmentRepository,
+        user_id: i32,
+    ) -> AppResult<Vec<shipment::Model>> {
+        repo.find_by_user(user_id).await
+    }
+
+    /// One of the user's shipments with its timeline. Other users'
+    /// shipments look like they do not exist.
+    pub async fn find_for_user(
+        repo: &dyn ShipmentRepository,
+        user_id: i32,
+        id: i32,
+    ) -> AppResult<ShipmentView> {
+        let shipment = repo
+            .find_by_id(id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
+        let events = repo.find_events(shipment.id).await?;
+        Ok(ShipmentView { shipment, events })
+    }
+
+    /// The decrypted destination of a shipment, for its recipient or staff
+    /// assigned to its delivery. Other staff are refused.
+    pub async fn reveal_address(
+        repo: &dyn ShipmentRepository,
+        delivery_repo: &dyn DeliveryRepository,
+        cipher: &FieldCipher,
+        reader: &user::Model,
+        id: i32,
+    ) -> AppResult<delivery_data::Model> {
+        let shipment = repo
+            .find_by_id(id)
+            .await?
+            .filter(|s| s.user_id == reader.id || reader.role == UserRole::Fulfillment)
+            .ok_or(AppError::NotFound)?;
+        let delivery_id = shipment.delivery_id.ok_or(AppError::NotFound)?;
+        DeliveryService::reveal(delivery_repo, cipher, delivery_id, reader).await
+    }
+
+    /// Stores events not seen before and moves the shipment to the status
+    /// of its latest event. Events may arrive late or twice.
+    pub async fn apply_events(
+        repo: &dyn ShipmentRepository,
+        shipment: shipment::Model,
+        events: Vec<TrackingEvent>,
+    ) -> AppResult<shipment::Model> {
+        let now = chrono::Utc::now().naive_utc();
+        let added = repo
+            .add_events(
+                events
+                    .into_iter()
+                    .map(|e| shipment_event::ActiveModel {
+                        shipment_id: Set(shipment.id),
+                        status: Set(e.status),
+                        carrier_status: Set(e.carrier_status),
+                        location: Set(e.location),
+                        occurred_at: Set(e.occurred_at),
+                        created_at: Set(now),
+                        ..Default::default()
+                    })
+                    .collect(),
+            )
+            .await?;
+        if added == 0 {
+            return Ok(shipment);
+        }
+
+        let timeline = repo.find_events(shipment.id).await?;
+        let Some(latest) = timeline.last() else {
+            return Ok(shipment);
+        };
+        let delivered_at = timeline
+            .iter()
+            .find(|e| e.status == ShipmentStatus::Delivered)
+            .map(|e| e.occurred_at);
+        repo.update(shipment::ActiveModel {
+            id: Unchanged(shipment.id),
+            status: Set(latest.status),
+            last_event_at: Set(Some(latest.occurred_at)),
+            delivered_at: Set(delivered_at),
+            updated_at: Set(now),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Handles a push from `carrier`. Updates for parcels we never
+    /// registered are skipped. Returns how many shipments were updated.
+    pub async fn receive_webhook(
+        repo: &dyn ShipmentRepository,
+        carriers: &CarrierRegistry,
+        carrier: Carrier,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> AppResult<u64> {
+        let adapter = carriers.get(carrier).ok_or(AppError::NotFound)?;
+        let updates = adapter.receive_webhook(headers, body, chrono::Utc::now().naive_utc())?;
+
+        let mut updated = 0;
+        for update in updates {
+            let Some(shipment) = repo
+                .find_by_tracking_number(carrier, &update.tracking_number)
+                .await?
+            else {
+                tracing::warn!(
+                    "{:?} pushed unknown tracking number {}",
+                    carrier,
+                    update.tracking_number
+                );
+                continue;
+            };
+            Self::apply_events(repo, shipment, update.events).await?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    /// Digits only, within the lengths Korean carriers issue.
+    fn clean_tracking_number(raw: &str) -> AppResult<String> {
+        let cleaned: String = raw
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect();
+        if !cleaned.chars().all(|c| c.is_ascii_digit())
+            || !(TRACKING_NUMBER_MIN_DIGITS..=TRACKING_NUMBER_MAX_DIGITS).contains(&cleaned.len())
+        {
+            return Err(AppError::BadRequest(format!(
+                "Tracking numbers are {}-{} digits",
+                TRACKING_NUMBER_MIN_DIGITS, TRACKING_NUMBER_MAX_DIGITS
+            )));
+        }
+        Ok(cleaned)
+    }
+}
+
+fn already_registered() -> AppError {
+    AppError::Conflict("Tracking number already registered".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::delivery::dtos::CreateAddressDto;
+    use crate::modules::delivery::infra::persistence::InMemoryDeliveryRepository;
+    use crate::modules::delivery::lookup::FixtureAddressLookup;
+    use crate::modules::shipment::carriers::{
+        MockCarrierAdapter, SIGNATURE_HEADER, TIMESTAMP_HEADER, sign_webhook,
+    };
+    use crate::modules::shipment::infra::persistence::InMemoryShipmentRepository;
+    use crate::modules::users::infra::fixtures::{detached_user, sign_up};
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::shared::config::Config;
+    use crate::shared::geocoding::FixtureGeocoder;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    async fn create_user(
+        repo: &InMemoryUserRepository,
+        provider_id: &str,
+        role: UserRole,
+    ) -> user::Model {
+        let created = sign_up(repo, provider_id).await;
+        user::Model {
+            role,
+            account_status: AccountStatus::Active,
+            ..created
+        }
+    }
+
+    /// An address for `user_id` at 테헤란로 152.
+    async fn create_address(delivery_repo: &InMemoryDeliveryRepository, user_id: i32) -> i32 {
+        DeliveryService::create(
+            &InMemoryRepositoryManager::new(),
+            delivery_repo,
+            &FieldCipher::from_config(&Config::for_test()),
+            &FixtureAddressLookup::default(),
+            &FixtureGeocoder::default(),
+            user_id,
+            CreateAddressDto {
+                label: None,
+                is_default: true,
+                recipient_name: "김기미".to_string(),
+                phone_number: "010-1234-5678".to_string(),
+                zip_code: "06236".to_string(),
+                address: "서울특별시 강남구 테헤란로 152".to_string(),
+                detail_address: None,
+                entrance_password: None,
+                shipping_memo: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    /// An admin assigns `staff` to the delivery.
+    async fn assign(
+        delivery_repo: &InMemoryDeliveryRepository,
+        delivery_id: i32,
+        staff: &user::Model,
+    ) {
+        DeliveryService::assign_staff(
+            delivery_repo,
+            &detached_user(999, UserRole::Admin),
+            delivery_id,
+            staff,
+        )
+        .await
+        .unwrap();
+    }
+
+    fn carriers() -> CarrierRegistry {
+        CarrierRegistry::new()
+            .register(MockCarrierAdapter::new(
+                Carrier::CjLogistics,
+                Some("cj-secret".to_string()),
+            ))
+            .register(MockCarrierAdapter::new(Carrier::Lotte, None))
+    }
+
+    fn registration(
+        delivery_id: i32,
+        carrier: Carrier,
+        tracking_number: &str,
+    ) -> RegisterShipmentDto {
+        RegisterShipmentDto {
+            delivery_id,
+            place_id: Some(1),
+            carrier,
+            tracking_number: tracking_number.to_string(),
+        }
+    }
+
+    fn at(hour: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 27)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn event(status: ShipmentStatus, label: &str, hour: u32) -> TrackingEvent {
+        TrackingEvent {
+            status,
+            carrier_status: label.to_string(),
+            location: None,
+            occurred_at: at(hour),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_requires_staff_and_valid_tracking_number() {
+        let users = InMemoryUserRepository::default();
+        let delivery_repo = InMemoryDeliveryRepository::default();
+        let repo = InMemoryShipmentRepository::default();
+        let carriers = carriers();
+        let customer = create_user(&users, "customer", UserRole::User).await;
+        let staff = create_user(&users, "staff", UserRole::Fulfillment).await;
+        let delivery_id = create_address(&delivery_repo, customer.id).await;
+
+        for unauthorized in [&customer, &staff] {
+            assert!(matches!(
+                ShipmentService::register(
+                    &repo,
+                    &delivery_repo,
+                    &carriers,
+                    unauthorized,
+                    registration(delivery_id, Carrier::CjLogistics, "1234-5678-9012"),
+                )
+                .await,
+                Err(AppError::Forbidden(_))
+            ));
+        }
+        assign(&delivery_repo, delivery_id, &staff).await;
+
+        let created = ShipmentService::register(
+            &repo,
+            &delivery_repo,
+            &carriers,
+            &staff,
+            registration(delivery_id, Carrier::CjLogistics, "1234-5678-9012"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.user_id, customer.id);
+        assert_eq!(created.tracking_number, "123456789012");
+        assert_eq!(created.status, ShipmentStatus::InfoReceived);
+        assert_eq!(created.registered_by, Some(staff.id));
+
+        for (carrier, tracking_number) in [
+            (Carrier::CjLogistics, "1234 5678 9012"),
+            (Carrier::CjLogistics, "12345"),
+            (Carrier::CjLogistics, "ABCD56789012"),
+            // No adapter registered.
+            (Carrier::Hanjin, "123456789012"),
+        ] {
+            assert!(
+                ShipmentService::register(
+                    &repo,
+                    &delivery_repo,
+                    &carriers,
+                    &staff,
+                    registration(delivery_id, carrier, tracking_number),
+                )
+                .await
+                .is_err()
+            );
+        }
+        // The same number with another carrier is a different parcel.
+        assert!(
+            ShipmentService::register(
+                &repo,
+                &delivery_repo,
+                &carriers,
+                &staff,
+                registration(delivery_id, Carrier::Lotte, "123456789012"),
+            )
+            .await
+            .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_address_is_revealed_to_recipient_and_assigned_staff() {
+        let users = InMemoryUserRepository::default();
+        let delivery_repo = InMemoryDeliveryRepository::default();
+        let repo = InMemoryShipmentRepository::default();
+        let cipher = FieldCipher::from_config(&Config::for_test());
+        let customer = create_user(&users, "customer", UserRole::User).await;
+        let staff = create_user(&users, "staff", UserRole::Fulfillment).await;
+        let other_staff = create_user(&users, "other", UserRole::Fulfillment).await;
+        let stranger = create_user(&users, "stranger", UserRole::User).await;
+        let delivery_id = create_address(&delivery_repo, customer.id).await;
+        assign(&delivery_repo, delivery_id, &staff).await;
+        let shipment = ShipmentService::register(
+            &repo,
+            &delivery_repo,
+            &carriers(),
+            &staff,
+            registration(delivery_id, Carrier::CjLogistics, "123456789012"),
+        )
+        .await
+        .unwrap();
+
+        // Registering a parcel does not make unassigned staff assigned.
+        assert!(matches!(
+            ShipmentService::register(
+                &repo,
+                &delivery_repo,
+                &carriers(),
+                &other_staff,
+                registration(delivery_id, Carrier::Lotte, "999999999999"),
+            )
+            .await,
+            Err(AppError::Forbidden(_))
+        ));
+
+        for reader in [&customer, &staff] {
+            let address = ShipmentService::reveal_address(
+                &repo,
+                &delivery_repo,
+                &cipher,
+                reader,
+                shipment.id,
+            )
+            .await
+            .unwrap();
+            assert_eq!(address.phone_number, "+821012345678");
+        }
+        assert!(matches!(
+            ShipmentService::reveal_address(
+                &repo,
+                &delivery_repo,
+                &cipher,
+                &other_staff,
+                shipment.id
+            )
+            .await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            ShipmentService::reveal_address(&repo, &delivery_repo, &cipher, &stranger, shipment.id)
+                .await,
+            Err(AppError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_events_are_deduplicated_and_drive_status() {
+        let users = InMemoryUserRepository::default();
+        let delivery_repo = InMemoryDeliveryRepository::default();
+        let repo = InMemoryShipmentRepository::default();
+        let customer = create_user(&users, "customer", UserRole::User).await;
+        let staff = create_user(&users, "staff", UserRole::Fulfillment).await;
+        let delivery_id = create_address(&delivery_repo, customer.id).await;
+        assign(&delivery_repo, delivery_id, &staff).await;
+        let shipment = ShipmentService::register(
+            &repo,
+            &delivery_repo,
+            &carriers(),
+            &staff,
+            registration(delivery_id, Carrier::CjLogistics, "123456789012"),
+        )
+        .await
+        .unwrap();
+
+        let shipment = ShipmentService::apply_events(
+            &repo,
+            shipment,
+            vec![
+                event(ShipmentStatus::PickedUp, "집화처리", 8),
+                event(ShipmentStatus::InTransit, "간선상차", 12),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(shipment.status, ShipmentStatus::InTransit);
+        assert_eq!(shipment.last_event_at, Some(at(12)));
+
+        // A repeat plus a late scan from before the latest one.
+        let shipment = ShipmentService::apply_events(
+            &repo,
+            shipment,
+            vec![
+                event(ShipmentStatus::InTransit, "간선상차", 12),
+                event(ShipmentStatus::InTransit, "간선하차", 10),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(shipment.status, ShipmentStatus::InTransit);
+        assert_eq!(shipment.last_event_at, Some(at(12)));
+
+        let shipment = ShipmentService::apply_events(
+            &repo,
+            shipment,
+            vec![event(ShipmentStatus::Delivered, "배달완료", 17)],
+        )
+        .await
+        .unwrap();
+        assert_eq!(shipment.status, ShipmentStatus::Delivered);
+        assert_eq!(shipment.delivered_at, Some(at(17)));
+
+        let view = ShipmentService::find_for_user(&repo, customer.id, shipment.id)
+            .await
+            .unwrap();
+        let labels: Vec<_> = view
+            .events
+            .iter()
+            .map(|e| e.carrier_status.as_str())
+            .collect();
+        assert_eq!(labels, vec!["집화처리", "간선하차", "간선상차", "배달완료"]);
+        assert!(matches!(
+            ShipmentService::find_for_user(&repo, staff.id, shipment.id).await,
+            Err(AppError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_updates_registered_shipments_only() {
+        let users = InMemoryUserRepository::default();
+        let delivery_repo = InMemoryDeliveryRepository::default();
+        let repo = InMemoryShipmentRepository::default();
+        let carriers = carriers();
+        let customer = create_user(&users, "customer", UserRole::User).await;
+        let staff = create_user(&users, "staff", UserRole::Fulfillment).await;
+        let delivery_id = create_address(&delivery_repo, customer.id).await;
+        assign(&delivery_repo, delivery_id, &staff).await;
+        let shipment = ShipmentService::register(
+            &repo,
+            &delivery_repo,
+            &carriers,
+            &staff,
+            registration(delivery_id, Carrier::CjLogistics, "123456789012"),
+        )
+        .await
+        .unwrap();
+
+        let push = |tracking_number: &str, secret: &str| {
+            let body = serde_json::json!({
+                "tracking_number": tracking_number,
+                "scans": [{ "status": "배송출발", "location": "역삼", "occurred_at": "2024-05-27T09:00:00" }]
+            })
+            .to_string();
+            let ts = chrono::Utc::now().timestamp();
+            let mut headers = HeaderMap::new();
+            headers.insert(TIMESTAMP_HEADER, ts.to_string().parse().unwrap());
+            headers.insert(
+                SIGNATURE_HEADER,
+                sign_webhook(secret, ts, body.as_bytes()).parse().unwrap(),
+            );
+            (headers, body)
+        };
+
+        let (headers, body) = push("123456789012", "wrong");
+        assert!(matches!(
+            ShipmentService::receive_webhook(
+                &repo,
+                &carriers,
+                Carrier::CjLogistics,
+                &headers,
+                body.as_bytes()
+            )
+            .await,
+            Err(AppError::Carrier(_))
+        ));
+
+        let (headers, body) = push("999999999999", "cj-secret");
+        assert_eq!(
+            ShipmentService::receive_webhook(
+                &repo,
+                &carriers,
+                Carrier::CjLogistics,
+                &headers,
+                body.as_bytes()
+            )
+            .await
+            .unwrap(),
+            0
+        );
+
+        let (headers, body) = push("123456789012", "cj-secret");
+        assert_eq!(
+            ShipmentService::receive_webhook(
+                &repo,
+                &carriers,
+                Carrier::CjLogistics,
+                &headers,
+                body.as_bytes()
+            )
+            .await
+            .unwrap(),
+            1
+        );
+        let updated = repo.find_by_id(shipment.id).await.unwrap().unwrap();
+        assert_eq!(updated.status, ShipmentStatus::OutForDelivery);
+
+        // Lotte has no webhook secret and is polled instead.
+        assert!(matches!(
+            ShipmentService::receive_webhook(
+                &repo,
+                &carriers,
+                Carrier::Lotte,
+                &headers,
+                body.as_bytes()
+            )
+            .await,
+            Err(AppError::Carrier(_))
+        ));
+    }
+}
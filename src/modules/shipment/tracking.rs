@@ -0,0 +1,196 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::carriers::CarrierRegistry;
+use super::entities::shipment;
+use super::repository::ShipmentRepository;
+use super::service::ShipmentService;
+use crate::shared::error::AppResult;
+use crate::shared::jobs::spawn_interval;
+use crate::shared::repository::RepositoryManager;
+
+pub const POLL_BATCH_SIZE: u64 = 100;
+/// Carriers update their tracking pages a few times a day at most.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Pulls tracking for parcels whose carrier does not push updates, until
+/// they are delivered.
+pub struct TrackingPoller;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PollReport {
+    pub polled: u64,
+    /// Shipments that got new events.
+    pub updated: u64,
+    /// Carrier calls that failed; retried on the next run.
+    pub failed: u64,
+}
+
+impl TrackingPoller {
+    pub async fn run(
+        repo: &dyn ShipmentRepository,
+        carriers: &CarrierRegistry,
+    ) -> AppResult<PollReport> {
+        let mut report = PollReport::default();
+        let polled_carriers = carriers.polled_carriers();
+        if polled_carriers.is_empty() {
+            return Ok(report);
+        }
+        let mut after_id = 0;
+        loop {
+            let batch = repo
+                .find_pollable(polled_carriers.clone(), after_id, POLL_BATCH_SIZE)
+                .await?;
+            let Some(last) = batch.last() else {
+                return Ok(report);
+            };
+            after_id = last.id;
+
+            for shipment in batch {
+                let id = shipment.id;
+                report.polled += 1;
+                match Self::poll(repo, carriers, shipment).await {
+                    Ok(true) => report.updated += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::warn!("Polling shipment {} failed: {}", id, e);
+                        report.failed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns whether the shipment got new events.
+    async fn poll(
+        repo: &dyn ShipmentRepository,
+        carriers: &CarrierRegistry,
+        shipment: shipment::Model,
+    ) -> AppResult<bool> {
+        let Some(adapter) = carriers.get(shipment.carrier) else {
+            return Ok(false);
+        };
+        let events = adapter.track(&shipment.tracking_number).await?;
+        let before = shipment.clone();
+        let updated = ShipmentService::apply_events(repo, shipment, events).await?;
+        repo.update(shipment::ActiveModel {
+            id: Unchanged(before.id),
+            last_polled_at: Set(Some(chrono::Utc::now().naive_utc())),
+            ..Default::default()
+        })
+        .await?;
+        Ok(updated != before)
+    }
+
+    /// Runs on a fixed interval for the lifetime of the process.
+    pub fn spawn(
+        repo_manager: Arc<dyn RepositoryManager>,
+        carriers: CarrierRegistry,
+    ) -> tokio::task::JoinHandle<()> {
+        spawn_interval(
+            "Tracking poll",
+            POLL_INTERVAL,
+            repo_manager,
+            move |repo: Arc<dyn ShipmentRepository>| {
+                let carriers = carriers.clone();
+                async move {
+                    let report = Self::run(repo.as_ref(), &carriers).await?;
+                    if report.updated > 0 || report.failed > 0 {
+                        tracing::info!(
+                            "Polled {} shipments, {} updated ({} failed)",
+                            report.polled,
+                            report.updated,
+                            report.failed
+                        );
+                    }
+                    Ok(())
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::shipment::carriers::MockCarrierAdapter;
+    use crate::modules::shipment::entities::shipment::{Carrier, ShipmentStatus};
+    use crate::modules::shipment::infra::persistence::InMemoryShipmentRepository;
+
+    fn at(hour: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 27)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    async fn create_shipment(
+        repo: &InMemoryShipmentRepository,
+        carrier: Carrier,
+        tracking_number: &str,
+    ) -> shipment::Model {
+        let now = chrono::Utc::now().naive_utc();
+        repo.create(shipment::ActiveModel {
+            user_id: Set(1),
+            delivery_id: Set(None),
+            place_id: Set(None),
+            carrier: Set(carrier),
+            tracking_number: Set(tracking_number.to_string()),
+            status: Set(ShipmentStatus::InfoReceived),
+            last_event_at: Set(None),
+            delivered_at: Set(None),
+            last_polled_at: Set(None),
+            registered_by: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_polls_carriers_without_webhooks_until_delivered() {
+        let repo = InMemoryShipmentRepository::default();
+        let cj = MockCarrierAdapter::new(Carrier::CjLogistics, Some("cj-secret".to_string()));
+        let lotte = MockCarrierAdapter::new(Carrier::Lotte, None);
+        cj.record_scan("111111111111", "집화처리", None, at(8));
+        lotte.record_scan("222222222222", "상품집하", Some("송파"), at(8));
+        lotte.record_scan("222222222222", "배달 완료", Some("역삼"), at(15));
+        let carriers = CarrierRegistry::new().register(cj).register(lotte);
+
+        let pushed = create_shipment(&repo, Carrier::CjLogistics, "111111111111").await;
+        let delivered = create_shipment(&repo, Carrier::Lotte, "222222222222").await;
+        let quiet = create_shipment(&repo, Carrier::Lotte, "333333333333").await;
+
+        assert_eq!(
+            TrackingPoller::run(&repo, &carriers).await.unwrap(),
+            PollReport {
+                polled: 2,
+                updated: 1,
+                failed: 0
+            }
+        );
+        let delivered = repo.find_by_id(delivered.id).await.unwrap().unwrap();
+        assert_eq!(delivered.status, ShipmentStatus::Delivered);
+        assert_eq!(delivered.delivered_at, Some(at(15)));
+        assert!(delivered.last_polled_at.is_some());
+        let quiet = repo.find_by_id(quiet.id).await.unwrap().unwrap();
+        assert_eq!(quiet.status, ShipmentStatus::InfoReceived);
+        assert!(quiet.last_polled_at.is_some());
+        // Pushed by webhook, never polled.
+        let pushed = repo.find_by_id(pushed.id).await.unwrap().unwrap();
+        assert!(pushed.last_polled_at.is_none());
+
+        // Delivered parcels drop out of polling.
+        assert_eq!(
+            TrackingPoller::run(&repo, &carriers).await.unwrap(),
+            PollReport {
+                polled: 1,
+                updated: 0,
+                failed: 0
+            }
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod terms_document;
+pub mod user_consent;
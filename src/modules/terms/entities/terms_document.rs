@@ -0,0 +1,59 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TermsKind {
+    #[sea_orm(string_value = "TERMS_OF_SERVICE")]
+    #[serde(rename = "TERMS_OF_SERVICE")]
+    TermsOfService,
+    #[sea_orm(string_value = "PRIVACY_POLICY")]
+    #[serde(rename = "PRIVACY_POLICY")]
+    PrivacyPolicy,
+    /// 위치기반서비스 이용약관
+    #[sea_orm(string_value = "LOCATION_TERMS")]
+    #[serde(rename = "LOCATION_TERMS")]
+    LocationTerms,
+    #[sea_orm(string_value = "MARKETING_SMS")]
+    #[serde(rename = "MARKETING_SMS")]
+    MarketingSms,
+    #[sea_orm(string_value = "MARKETING_EMAIL")]
+    #[serde(rename = "MARKETING_EMAIL")]
+    MarketingEmail,
+    #[sea_orm(string_value = "MARKETING_PUSH")]
+    #[serde(rename = "MARKETING_PUSH")]
+    MarketingPush,
+}
+
+/// One published version of a legal document. Rows are never edited;
+/// a change is a new version.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "terms_documents")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub kind: TermsKind,
+    pub version: i32,
+    pub title: String,
+    pub content_url: String,
+    /// Required documents must be agreed to before using the service.
+    pub required: bool,
+    pub effective_at: DateTime,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::user_consent::Entity")]
+    UserConsents,
+}
+
+impl Related<super::user_consent::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserConsents.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Append-only consent history. The latest row per user and document kind
+/// is the user's current decision, which matters for withdrawable
+/// marketing consents.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user_consents")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub terms_document_id: i32,
+    pub agreed: bool,
+    pub recorded_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::modules::users::entities::user::Entity",
+        from = "Column::UserId",
+        to = "crate::modules::users::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::terms_document::Entity",
+        from = "Column::TermsDocumentId",
+        to = "super::terms_document::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    TermsDocument,
+}
+
+impl Related<super::terms_document::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TermsDocument.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,109 @@
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+
+use super::entities::terms_document;
+use super::repository::TermsRepository;
+use super::service::{ConsentSubmission, DocumentConsent, TermsService};
+use crate::modules::users::repository::UserRepository;
+use crate::shared::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct TermsStatusResponse {
+    #[serde(flatten)]
+    pub document: terms_document::Model,
+    pub agreed: bool,
+    pub agreed_at: Option<chrono::NaiveDateTime>,
+}
+
+impl From<DocumentConsent> for TermsStatusResponse {
+    fn from(status: DocumentConsent) -> Self {
+        Self {
+            agreed: status.is_agreed(),
+            agreed_at: status.consent.filter(|c| c.agreed).map(|c| c.recorded_at),
+            document: status.document,
+        }
+    }
+}
+
+pub async fn get_current_terms(
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<terms_document::Model>>> {
+    let repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+
+    Ok(Json(TermsService::current_documents(repo.as_ref()).await?))
+}
+
+pub async fn get_my_consents(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<Vec<TermsStatusResponse>>> {
+    let repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = user_repo
+        .find_by_uuid(&claims.sub)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let status = TermsService::status_for(repo.as_ref(), user.id).await?;
+    Ok(Json(status.into_iter().map(Into::into).collect()))
+}
+
+#[derive(Deserialize)]
+pub struct ConsentItem {
+    pub terms_document_id: i32,
+    pub agreed: bool,
+}
+
+#[derive(Deserialize)]
+pub struct SubmitConsentsRequest {
+    pub consents: Vec<ConsentItem>,
+}
+
+pub async fn submit_consents(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<SubmitConsentsRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = user_repo
+        .find_by_uuid(&claims.sub)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let status = TermsService::submit(
+        repo.as_ref(),
+        state.repo_manager.as_ref(),
+        user.id,
+        body.consents
+            .into_iter()
+            .map(|c| ConsentSubmission {
+                terms_document_id: c.terms_document_id,
+                agreed: c.agreed,
+            })
+            .collect(),
+    )
+    .await?;
+
+    let needs_agreement = status.iter().any(|s| s.document.required && !s.is_agreed());
+    let terms: Vec<TermsStatusResponse> = status.into_iter().map(Into::into).collect();
+
+    Ok(Json(serde_json::json!({
+        "needs_agreement": needs_agreement,
+        "terms": terms,
+    })))
+}
@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use sea_orm::*;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::impl_sea_orm_repo;
+use crate::modules::terms::entities::{
+    terms_document::{self, TermsKind},
+    user_consent,
+};
+use crate::modules::terms::repository::TermsRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
+use crate::shared::repository::UnitOfWork;
+
+/// Keeps the first (highest version) document of each kind.
+fn latest_per_kind(documents: Vec<terms_document::Model>) -> Vec<terms_document::Model> {
+    let mut seen = HashSet::new();
+    documents
+        .into_iter()
+        .filter(|d| seen.insert(d.kind))
+        .collect()
+}
+
+// =========================================================================
+// Postgres Implementation
+// =========================================================================
+
+pub type PostgresTermsRepository = SeaOrmRepository<terms_document::Entity>;
+
+impl_sea_orm_repo!(PostgresTermsRepository, TermsRepository, {
+    async fn find_current_documents(
+        &self,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<terms_document::Model>> {
+        let query = terms_document::Entity::find()
+            .filter(terms_document::Column::EffectiveAt.lte(at))
+            .order_by_asc(terms_document::Column::Kind)
+            .order_by_desc(terms_document::Column::Version);
+        let documents = match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+
+        Ok(latest_per_kind(documents))
+    }
+
+    async fn find_latest_document(
+        &self,
+        kind: TermsKind,
+    ) -> AppResult<Option<terms_document::Model>> {
+        let query = terms_document::Entity::find()
+            .filter(terms_document::Column::Kind.eq(kind))
+            .order_by_desc(terms_document::Column::Version);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_document(
+        &self,
+        document: terms_document::ActiveModel,
+    ) -> AppResult<terms_document::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => document.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                document.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_consents_by_user(&self, user_id: i32) -> AppResult<Vec<user_consent::Model>> {
+        let query = user_consent::Entity::find()
+            .filter(user_consent::Column::UserId.eq(user_id))
+            .order_by_asc(user_consent::Column::RecordedAt)
+            .order_by_asc(user_consent::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_consent(
+        &self,
+        consent: user_consent::ActiveModel,
+    ) -> AppResult<user_consent::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => consent.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                consent.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+});
+
+// =========================================================================
+// InMemory Implementation
+// =========================================================================
+
+#[derive(Clone, Default)]
+pub struct InMemoryTermsRepository {
+    documents: Arc<Mutex<Vec<terms_document::Model>>>,
+    consents: Arc<Mutex<Vec<user_consent::Model>>>,
+}
+
+#[async_trait]
+impl TermsRepository for InMemoryTermsRepository {
+    async fn find_current_documents(
+        &self,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<terms_document::Model>> {
+        let documents = self.documents.lock().unwrap();
+        let mut current: Vec<terms_document::Model> = documents
+            .iter()
+            .filter(|d| d.effective_at <= at)
+            .cloned()
+            .collect();
+        current.sort_by_key(|d| std::cmp::Reverse(d.version));
+        Ok(latest_per_kind(current))
+    }
+
+    async fn find_latest_document(
+        &self,
+        kind: TermsKind,
+    ) -> AppResult<Option<terms_document::Model>> {
+        let documents = self.documents.lock().unwrap();
+        Ok(documents
+            .iter()
+            .filter(|d| d.kind == kind)
+            .max_by_key(|d| d.version)
+            .cloned())
+    }
+
+    async fn create_document(
+        &self,
+        document: terms_document::ActiveModel,
+    ) -> AppResult<terms_document::Model> {
+        let mut documents = self.documents.lock().unwrap();
+        let model = terms_document::Model {
+            id: documents.len() as i32 + 1,
+            kind: document.kind.unwrap(),
+            version: document.version.unwrap(),
+            title: document.title.unwrap(),
+            content_url: document.content_url.unwrap(),
+            required: document.required.unwrap(),
+            effective_at: document.effective_at.unwrap(),
+            created_at: document.created_at.unwrap(),
+        };
+        documents.push(model.clone());
+        Ok(model)
+    }
+
+    async fn find_consents_by_user(&self, user_id: i32) -> AppResult<Vec<user_consent::Model>> {
+        let consents = self.consents.lock().unwrap();
+        Ok(consents
+            .iter()
+            .filter(|c| c.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_consent(
+        &self,
+        consent: user_consent::ActiveModel,
+    ) -> AppResult<user_consent::Model> {
+        let mut consents = self.consents.lock().unwrap();
+        let model = user_consent::Model {
+            id: consents.len() as i32 + 1,
+            user_id: consent.user_id.unwrap(),
+            terms_document_id: consent.terms_document_id.unwrap(),
+            agreed: consent.agreed.unwrap(),
+            recorded_at: consent.recorded_at.unwrap(),
+        };
+        consents.push(model.clone());
+        Ok(model)
+    }
+
+    fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn TermsRepository>> {
+        Some(Box::new(self.clone()))
+    }
+}
@@ -0,0 +1,31 @@
+use super::entities::{
+    terms_document::{self, TermsKind},
+    user_consent,
+};
+use crate::shared::error::AppResult;
+
+crate::define_repo!(TermsRepository, {
+    /// Latest version of each kind already in effect at `at`.
+    async fn find_current_documents(
+        &self,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<terms_document::Model>>;
+
+    /// Latest version of `kind`, including ones not yet in effect.
+    async fn find_latest_document(
+        &self,
+        kind: TermsKind,
+    ) -> AppResult<Option<terms_document::Model>>;
+
+    async fn create_document(
+        &self,
+        document: terms_document::ActiveModel,
+    ) -> AppResult<terms_document::Model>;
+
+    async fn find_consents_by_user(&self, user_id: i32) -> AppResult<Vec<user_consent::Model>>;
+
+    async fn create_consent(
+        &self,
+        consent: user_consent::ActiveModel,
+    ) -> AppResult<user_consent::Model>;
+});
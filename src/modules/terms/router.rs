@@ -0,0 +1,14 @@
+use super::handlers;
+use crate::shared::state::AppState;
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(handlers::get_current_terms))
+        .route("/me", get(handlers::get_my_consents))
+        .route("/consents", post(handlers::submit_consents))
+        .with_state(state)
+}
@@ -0,0 +1,262 @@
+use sea_orm::ActiveValue::Set;
+use std::collections::HashMap;
+
+use super::entities::{
+    terms_document::{self, TermsKind},
+    user_consent,
+};
+use super::repository::TermsRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::repository::RepositoryManager;
+
+pub struct ConsentSubmission {
+    pub terms_document_id: i32,
+    pub agreed: bool,
+}
+
+/// A current document and the user's latest decision on it, if any.
+pub struct DocumentConsent {
+    pub document: terms_document::Model,
+    pub consent: Option<user_consent::Model>,
+}
+
+impl DocumentConsent {
+    pub fn is_agreed(&self) -> bool {
+        self.consent.as_ref().is_some_and(|c| c.agreed)
+    }
+}
+
+pub struct TermsService;
+
+impl TermsService {
+    pub async fn current_documents(
+        repo: &dyn TermsRepository,
+    ) -> AppResult<Vec<terms_document::Model>> {
+        repo.find_current_documents(chrono::Utc::now().naive_utc())
+            .await
+    }
+
+    /// Current documents paired with the user's consent to that exact version.
+    pub async fn status_for(
+        repo: &dyn TermsRepository,
+        user_id: i32,
+    ) -> AppResult<Vec<DocumentConsent>> {
+        let documents = Self::current_documents(repo).await?;
+        // Consents come oldest first, so later rows overwrite earlier ones.
+        let latest: HashMap<i32, user_consent::Model> = repo
+            .find_consents_by_user(user_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.terms_document_id, c))
+            .collect();
+
+        Ok(documents
+            .into_iter()
+            .map(|document| DocumentConsent {
+                consent: latest.get(&document.id).cloned(),
+                document,
+            })
+            .collect())
+    }
+
+    /// Required documents whose current version the user has not agreed to.
+    /// A newly published required version lands here for every user.
+    pub async fn pending_required(
+        repo: &dyn TermsRepository,
+        user_id: i32,
+    ) -> AppResult<Vec<terms_document::Model>> {
+        Ok(Self::status_for(repo, user_id)
+            .await?
+            .into_iter()
+            .filter(|s| s.document.required && !s.is_agreed())
+            .map(|s| s.document)
+            .collect())
+    }
+
+    /// Records consents to current documents in one transaction.
+    /// Required documents can only be agreed to; optional ones (marketing)
+    /// may also be declined or withdrawn.
+    pub async fn submit(
+        repo: &dyn TermsRepository,
+        repo_manager: &dyn RepositoryManager,
+        user_id: i32,
+        submissions: Vec<ConsentSubmission>,
+    ) -> AppResult<Vec<DocumentConsent>> {
+        if submissions.is_empty() {
+            return Err(AppError::BadRequest("No consents submitted".to_string()));
+        }
+
+        let current: HashMap<i32, terms_document::Model> = Self::current_documents(repo)
+            .await?
+            .into_iter()
+            .map(|d| (d.id, d))
+            .collect();
+        for submission in &submissions {
+            let document =
+                current
+                    .get(&submission.terms_document_id)
+                    .ok_or(AppError::BadRequest(format!(
+                        "Terms document {} is not a current version",
+                        submission.terms_document_id
+                    )))?;
+            if document.required && !submission.agreed {
+                return Err(AppError::BadRequest(format!(
+                    "{:?} is required and cannot be declined",
+                    document.kind
+                )));
+            }
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let uow = repo_manager.begin().await?;
+        let tx_repo = repo
+            .with_transaction(&*uow)
+            .ok_or(AppError::InternalServerError(
+                "Failed to start transaction for terms repo".to_string(),
+            ))?;
+        for submission in submissions {
+            let result = tx_repo
+                .create_consent(user_consent::ActiveModel {
+                    user_id: Set(user_id),
+                    terms_document_id: Set(submission.terms_document_id),
+                    agreed: Set(submission.agreed),
+                    recorded_at: Set(now),
+                    ..Default::default()
+                })
+                .await;
+            if let Err(e) = result {
+                uow.rollback().await?;
+                return Err(e);
+            }
+        }
+        uow.commit().await?;
+
+        Self::status_for(repo, user_id).await
+    }
+
+    /// Publishes the next version of `kind`. Existing consents stay attached
+    /// to the version they were given for.
+    pub async fn publish(
+        repo: &dyn TermsRepository,
+        kind: TermsKind,
+        title: &str,
+        content_url: &str,
+        required: bool,
+        effective_at: Option<chrono::NaiveDateTime>,
+    ) -> AppResult<terms_document::Model> {
+        let title = title.trim();
+        let content_url = content_url.trim();
+        if title.is_empty() || content_url.is_empty() {
+            return Err(AppError::BadRequest(
+                "Title and content URL are required".to_string(),
+            ));
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let version = repo
+            .find_latest_document(kind)
+            .await?
+            .map_or(1, |d| d.version + 1);
+
+        repo.create_document(terms_document::ActiveModel {
+            kind: Set(kind),
+            version: Set(version),
+            title: Set(title.to_string()),
+            content_url: Set(content_url.to_string()),
+            required: Set(required),
+            effective_at: Set(effective_at.unwrap_or(now)),
+            created_at: Set(now),
+            ..Default::default()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::terms::infra::persistence::InMemoryTermsRepository;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    #[tokio::test]
+    async fn test_new_required_version_needs_agreement_again() {
+        let repo = InMemoryTermsRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let tos = TermsService::publish(
+            &repo,
+            TermsKind::TermsOfService,
+            "이용약관",
+            "/tos/1",
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        let sms = TermsService::publish(
+            &repo,
+            TermsKind::MarketingSms,
+            "SMS 수신",
+            "/sms/1",
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            TermsService::pending_required(&repo, 1).await.unwrap(),
+            vec![tos.clone()]
+        );
+
+        let declined_required = TermsService::submit(
+            &repo,
+            &manager,
+            1,
+            vec![ConsentSubmission {
+                terms_document_id: tos.id,
+                agreed: false,
+            }],
+        )
+        .await;
+        assert!(matches!(declined_required, Err(AppError::BadRequest(_))));
+
+        TermsService::submit(
+            &repo,
+            &manager,
+            1,
+            vec![
+                ConsentSubmission {
+                    terms_document_id: tos.id,
+                    agreed: true,
+                },
+                ConsentSubmission {
+                    terms_document_id: sms.id,
+                    agreed: false,
+                },
+            ],
+        )
+        .await
+        .unwrap();
+        assert!(
+            TermsService::pending_required(&repo, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let tos_v2 = TermsService::publish(
+            &repo,
+            TermsKind::TermsOfService,
+            "이용약관",
+            "/tos/2",
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(tos_v2.version, 2);
+        assert_eq!(
+            TermsService::pending_required(&repo, 1).await.unwrap(),
+            vec![tos_v2]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use chrono::Datelike;
+
+use super::entities::user;
+use crate::shared::error::{AppError, AppResult};
+
+/// 청소년보호법: a buyer is an adult from January 1st of the year they turn 19.
+pub const ADULT_AGE: i32 = 19;
+
+pub struct AgeGate;
+
+impl AgeGate {
+    /// Parses the lower bound of a provider bracket such as "20~29" or "90~".
+    pub fn age_range_lower_bound(age_range: &str) -> Option<i32> {
+        age_range.split('~').next()?.trim().parse().ok()
+    }
+
+    /// True only if the provider data proves the user is an adult in `year`.
+    /// A bracket that straddles the limit ("15~19") does not prove anything.
+    pub fn is_verified_adult(user: &user::Model, year: i32) -> bool {
+        if let Some(birth_year) = user.birth_year {
+            return year - birth_year >= ADULT_AGE;
+        }
+
+        user.age_range
+            .as_deref()
+            .and_then(Self::age_range_lower_bound)
+            .is_some_and(|lower| lower > ADULT_AGE)
+    }
+
+    /// Guard for age-restricted goods such as alcohol.
+    pub fn ensure_adult(user: &user::Model) -> AppResult<()> {
+        if Self::is_verified_adult(user, chrono::Utc::now().year()) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(
+                "Age verification required for age-restricted goods".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::entities::enums::UserRole;
+    use crate::modules::users::infra::fixtures::detached_user;
+
+    fn user_with(age_range: Option<&str>, birth_year: Option<i32>) -> user::Model {
+        user::Model {
+            age_range: age_range.map(str::to_string),
+            birth_year,
+            ..detached_user(1, UserRole::User)
+        }
+    }
+
+    #[test]
+    fn test_birth_year_takes_precedence() {
+        assert!(AgeGate::is_verified_adult(
+            &user_with(None, Some(2006)),
+            2025
+        ));
+        assert!(!AgeGate::is_verified_adult(
+            &user_with(None, Some(2007)),
+            2025
+        ));
+        assert!(!AgeGate::is_verified_adult(
+            &user_with(Some("20~29"), Some(2010)),
+            2025
+        ));
+    }
+
+    #[test]
+    fn test_age_range_fallback() {
+        assert!(AgeGate::is_verified_adult(
+            &user_with(Some("20~29"), None),
+            2025
+        ));
+        assert!(AgeGate::is_verified_adult(
+            &user_with(Some("90~"), None),
+            2025
+        ));
+        assert!(!AgeGate::is_verified_adult(
+            &user_with(Some("15~19"), None),
+            2025
+        ));
+        assert!(!AgeGate::is_verified_adult(&user_with(None, None), 2025));
+    }
+}
@@ -0,0 +1,299 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, Rgb, RgbImage};
+use sea_orm::ActiveValue::{Set, Unchanged};
+use std::io::Cursor;
+
+use super::entities::user;
+use super::repository::UserRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::storage::FileStorage;
+
+/// Upload limit before decoding; the request body limit is set slightly above this.
+pub const AVATAR_MAX_BYTES: usize = 5 * 1024 * 1024;
+/// Larger sources are rejected before allocating the decoded bitmap.
+pub const AVATAR_MAX_DIMENSION: u32 = 4096;
+/// Cap on the decoded bitmap: a full-size 8-bit RGBA image. 16-bit sources
+/// of the maximum size are refused rather than doubling it.
+pub const AVATAR_MAX_DECODED_BYTES: u64 =
+    AVATAR_MAX_DIMENSION as u64 * AVATAR_MAX_DIMENSION as u64 * 4;
+/// Both renditions are square, center-cropped JPEGs.
+pub const AVATAR_SIZE: u32 = 512;
+pub const AVATAR_THUMBNAIL_SIZE: u32 = 128;
+const JPEG_QUALITY: u8 = 85;
+
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &["image/jpeg", "image/png", "image/webp"];
+
+/// Re-encoded avatar renditions, free of any metadata from the upload.
+#[derive(Debug)]
+pub struct ProcessedAvatar {
+    pub image: Vec<u8>,
+    pub thumbnail: Vec<u8>,
+}
+
+pub struct AvatarService;
+
+impl AvatarService {
+    /// Validates and normalizes an uploaded image. The format is sniffed from
+    /// the bytes rather than trusted from the request; EXIF orientation is
+    /// applied and the pixels re-encoded, so EXIF (GPS included) never reaches storage.
+    pub fn process(bytes: &[u8]) -> AppResult<ProcessedAvatar> {
+        if bytes.len() > AVATAR_MAX_BYTES {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Avatar must be at most {} MB",
+                AVATAR_MAX_BYTES / 1024 / 1024
+            )));
+        }
+
+        let unsupported =
+            || AppError::UnsupportedMediaType("Avatar must be a JPEG, PNG or WebP image".into());
+        let mut reader = ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|_| unsupported())?;
+        if !matches!(
+            reader.format(),
+            Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)
+        ) {
+            return Err(unsupported());
+        }
+
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(AVATAR_MAX_DIMENSION);
+        limits.max_image_height = Some(AVATAR_MAX_DIMENSION);
+        limits.max_alloc = Some(AVATAR_MAX_DECODED_BYTES);
+        reader.limits(limits);
+
+        let invalid = |e: image::ImageError| AppError::BadRequest(format!("Invalid image: {}", e));
+        let mut decoder = reader.into_decoder().map_err(invalid)?;
+        // Not every decoder honours `max_alloc`, so check the bitmap size here too.
+        if decoder.total_bytes() > AVATAR_MAX_DECODED_BYTES {
+            return Err(AppError::BadRequest(
+                "Invalid image: decoded size is too large".to_string(),
+            ));
+        }
+        let orientation = decoder.orientation().map_err(invalid)?;
+        let decoded = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+
+        // Only the full-size decode is held; everything after works on the
+        // 512px rendition. A centered square crop commutes with the EXIF
+        // rotations and flips, so orienting afterwards gives the same result.
+        let mut avatar = decoded.resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3);
+        drop(decoded);
+        avatar.apply_orientation(orientation);
+        let avatar = DynamicImage::ImageRgb8(Self::flatten_on_white(avatar));
+        let thumbnail = avatar.resize_to_fill(
+            AVATAR_THUMBNAIL_SIZE,
+            AVATAR_THUMBNAIL_SIZE,
+            FilterType::Lanczos3,
+        );
+        Ok(ProcessedAvatar {
+            image: Self::encode_jpeg(&avatar)?,
+            thumbnail: Self::encode_jpeg(&thumbnail)?,
+        })
+    }
+
+    /// JPEG has no alpha; transparent areas become white instead of black.
+    fn flatten_on_white(image: DynamicImage) -> RgbImage {
+        if !image.color().has_alpha() {
+            return image.into_rgb8();
+        }
+        let rgba = image.into_rgba8();
+        RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+            let [r, g, b, a] = rgba.get_pixel(x, y).0;
+            let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+            Rgb([blend(r), blend(g), blend(b)])
+        })
+    }
+
+    fn encode_jpeg(image: &DynamicImage) -> AppResult<Vec<u8>> {
+        let mut out = Vec::new();
+        JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)
+            .encode_image(image)
+            .map_err(|e| AppError::InternalServerError(format!("Avatar encoding failed: {}", e)))?;
+        Ok(out)
+    }
+
+    /// Processes and stores a new avatar, then points the user at it.
+    /// The previous renditions are removed once the user row is updated.
+    pub async fn upload(
+        repo: &dyn UserRepository,
+        storage: &dyn FileStorage,
+        uuid: &str,
+        bytes: Vec<u8>,
+    ) -> AppResult<user::Model> {
+        let user = repo
+            .find_with_details_by_uuid(uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let processed = tokio::task::spawn_blocking(move || Self::process(&bytes))
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Avatar task failed: {}", e)))??;
+
+        let id = uuid::Uuid::now_v7().simple();
+        let image_key = format!("avatars/{}/{}.jpg", user.uuid, id);
+        let thumbnail_key = format!("avatars/{}/{}_thumb.jpg", user.uuid, id);
+        storage
+            .put(&image_key, processed.image, "image/jpeg")
+            .await?;
+        storage
+            .put(&thumbnail_key, processed.thumbnail, "image/jpeg")
+            .await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let user_active = user::ActiveModel {
+            id: Unchanged(user.id),
+            avatar_key: Set(Some(image_key.clone())),
+            avatar_thumbnail_key: Set(Some(thumbnail_key.clone())),
+            avatar_updated_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let mut updated = match repo.update_user(user_active).await {
+            Ok(u) => u,
+            Err(e) => {
+                Self::delete_quietly(storage, [Some(image_key), Some(thumbnail_key)]).await;
+                return Err(e);
+            }
+        };
+
+        Self::delete_quietly(storage, [user.avatar_key, user.avatar_thumbnail_key]).await;
+        updated.verification = user.verification;
+        updated.socials = user.socials;
+
+        Ok(updated)
+    }
+
+    /// Clears the avatar so clients fall back to their placeholder.
+    pub async fn remove(
+        repo: &dyn UserRepository,
+        storage: &dyn FileStorage,
+        uuid: &str,
+    ) -> AppResult<user::Model> {
+        let user = repo
+            .find_with_details_by_uuid(uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if user.avatar_key.is_none() && user.avatar_thumbnail_key.is_none() {
+            return Ok(user);
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let user_active = user::ActiveModel {
+            id: Unchanged(user.id),
+            avatar_key: Set(None),
+            avatar_thumbnail_key: Set(None),
+            avatar_updated_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let mut updated = repo.update_user(user_active).await?;
+
+        Self::delete_quietly(storage, [user.avatar_key, user.avatar_thumbnail_key]).await;
+        updated.verification = user.verification;
+        updated.socials = user.socials;
+
+        Ok(updated)
+    }
+
+    /// Orphaned objects are harmless, so cleanup failures are only logged.
+    async fn delete_quietly(storage: &dyn FileStorage, keys: [Option<String>; 2]) {
+        for key in keys.into_iter().flatten() {
+            if let Err(e) = storage.delete(&key).await {
+                tracing::warn!("Failed to delete avatar object {}: {}", key, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::infra::fixtures::sign_up;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::shared::storage::LocalFileStorage;
+    use image::{ImageEncoder, Rgba, RgbaImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let img = RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 0]));
+        let mut out = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut out)
+            .write_image(&img, width, height, image::ExtendedColorType::Rgba8)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_process_validates_and_resizes() {
+        let processed = AvatarService::process(&png(800, 600)).unwrap();
+        let image = image::load_from_memory(&processed.image).unwrap();
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert_eq!(
+            image::guess_format(&processed.image).unwrap(),
+            ImageFormat::Jpeg
+        );
+        assert_eq!((image.width(), image.height()), (AVATAR_SIZE, AVATAR_SIZE));
+        assert_eq!(
+            (thumbnail.width(), thumbnail.height()),
+            (AVATAR_THUMBNAIL_SIZE, AVATAR_THUMBNAIL_SIZE)
+        );
+        // Fully transparent pixels are flattened onto white.
+        assert!(image.to_rgb8().get_pixel(0, 0).0.iter().all(|c| *c > 240));
+
+        assert!(matches!(
+            AvatarService::process(b"GIF89a not really"),
+            Err(AppError::UnsupportedMediaType(_))
+        ));
+        assert!(matches!(
+            AvatarService::process(&vec![0u8; AVATAR_MAX_BYTES + 1]),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+        // Small on disk, but past the dimension limit.
+        assert!(matches!(
+            AvatarService::process(&png(AVATAR_MAX_DIMENSION + 1, 16)),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_replaces_previous_avatar() {
+        let repo = InMemoryUserRepository::default();
+        let root = std::env::temp_dir().join(format!("gimme-avatar-{}", uuid::Uuid::new_v4()));
+        let storage = LocalFileStorage::new(&root, "http://localhost:3000/files");
+        let user = sign_up(&repo, "avatar").await;
+
+        let first = AvatarService::upload(&repo, &storage, &user.uuid, png(64, 64))
+            .await
+            .unwrap();
+        let first_key = first.avatar_key.clone().unwrap();
+        assert!(first_key.starts_with(&format!("avatars/{}/", user.uuid)));
+        assert!(storage.get(&first_key).await.unwrap().is_some());
+
+        let second = AvatarService::upload(&repo, &storage, &user.uuid, png(64, 64))
+            .await
+            .unwrap();
+        assert_ne!(second.avatar_key, first.avatar_key);
+        assert!(storage.get(&first_key).await.unwrap().is_none());
+        assert!(
+            storage
+                .get(first.avatar_thumbnail_key.as_deref().unwrap())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let removed = AvatarService::remove(&repo, &storage, &user.uuid)
+            .await
+            .unwrap();
+        assert!(removed.avatar_key.is_none());
+        assert!(
+            storage
+                .get(second.avatar_key.as_deref().unwrap())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+}
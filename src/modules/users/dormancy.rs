@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::repository::UserRepository;
+use crate::shared::error::AppResult;
+use crate::shared::jobs::spawn_interval;
+use crate::shared::repository::RepositoryManager;
+
+/// 개인정보보호법: accounts unused for a year must be made dormant.
+pub const DORMANT_AFTER_DAYS: i64 = 365;
+/// How often the sweep runs. Dormancy is day-granular, so hourly is plenty.
+pub const DORMANCY_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct DormancyService;
+
+impl DormancyService {
+    /// Marks every active account idle for `DORMANT_AFTER_DAYS` as dormant.
+    /// Users who never logged in are measured from their sign-up date.
+    pub async fn sweep(repo: &dyn UserRepository, now: chrono::NaiveDateTime) -> AppResult<u64> {
+        let idle_before = now - chrono::Duration::days(DORMANT_AFTER_DAYS);
+        repo.mark_dormant(idle_before, now).await
+    }
+
+    /// Runs the sweep on a fixed interval for the lifetime of the process.
+    pub fn spawn(repo_manager: Arc<dyn RepositoryManager>) -> tokio::task::JoinHandle<()> {
+        spawn_interval(
+            "Dormancy sweep",
+            DORMANCY_SWEEP_INTERVAL,
+            repo_manager,
+            |repo: Arc<dyn UserRepository>| async move {
+                let marked = Self::sweep(repo.as_ref(), chrono::Utc::now().naive_utc()).await?;
+                if marked > 0 {
+                    tracing::info!("Marked {} accounts dormant", marked);
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::entities::{enums::AccountStatus, social::SocialProvider, user};
+    use crate::modules::users::infra::fixtures::kakao_login;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::modules::users::service::UserService;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+    use sea_orm::ActiveValue::Set;
+
+    #[tokio::test]
+    async fn test_idle_account_goes_dormant_and_login_reactivates() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let created = UserService::handle_social_login(&repo, &manager, kakao_login("12345"))
+            .await
+            .unwrap()
+            .user;
+        assert_eq!(created.login_count, 1);
+
+        let mut active: user::ActiveModel = created.clone().into();
+        active.account_status = Set(AccountStatus::Active);
+        repo.update_user(active).await.unwrap();
+
+        let now = chrono::Utc::now().naive_utc();
+        let soon = now + chrono::Duration::days(30);
+        assert_eq!(DormancyService::sweep(&repo, soon).await.unwrap(), 0);
+
+        let later = now + chrono::Duration::days(DORMANT_AFTER_DAYS + 1);
+        assert_eq!(DormancyService::sweep(&repo, later).await.unwrap(), 1);
+        let dormant = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(dormant.account_status, AccountStatus::Dormant);
+        assert!(dormant.dormant_at.is_some());
+
+        let result = UserService::handle_social_login(&repo, &manager, kakao_login("12345"))
+            .await
+            .unwrap();
+        assert_eq!(result.user.account_status, AccountStatus::Active);
+        assert!(result.user.dormant_at.is_none());
+        assert_eq!(result.user.login_count, 2);
+        assert_eq!(result.user.last_login_provider, Some(SocialProvider::Kakao));
+    }
+}
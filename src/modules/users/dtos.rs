@@ -7,4 +7,144 @@ pub struct SocialLoginDto {
     pub name: Option<String>,
     pub phone_number: Option<String>,
     pub connected_at: Option<String>,
+    /// Provider age bracket, e.g. Kakao's "20~29".
+    pub age_range: Option<String>,
+    pub birthyear: Option<String>,
+    /// App installation id, used for referral fraud checks.
+    pub device_id: Option<String>,
+    /// Invite code entered before sign-up; ignored for existing accounts.
+    pub referral_code: Option<String>,
+}
+
+/// What the app sends alongside the provider callback.
+#[derive(Debug, Clone, Default)]
+pub struct LoginClientInfo {
+    pub device_id: Option<String>,
+    pub referral_code: Option<String>,
+}
+
+pub struct SocialLoginResult {
+    /// Loaded with its verification record for returning users.
+    pub user: super::entities::user::Model,
+    /// Address a verification code should be issued to, if any.
+    pub verification_email: Option<String>,
+}
+
+/// Fields a user may edit on their own profile. `None` leaves a field as is.
+pub struct UpdateProfileDto {
+    pub username: Option<String>,
+    pub country_code: Option<String>,
+    pub locale: Option<String>,
+    pub phone_number: Option<String>,
+}
+
+/// Admin search filters. Every `None` field is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct UserSearchFilter {
+    pub account_status: Option<super::entities::enums::AccountStatus>,
+    pub provider: Option<SocialProvider>,
+    pub email_verified: Option<bool>,
+    pub phone_verified: Option<bool>,
+    /// Case-insensitive substring of the email address.
+    pub email: Option<String>,
+    /// Substring of the stored (E.164) phone number.
+    pub phone_number: Option<String>,
+    pub created_from: Option<chrono::NaiveDateTime>,
+    pub created_to: Option<chrono::NaiveDateTime>,
+    pub last_login_from: Option<chrono::NaiveDateTime>,
+    pub last_login_to: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    #[default]
+    CreatedAt,
+    /// Users who never logged in sort as the oldest.
+    LastLoginAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Keyset position: the sort key of the last row served and its id as tie-breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserCursor {
+    pub sort_key: chrono::NaiveDateTime,
+    pub id: i32,
+}
+
+impl UserCursor {
+    /// Sort key for users without a last login.
+    pub const NEVER: chrono::NaiveDateTime = chrono::DateTime::UNIX_EPOCH.naive_utc();
+
+    pub fn sort_key_of(
+        user: &super::entities::user::Model,
+        sort: UserSortField,
+    ) -> chrono::NaiveDateTime {
+        match sort {
+            UserSortField::CreatedAt => user.created_at,
+            UserSortField::LastLoginAt => user.last_login_at.unwrap_or(Self::NEVER),
+        }
+    }
+
+    /// Opaque form handed to clients: `<micros>_<id>`.
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.sort_key.and_utc().timestamp_micros(), self.id)
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let (micros, id) = raw.split_once('_')?;
+        let sort_key = chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc();
+        Some(Self {
+            sort_key,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UserSearchQuery {
+    pub filter: UserSearchFilter,
+    pub sort: UserSortField,
+    pub direction: SortDirection,
+    pub limit: u64,
+    pub cursor: Option<UserCursor>,
+}
+
+/// One page of users, loaded with verification and socials.
+pub struct UserPage {
+    pub items: Vec<super::entities::user::Model>,
+    pub next_cursor: Option<String>,
+}
+
+impl UserPage {
+    /// Builds a page from up to `limit + 1` sorted rows; the extra row only
+    /// signals that another page exists.
+    pub fn from_rows(
+        mut rows: Vec<super::entities::user::Model>,
+        limit: u64,
+        sort: UserSortField,
+    ) -> Self {
+        let mut next_cursor = None;
+        if rows.len() as u64 > limit {
+            rows.truncate(limit as usize);
+            next_cursor = rows.last().map(|u| {
+                UserCursor {
+                    sort_key: UserCursor::sort_key_of(u, sort),
+                    id: u.id,
+                }
+                .encode()
+            });
+        }
+        Self {
+            items: rows,
+            next_cursor,
+        }
+    }
 }
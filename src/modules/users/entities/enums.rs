@@ -16,4 +16,42 @@ pub enum AccountStatus {
     #[sea_orm(string_value = "PERM_BANNED")]
     #[serde(rename = "PERM_BANNED")]
     PermBanned,
+    /// 휴면계정: idle for a year, reactivated by the next login.
+    #[sea_orm(string_value = "DORMANT")]
+    #[serde(rename = "DORMANT")]
+    Dormant,
+}
+
+impl AccountStatus {
+    /// Statuses an account may move to `self` from. Everything else is an
+    /// illegal transition and is rejected by the repository.
+    pub fn allowed_sources(&self) -> &'static [AccountStatus] {
+        use AccountStatus::*;
+        match self {
+            Active => &[Pending, Banned, Dormant],
+            Pending => &[],
+            Banned => &[Active],
+            PermBanned => &[Active, Pending, Banned, Dormant],
+            Dormant => &[Active],
+        }
+    }
+
+    pub fn can_transition_to(&self, to: &AccountStatus) -> bool {
+        to.allowed_sources().contains(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum UserRole {
+    #[sea_orm(string_value = "USER")]
+    #[serde(rename = "USER")]
+    User,
+    #[sea_orm(string_value = "ADMIN")]
+    #[serde(rename = "ADMIN")]
+    Admin,
+    /// Warehouse and delivery staff; may read the addresses they are assigned.
+    #[sea_orm(string_value = "FULFILLMENT")]
+    #[serde(rename = "FULFILLMENT")]
+    Fulfillment,
 }
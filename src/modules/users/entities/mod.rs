@@ -1,5 +1,10 @@
+pub mod device;
 pub mod enums;
+pub mod notification_setting;
+pub mod referral;
+pub mod referral_reward;
 pub mod social;
+pub mod status_transition;
 pub mod user;
 
 pub mod verification;
@@ -0,0 +1,43 @@
+use crate::modules::users::entities::user;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Per-user notification switches. Users without a row get the defaults
+/// from `NotificationSettings::default()`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user_notification_settings")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub user_id: i32,
+    pub push_enabled: bool,
+    pub sms_enabled: bool,
+    pub email_enabled: bool,
+    pub alimtalk_enabled: bool,
+    pub order_status_enabled: bool,
+    /// 야간 광고성 정보 수신 동의: marketing during quiet hours. Whether
+    /// marketing may be sent at all is the `MARKETING_*` terms consents.
+    pub marketing_night_opt_in: bool,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
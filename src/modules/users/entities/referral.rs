@@ -0,0 +1,73 @@
+use crate::modules::users::entities::user;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReferralStatus {
+    /// Attributed; waiting for the invitee's first order.
+    #[sea_orm(string_value = "PENDING")]
+    #[serde(rename = "PENDING")]
+    Pending,
+    /// First order completed and both rewards granted.
+    #[sea_orm(string_value = "CONVERTED")]
+    #[serde(rename = "CONVERTED")]
+    Converted,
+    #[sea_orm(string_value = "REJECTED")]
+    #[serde(rename = "REJECTED")]
+    Rejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum ReferralRejection {
+    /// The invitee signed in on a device the referrer has used.
+    #[sea_orm(string_value = "SAME_DEVICE")]
+    #[serde(rename = "SAME_DEVICE")]
+    SameDevice,
+    #[sea_orm(string_value = "SAME_PHONE")]
+    #[serde(rename = "SAME_PHONE")]
+    SamePhone,
+}
+
+/// One edge of the referral tree: every user has at most one referrer.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "referrals")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub referrer_id: i32,
+    #[sea_orm(unique)]
+    pub referee_id: i32,
+    /// The code as entered, kept for support.
+    pub code: String,
+    pub status: ReferralStatus,
+    pub rejection_reason: Option<ReferralRejection>,
+    /// A device the invitee shared with the referrer at attribution, kept
+    /// as evidence for `SAME_DEVICE` rejections.
+    pub device_id: Option<String>,
+    pub created_at: DateTime,
+    pub converted_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::RefereeId",
+        to = "user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Referee,
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::ReferrerId",
+        to = "user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Referrer,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
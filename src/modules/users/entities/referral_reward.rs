@@ -0,0 +1,62 @@
+use crate::modules::users::entities::{referral, user};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum RewardRole {
+    #[sea_orm(string_value = "REFERRER")]
+    #[serde(rename = "REFERRER")]
+    Referrer,
+    #[sea_orm(string_value = "REFEREE")]
+    #[serde(rename = "REFEREE")]
+    Referee,
+}
+
+/// Points granted for a converted referral, one row per party.
+/// Payout to a wallet reads from here.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "referral_rewards")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub referral_id: i32,
+    pub user_id: i32,
+    pub role: RewardRole,
+    pub points: i32,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "referral::Entity",
+        from = "Column::ReferralId",
+        to = "referral::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Referral,
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<referral::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Referral.def()
+    }
+}
+
+impl Related<user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
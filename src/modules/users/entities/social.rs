@@ -14,6 +14,20 @@ pub enum SocialProvider {
     Apple,
 }
 
+impl std::str::FromStr for SocialProvider {
+    type Err = String;
+
+    /// Accepts the stored form ("KAKAO") in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "KAKAO" => Ok(Self::Kakao),
+            "GOOGLE" => Ok(Self::Google),
+            "APPLE" => Ok(Self::Apple),
+            _ => Err(format!("Unknown social provider: {}", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "user_socials")]
 pub struct Model {
@@ -0,0 +1,54 @@
+use crate::modules::users::entities::{enums::AccountStatus, user};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum StatusActorType {
+    /// Scheduled jobs and automatic rules (dormancy, ban expiry).
+    #[sea_orm(string_value = "SYSTEM")]
+    #[serde(rename = "SYSTEM")]
+    System,
+    #[sea_orm(string_value = "USER")]
+    #[serde(rename = "USER")]
+    User,
+    #[sea_orm(string_value = "ADMIN")]
+    #[serde(rename = "ADMIN")]
+    Admin,
+}
+
+/// Append-only audit trail of account status changes.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "account_status_transitions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub from_status: AccountStatus,
+    pub to_status: AccountStatus,
+    pub actor_type: StatusActorType,
+    /// The acting user or admin; `None` for the system.
+    pub actor_id: Option<i32>,
+    pub reason: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
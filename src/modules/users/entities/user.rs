@@ -9,18 +9,45 @@ pub struct Model {
     #[serde(skip_serializing)]
     pub id: i32,
     #[sea_orm(unique, index)]
-    pub uuid: String, // Decimal string representation of UUIDv4
+    pub uuid: String, // Hyphenated UUIDv7
+    /// Pre-UUIDv7 SHA-512 identifier, still accepted for lookups.
+    #[sea_orm(unique, nullable)]
+    #[serde(skip_serializing)]
+    pub legacy_uuid: Option<String>,
     pub username: String,
+    /// Unique `@handle`, stored canonical (Latin lowercased, no `@`).
+    #[sea_orm(unique, nullable)]
+    pub handle: Option<String>,
+    pub handle_changed_at: Option<DateTime>,
+    /// Shareable invite code, assigned at sign-up or on first request.
+    #[sea_orm(unique, nullable)]
+    pub referral_code: Option<String>,
+    /// Storage keys of the processed avatar and its thumbnail.
+    pub avatar_key: Option<String>,
+    pub avatar_thumbnail_key: Option<String>,
+    pub avatar_updated_at: Option<DateTime>,
     pub email: String,
     pub country_code: String,
     pub phone_number: String,
+    pub locale: String,
 
     pub account_status: super::enums::AccountStatus,
+    pub role: super::enums::UserRole,
     #[serde(skip_deserializing)]
     pub created_at: DateTime,
     #[serde(skip_deserializing)]
     pub updated_at: DateTime,
     pub last_login_at: Option<DateTime>,
+    pub login_count: i32,
+    pub last_login_provider: Option<super::social::SocialProvider>,
+    /// Set while the account is dormant (휴면), cleared on reactivation.
+    pub dormant_at: Option<DateTime>,
+    /// End of a temporary ban; the account becomes active again after it.
+    pub banned_until: Option<DateTime>,
+    /// Age bracket and birth year as last reported by the OAuth provider.
+    pub age_range: Option<String>,
+    pub birth_year: Option<i32>,
+    pub age_updated_at: Option<DateTime>,
 
     #[sea_orm(ignore)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -29,10 +56,6 @@ pub struct Model {
     #[sea_orm(ignore)]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub socials: Vec<super::social::Model>,
-
-    #[sea_orm(ignore)]
-    #[serde(skip_serializing)]
-    pub delivery: Vec<crate::modules::delivery::entities::delivery_data::Model>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -41,8 +64,6 @@ pub enum Relation {
     UserVerification,
     #[sea_orm(has_many = "super::social::Entity")]
     UserSocials,
-    #[sea_orm(has_many = "crate::modules::delivery::entities::delivery_data::Entity")]
-    UserDeliveryData,
 }
 
 impl Related<verification::Entity> for Entity {
@@ -57,10 +78,4 @@ impl Related<super::social::Entity> for Entity {
     }
 }
 
-impl Related<crate::modules::delivery::entities::delivery_data::Entity> for Entity {
-    fn to() -> RelationDef {
-        Relation::UserDeliveryData.def()
-    }
-}
-
 impl ActiveModelBehavior for ActiveModel {}
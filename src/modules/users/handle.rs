@@ -0,0 +1,220 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::SqlErr;
+use serde::Serialize;
+
+use super::entities::user;
+use super::repository::UserRepository;
+use crate::shared::error::{AppError, AppResult};
+
+pub const HANDLE_MIN_CHARS: usize = 2;
+pub const HANDLE_MAX_CHARS: usize = 20;
+/// A handle may be changed once per this many days; the first one is free.
+pub const HANDLE_CHANGE_COOLDOWN_DAYS: i64 = 30;
+/// Compared after dropping `.` and `_`, so `ad_min` is reserved too.
+pub const RESERVED_HANDLES: &[&str] = &["admin", "gimme", "support"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HandleUnavailableReason {
+    Invalid,
+    Reserved,
+    Taken,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HandleAvailability {
+    pub handle: String,
+    pub available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<HandleUnavailableReason>,
+}
+
+pub struct HandleService;
+
+impl HandleService {
+    /// Canonical form of a handle: leading `@` and surrounding whitespace
+    /// removed, Latin letters lowercased. Allowed characters are Hangul
+    /// syllables, Latin letters, digits, `_` and `.`; at least one letter is
+    /// required and dots may not lead, trail or repeat.
+    pub fn normalize(raw: &str) -> AppResult<String> {
+        let handle = raw.trim().trim_start_matches('@').to_lowercase();
+        let len = handle.chars().count();
+        let invalid = |msg: &str| Err(AppError::BadRequest(msg.to_string()));
+
+        if !(HANDLE_MIN_CHARS..=HANDLE_MAX_CHARS).contains(&len) {
+            return Err(AppError::BadRequest(format!(
+                "Handle must be {}-{} characters",
+                HANDLE_MIN_CHARS, HANDLE_MAX_CHARS
+            )));
+        }
+        if !handle.chars().all(|c| {
+            Self::is_hangul(c)
+                || c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || c == '_'
+                || c == '.'
+        }) {
+            return invalid("Handle may only contain Korean, Latin letters, digits, '_' and '.'");
+        }
+        if !handle
+            .chars()
+            .any(|c| Self::is_hangul(c) || c.is_ascii_lowercase())
+        {
+            return invalid("Handle must contain at least one letter");
+        }
+        if handle.starts_with('.') || handle.ends_with('.') || handle.contains("..") {
+            return invalid("Handle may not start or end with '.' or repeat it");
+        }
+
+        Ok(handle)
+    }
+
+    pub fn is_reserved(handle: &str) -> bool {
+        let bare: String = handle.chars().filter(|c| !matches!(c, '.' | '_')).collect();
+        RESERVED_HANDLES.contains(&bare.as_str())
+    }
+
+    fn is_hangul(c: char) -> bool {
+        ('가'..='힣').contains(&c)
+    }
+
+    /// Availability as seen by `requester_id`; a user's own handle counts as available.
+    pub async fn check_availability(
+        repo: &dyn UserRepository,
+        raw: &str,
+        requester_id: Option<i32>,
+    ) -> AppResult<HandleAvailability> {
+        let (handle, reason) = match Self::normalize(raw) {
+            Err(_) => (
+                raw.trim().to_string(),
+                Some(HandleUnavailableReason::Invalid),
+            ),
+            Ok(handle) if Self::is_reserved(&handle) => {
+                (handle, Some(HandleUnavailableReason::Reserved))
+            }
+            Ok(handle) => {
+                let taken = repo
+                    .find_by_handle(&handle)
+                    .await?
+                    .is_some_and(|owner| Some(owner.id) != requester_id);
+                (handle, taken.then_some(HandleUnavailableReason::Taken))
+            }
+        };
+
+        Ok(HandleAvailability {
+            handle,
+            available: reason.is_none(),
+            reason,
+        })
+    }
+
+    /// Sets or changes the user's handle, at most once per cooldown period.
+    pub async fn change(
+        repo: &dyn UserRepository,
+        uuid: &str,
+        raw: &str,
+    ) -> AppResult<user::Model> {
+        let user = repo
+            .find_with_details_by_uuid(uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let handle = Self::normalize(raw)?;
+        if user.handle.as_deref() == Some(handle.as_str()) {
+            return Ok(user);
+        }
+        if Self::is_reserved(&handle) {
+            return Err(AppError::BadRequest("Handle is reserved".to_string()));
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        if let Some(changed_at) = user.handle_changed_at {
+            let next_allowed = changed_at + chrono::Duration::days(HANDLE_CHANGE_COOLDOWN_DAYS);
+            if now < next_allowed {
+                return Err(AppError::TooManyRequests(format!(
+                    "Handle can be changed again after {}",
+                    next_allowed.format("%Y-%m-%d %H:%M UTC")
+                )));
+            }
+        }
+        if repo.find_by_handle(&handle).await?.is_some() {
+            return Err(AppError::Conflict("Handle already taken".to_string()));
+        }
+
+        let user_active = user::ActiveModel {
+            id: Unchanged(user.id),
+            handle: Set(Some(handle)),
+            handle_changed_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let mut updated = match repo.update_user(user_active).await {
+            Ok(u) => u,
+            // Lost a race with another user claiming the same handle.
+            Err(AppError::DbError(e))
+                if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+            {
+                return Err(AppError::Conflict("Handle already taken".to_string()));
+            }
+            Err(e) => return Err(e),
+        };
+        updated.verification = user.verification;
+        updated.socials = user.socials;
+
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::infra::fixtures::sign_up;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+
+    #[test]
+    fn test_normalize_handle() {
+        assert_eq!(HandleService::normalize("@Gimme.Kim").unwrap(), "gimme.kim");
+        assert_eq!(HandleService::normalize("김기미_01").unwrap(), "김기미_01");
+        assert!(HandleService::normalize("a").is_err());
+        assert!(HandleService::normalize("12345").is_err());
+        assert!(HandleService::normalize(".gimme").is_err());
+        assert!(HandleService::normalize("gim..me").is_err());
+        assert!(HandleService::normalize("gimme!").is_err());
+        assert!(HandleService::normalize("ㄱㅣㅁ").is_err());
+
+        assert!(HandleService::is_reserved("admin"));
+        assert!(HandleService::is_reserved("sup_port"));
+        assert!(!HandleService::is_reserved("gimme_kim"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_is_unique_case_insensitively_and_rate_limited() {
+        let repo = InMemoryUserRepository::default();
+        let first = sign_up(&repo, "1").await;
+        let second = sign_up(&repo, "2").await;
+
+        let updated = HandleService::change(&repo, &first.uuid, "@GimmeKim")
+            .await
+            .unwrap();
+        assert_eq!(updated.handle.as_deref(), Some("gimmekim"));
+
+        let taken = HandleService::check_availability(&repo, "gimmeKIM", Some(second.id))
+            .await
+            .unwrap();
+        assert_eq!(taken.reason, Some(HandleUnavailableReason::Taken));
+        let own = HandleService::check_availability(&repo, "gimmekim", Some(first.id))
+            .await
+            .unwrap();
+        assert!(own.available);
+
+        assert!(matches!(
+            HandleService::change(&repo, &second.uuid, "GIMMEKIM").await,
+            Err(AppError::Conflict(_))
+        ));
+        assert!(matches!(
+            HandleService::change(&repo, &first.uuid, "another").await,
+            Err(AppError::TooManyRequests(_))
+        ));
+    }
+}
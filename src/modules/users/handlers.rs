@@ -1,34 +1,136 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Multipart, Path, State},
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::modules::users::entities::enums::AccountStatus;
+use crate::modules::moderation::repository::ModerationRepository;
+use crate::modules::moderation::service::BlockService;
+use crate::modules::terms::repository::TermsRepository;
+use crate::modules::users::avatar::{ALLOWED_CONTENT_TYPES, AvatarService};
+use crate::modules::users::dtos::UpdateProfileDto;
+use crate::modules::users::entities::enums::{AccountStatus, UserRole};
 use crate::modules::users::entities::user;
+use crate::modules::users::handle::{HandleAvailability, HandleService};
+use crate::modules::users::notification::{
+    NotificationService, NotificationSettings, UpdateNotificationSettingsDto,
+};
+use crate::modules::users::referral::{ReferralService, ReferralSummary};
 use crate::modules::users::repository::UserRepository;
+use crate::modules::users::service::UserService;
 use crate::shared::{
     error::{AppError, AppResult},
     state::AppState,
+    storage::FileStorage,
 };
 use std::sync::Arc;
+
+/// Which parts of a user a requester may see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserProjection {
+    /// Username, avatar and join date only.
+    Public,
+    /// Everything, including contact data and verification state.
+    Private,
+}
+
+impl UserProjection {
+    /// Owners and admins get the private projection, everyone else the public one.
+    pub fn for_requester(requester: Option<&user::Model>, target: &user::Model) -> Self {
+        match requester {
+            Some(r) if r.id == target.id || r.role == UserRole::Admin => Self::Private,
+            _ => Self::Public,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct UserResponse {
-    #[serde(skip_serializing)]
-    pub id: i32,
     pub uuid: String,
     pub username: String,
+    pub handle: Option<String>,
+    /// `null` when no avatar is set; clients render a placeholder.
+    pub avatar_url: Option<String>,
+    pub avatar_thumbnail_url: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    #[serde(flatten)]
+    pub private: Option<UserPrivateResponse>,
+}
+
+#[derive(Serialize)]
+pub struct UserPrivateResponse {
     pub email: String,
     pub country_code: String,
     pub phone_number: String,
+    pub locale: String,
     pub account_status: AccountStatus,
-    pub created_at: chrono::NaiveDateTime,
+    pub role: UserRole,
     pub updated_at: chrono::NaiveDateTime,
     pub last_login_at: Option<chrono::NaiveDateTime>,
     pub verification: Option<UserVerificationResponse>,
     pub social_accounts: Vec<UserSocialResponse>,
 }
 
+impl UserResponse {
+    /// Builds the response from a user loaded with its verification and socials.
+    /// `storage` resolves avatar keys to URLs.
+    pub fn project(
+        user: user::Model,
+        projection: UserProjection,
+        storage: &dyn FileStorage,
+    ) -> Self {
+        let private = (projection == UserProjection::Private).then(|| {
+            let verification = user
+                .verification
+                .as_ref()
+                .map(|v| UserVerificationResponse {
+                    email_verified: v.email_verified,
+                    email_verified_at: v.email_verified_at,
+                    phone_verified: v.phone_verified,
+                    phone_verified_at: v.phone_verified_at,
+                    business_verified: v.business_verified,
+                    business_info: v.business_info.clone(),
+                });
+
+            let social_accounts = user
+                .socials
+                .iter()
+                .map(|s| UserSocialResponse {
+                    provider: format!("{:?}", s.provider),
+                    provider_id: s.provider_id.clone(),
+                    created_at: s.created_at,
+                })
+                .collect();
+
+            UserPrivateResponse {
+                email: user.email.clone(),
+                country_code: user.country_code.clone(),
+                phone_number: user.phone_number.clone(),
+                locale: user.locale.clone(),
+                account_status: user.account_status.clone(),
+                role: user.role.clone(),
+                updated_at: user.updated_at,
+                last_login_at: user.last_login_at,
+                verification,
+                social_accounts,
+            }
+        });
+
+        Self {
+            uuid: user.uuid,
+            username: user.username,
+            handle: user.handle,
+            avatar_url: user.avatar_key.as_deref().map(|k| storage.public_url(k)),
+            avatar_thumbnail_url: user
+                .avatar_thumbnail_key
+                .as_deref()
+                .map(|k| storage.public_url(k)),
+            created_at: user.created_at,
+            private,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct UserVerificationResponse {
     pub email_verified: bool,
@@ -48,77 +150,454 @@ pub struct UserSocialResponse {
 
 pub async fn get_user(
     State(state): State<AppState>,
-    Path(id): Path<i32>,
+    claims: Option<crate::modules::auth::service::Claims>,
+    Path(uuid): Path<String>,
 ) -> AppResult<Json<UserResponse>> {
     let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
         AppError::InternalServerError("UserRepository not registered".to_string()),
     )?;
 
-    let user: user::Model = user_repo.find_by_id(id).await?.ok_or(AppError::NotFound)?;
-
-    Ok(Json(UserResponse {
-        id: user.id,
-        uuid: user.uuid,
-        username: user.username,
-        email: user.email,
-        country_code: user.country_code,
-        phone_number: user.phone_number,
-        account_status: user.account_status,
-        created_at: user.created_at,
-        updated_at: user.updated_at,
-        last_login_at: user.last_login_at,
-        verification: None, // TODO: Fetch verification if needed for public profile or admin
-        social_accounts: vec![], // TODO: Fetch socials if needed
-    }))
-}
-
-pub async fn get_me(
+    let requester = match &claims {
+        Some(c) => user_repo.find_by_uuid(&c.sub).await?,
+        None => None,
+    };
+
+    let user = user_repo
+        .find_with_details_by_uuid(&uuid)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let projection = UserProjection::for_requester(requester.as_ref(), &user);
+    // Only active accounts have a public profile.
+    if projection == UserProjection::Public && user.account_status != AccountStatus::Active {
+        return Err(AppError::NotFound);
+    }
+    // Blocked users look like they do not exist to each other.
+    if projection == UserProjection::Public
+        && let Some(requester) = &requester
+    {
+        let moderation_repo = state
+            .repo_manager
+            .get::<Arc<dyn ModerationRepository>>()
+            .ok_or(AppError::InternalServerError(
+                "ModerationRepository not registered".to_string(),
+            ))?;
+        if BlockService::is_blocked(moderation_repo.as_ref(), requester.id, user.id).await? {
+            return Err(AppError::NotFound);
+        }
+    }
+
+    Ok(Json(UserResponse::project(
+        user,
+        projection,
+        state.storage.as_ref(),
+    )))
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMeRequest {
+    pub username: Option<String>,
+    pub country_code: Option<String>,
+    pub locale: Option<String>,
+    pub phone_number: Option<String>,
+}
+
+pub async fn update_me(
     State(state): State<AppState>,
     claims: crate::modules::auth::service::Claims,
+    Json(body): Json<UpdateMeRequest>,
 ) -> AppResult<Json<UserResponse>> {
     let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
         AppError::InternalServerError("UserRepository not registered".to_string()),
     )?;
 
+    let user = UserService::update_profile(
+        user_repo.as_ref(),
+        state.repo_manager.as_ref(),
+        &claims.sub,
+        UpdateProfileDto {
+            username: body.username,
+            country_code: body.country_code,
+            locale: body.locale,
+            phone_number: body.phone_number,
+        },
+    )
+    .await?;
+
+    Ok(Json(UserResponse::project(
+        user,
+        UserProjection::Private,
+        state.storage.as_ref(),
+    )))
+}
+
+pub async fn check_handle_availability(
+    State(state): State<AppState>,
+    claims: Option<crate::modules::auth::service::Claims>,
+    Path(handle): Path<String>,
+) -> AppResult<Json<HandleAvailability>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let requester = match &claims {
+        Some(c) => user_repo.find_by_uuid(&c.sub).await?,
+        None => None,
+    };
+
+    let availability =
+        HandleService::check_availability(user_repo.as_ref(), &handle, requester.map(|u| u.id))
+            .await?;
+
+    Ok(Json(availability))
+}
+
+#[derive(Deserialize)]
+pub struct UpdateHandleRequest {
+    pub handle: String,
+}
+
+pub async fn update_my_handle(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<UpdateHandleRequest>,
+) -> AppResult<Json<UserResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let user = HandleService::change(user_repo.as_ref(), &claims.sub, &body.handle).await?;
+
+    Ok(Json(UserResponse::project(
+        user,
+        UserProjection::Private,
+        state.storage.as_ref(),
+    )))
+}
+
+/// Multipart upload with the image in a `file` part.
+pub async fn upload_my_avatar(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    mut multipart: Multipart,
+) -> AppResult<Json<UserResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let multipart_error = |e: axum::extract::multipart::MultipartError| {
+        if e.status() == axum::http::StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::PayloadTooLarge(e.body_text())
+        } else {
+            AppError::BadRequest(e.body_text())
+        }
+    };
+    let mut bytes = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        // The declared type is only a first filter; the bytes are sniffed later.
+        if let Some(content_type) = field.content_type()
+            && !ALLOWED_CONTENT_TYPES.contains(&content_type)
+        {
+            return Err(AppError::UnsupportedMediaType(format!(
+                "Unsupported content type: {}",
+                content_type
+            )));
+        }
+        bytes = Some(field.bytes().await.map_err(multipart_error)?);
+        break;
+    }
+    let bytes = bytes.ok_or(AppError::BadRequest("Missing file part".to_string()))?;
+
+    let user = AvatarService::upload(
+        user_repo.as_ref(),
+        state.storage.as_ref(),
+        &claims.sub,
+        bytes.to_vec(),
+    )
+    .await?;
+
+    Ok(Json(UserResponse::project(
+        user,
+        UserProjection::Private,
+        state.storage.as_ref(),
+    )))
+}
+
+pub async fn delete_my_avatar(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<UserResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    let user =
+        AvatarService::remove(user_repo.as_ref(), state.storage.as_ref(), &claims.sub).await?;
+
+    Ok(Json(UserResponse::project(
+        user,
+        UserProjection::Private,
+        state.storage.as_ref(),
+    )))
+}
+
+#[derive(Serialize)]
+pub struct NotificationChannelsResponse {
+    pub push: bool,
+    pub sms: bool,
+    pub email: bool,
+    pub alimtalk: bool,
+}
+
+#[derive(Serialize)]
+pub struct NotificationSettingsResponse {
+    pub channels: NotificationChannelsResponse,
+    pub categories: NotificationCategoriesResponse,
+    pub marketing_night_opt_in: bool,
+    pub marketing_quiet_hours: QuietHoursResponse,
+}
+
+/// From the `MARKETING_*` terms consents; change them under `/terms/consents`.
+#[derive(Serialize)]
+pub struct MarketingConsentResponse {
+    pub push: bool,
+    pub sms: bool,
+    pub email: bool,
+}
+
+#[derive(Serialize)]
+pub struct NotificationCategoriesResponse {
+    pub order_status: bool,
+    pub marketing: MarketingConsentResponse,
+    /// Always on; listed so clients can render it as locked.
+    pub security: bool,
+}
+
+#[derive(Serialize)]
+pub struct QuietHoursResponse {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+    pub timezone: &'static str,
+}
+
+impl From<NotificationSettings> for NotificationSettingsResponse {
+    fn from(s: NotificationSettings) -> Self {
+        let (start, end) = NotificationSettings::quiet_hours();
+        Self {
+            channels: NotificationChannelsResponse {
+                push: s.push,
+                sms: s.sms,
+                email: s.email,
+                alimtalk: s.alimtalk,
+            },
+            categories: NotificationCategoriesResponse {
+                order_status: s.order_status,
+                marketing: MarketingConsentResponse {
+                    push: s.marketing.push,
+                    sms: s.marketing.sms,
+                    email: s.marketing.email,
+                },
+                security: true,
+            },
+            marketing_night_opt_in: s.marketing_night_opt_in,
+            marketing_quiet_hours: QuietHoursResponse {
+                start,
+                end,
+                timezone: "Asia/Seoul",
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct UpdateNotificationChannelsRequest {
+    pub push: Option<bool>,
+    pub sms: Option<bool>,
+    pub email: Option<bool>,
+    pub alimtalk: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct UpdateNotificationCategoriesRequest {
+    pub order_status: Option<bool>,
+    /// Rejected: marketing consent is given per channel under terms.
+    pub marketing: Option<bool>,
+    pub security: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateNotificationSettingsRequest {
+    pub channels: Option<UpdateNotificationChannelsRequest>,
+    pub categories: Option<UpdateNotificationCategoriesRequest>,
+    pub marketing_night_opt_in: Option<bool>,
+}
+
+pub async fn get_my_referral(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<ReferralSummary>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = user_repo
+        .find_by_uuid(&claims.sub)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(
+        ReferralService::summary(user_repo.as_ref(), &user).await?,
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct RedeemReferralRequest {
+    pub code: String,
+}
+
+/// Applies an invite code during onboarding. A suspected self-referral is
+/// accepted as `REJECTED` rather than failing, so it cannot be probed.
+pub async fn redeem_referral(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<RedeemReferralRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+
+    ReferralService::redeem(user_repo.as_ref(), &claims.sub, &body.code).await?;
+    Ok(Json(serde_json::json!({ "applied": true })))
+}
+
+pub async fn get_my_notification_settings(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+) -> AppResult<Json<NotificationSettingsResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let terms_repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
     let user = user_repo
-        .find_with_details_by_uuid(&claims.sub)
+        .find_by_uuid(&claims.sub)
         .await?
         .ok_or(AppError::NotFound)?;
 
-    let verification_response = user
-        .verification
-        .as_ref()
-        .map(|v| UserVerificationResponse {
-            email_verified: v.email_verified,
-            email_verified_at: v.email_verified_at,
-            phone_verified: v.phone_verified,
-            phone_verified_at: v.phone_verified_at,
-            business_verified: v.business_verified,
-            business_info: v.business_info.clone(),
-        });
+    let settings =
+        NotificationService::settings_for(user_repo.as_ref(), terms_repo.as_ref(), user.id).await?;
+    Ok(Json(settings.into()))
+}
+
+pub async fn update_my_notification_settings(
+    State(state): State<AppState>,
+    claims: crate::modules::auth::service::Claims,
+    Json(body): Json<UpdateNotificationSettingsRequest>,
+) -> AppResult<Json<NotificationSettingsResponse>> {
+    let user_repo = state.repo_manager.get::<Arc<dyn UserRepository>>().ok_or(
+        AppError::InternalServerError("UserRepository not registered".to_string()),
+    )?;
+    let user = user_repo
+        .find_by_uuid(&claims.sub)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let categories = body.categories.unwrap_or_default();
+    if categories.security == Some(false) {
+        return Err(AppError::BadRequest(
+            "Security notifications cannot be disabled".to_string(),
+        ));
+    }
+    if categories.marketing.is_some() {
+        return Err(AppError::BadRequest(
+            "Marketing consent is given per channel under /terms/consents".to_string(),
+        ));
+    }
+    let mut dto = UpdateNotificationSettingsDto {
+        order_status: categories.order_status,
+        marketing_night_opt_in: body.marketing_night_opt_in,
+        ..Default::default()
+    };
+    if let Some(channels) = body.channels {
+        dto.push = channels.push;
+        dto.sms = channels.sms;
+        dto.email = channels.email;
+        dto.alimtalk = channels.alimtalk;
+    }
+
+    let terms_repo = state.repo_manager.get::<Arc<dyn TermsRepository>>().ok_or(
+        AppError::InternalServerError("TermsRepository not registered".to_string()),
+    )?;
+    let settings =
+        NotificationService::update(user_repo.as_ref(), terms_repo.as_ref(), user.id, dto).await?;
+    Ok(Json(settings.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::dtos::SocialLoginDto;
+    use crate::modules::users::infra::fixtures::{kakao_login, login_with};
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::shared::storage::LocalFileStorage;
+
+    async fn create_user(repo: &InMemoryUserRepository, provider_id: &str) -> user::Model {
+        login_with(
+            repo,
+            SocialLoginDto {
+                phone_number: Some("010-1234-5678".to_string()),
+                ..kakao_login(provider_id)
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_projection_follows_requester_role() {
+        let repo = InMemoryUserRepository::default();
+        let target = create_user(&repo, "target").await;
+        let stranger = create_user(&repo, "stranger").await;
+        let mut admin = create_user(&repo, "admin").await;
+        admin.role = UserRole::Admin;
+
+        assert_eq!(
+            UserProjection::for_requester(None, &target),
+            UserProjection::Public
+        );
+        assert_eq!(
+            UserProjection::for_requester(Some(&stranger), &target),
+            UserProjection::Public
+        );
+        assert_eq!(
+            UserProjection::for_requester(Some(&target), &target),
+            UserProjection::Private
+        );
+        assert_eq!(
+            UserProjection::for_requester(Some(&admin), &target),
+            UserProjection::Private
+        );
+
+        let storage = LocalFileStorage::new("storage", "https://gimme.com/files");
+        let public = serde_json::to_value(UserResponse::project(
+            target.clone(),
+            UserProjection::Public,
+            &storage,
+        ))
+        .unwrap();
+        assert_eq!(public["username"], "target");
+        assert!(public.get("email").is_none());
+        assert!(public.get("phone_number").is_none());
 
-    let social_responses = user
-        .socials
-        .iter()
-        .map(|s| UserSocialResponse {
-            provider: format!("{:?}", s.provider),
-            provider_id: s.provider_id.clone(),
-            created_at: s.created_at,
-        })
-        .collect();
-
-    Ok(Json(UserResponse {
-        id: user.id,
-        uuid: user.uuid,
-        username: user.username,
-        email: user.email,
-        country_code: user.country_code,
-        phone_number: user.phone_number,
-        account_status: user.account_status,
-        created_at: user.created_at,
-        updated_at: user.updated_at,
-        last_login_at: user.last_login_at,
-        verification: verification_response,
-        social_accounts: social_responses,
-    }))
+        let private = serde_json::to_value(UserResponse::project(
+            target,
+            UserProjection::Private,
+            &storage,
+        ))
+        .unwrap();
+        assert_eq!(private["email"], "target@gimme.com");
+        assert_eq!(private["phone_number"], "+821012345678");
+    }
 }
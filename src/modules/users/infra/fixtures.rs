@@ -0,0 +1,82 @@
+//! Test users shared across modules. Stored users go through the real
+//! social login so they get the same rows and defaults as production.
+
+use crate::modules::users::dtos::SocialLoginDto;
+use crate::modules::users::entities::{
+    enums::{AccountStatus, UserRole},
+    social::SocialProvider,
+    user,
+};
+use crate::modules::users::repository::UserRepository;
+use crate::modules::users::service::UserService;
+use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+/// A Kakao login for `provider_id`, named after it and using
+/// `<provider_id>@gimme.com`. Override fields with struct update syntax.
+pub fn kakao_login(provider_id: &str) -> SocialLoginDto {
+    SocialLoginDto {
+        provider: SocialProvider::Kakao,
+        provider_id: provider_id.to_string(),
+        email: Some(format!("{}@gimme.com", provider_id)),
+        name: Some(provider_id.to_string()),
+        phone_number: None,
+        connected_at: Some("2024-01-29T00:00:00Z".to_string()),
+        age_range: None,
+        birthyear: None,
+        device_id: None,
+        referral_code: None,
+    }
+}
+
+/// Runs `login` through `UserService::handle_social_login` and returns the
+/// user with verification and socials loaded.
+pub async fn login_with(repo: &dyn UserRepository, login: SocialLoginDto) -> user::Model {
+    let user = UserService::handle_social_login(repo, &InMemoryRepositoryManager::new(), login)
+        .await
+        .unwrap()
+        .user;
+    repo.find_with_details_by_uuid(&user.uuid)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// Signs up a new `Pending` user with `kakao_login(provider_id)`.
+pub async fn sign_up(repo: &dyn UserRepository, provider_id: &str) -> user::Model {
+    login_with(repo, kakao_login(provider_id)).await
+}
+
+/// An active user that is never stored, for code that only reads the model.
+pub fn detached_user(id: i32, role: UserRole) -> user::Model {
+    let now = chrono::Utc::now().naive_utc();
+    user::Model {
+        id,
+        uuid: format!("uuid-{}", id),
+        legacy_uuid: None,
+        username: "gimme".to_string(),
+        handle: None,
+        handle_changed_at: None,
+        referral_code: None,
+        avatar_key: None,
+        avatar_thumbnail_key: None,
+        avatar_updated_at: None,
+        email: "".to_string(),
+        country_code: "".to_string(),
+        phone_number: "".to_string(),
+        locale: "ko-KR".to_string(),
+        account_status: AccountStatus::Active,
+        role,
+        created_at: now,
+        updated_at: now,
+        last_login_at: None,
+        login_count: 0,
+        last_login_provider: None,
+        dormant_at: None,
+        banned_until: None,
+        age_range: None,
+        birth_year: None,
+        age_updated_at: None,
+        verification: None,
+        socials: vec![],
+    }
+}
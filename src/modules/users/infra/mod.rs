@@ -1 +1,3 @@
+#[cfg(test)]
+pub mod fixtures;
 pub mod persistence;
@@ -1,11 +1,22 @@
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func, Query};
 use sea_orm::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use crate::impl_sea_orm_repo;
-use crate::modules::users::entities::{social, user, verification};
+use crate::modules::users::dtos::{
+    SortDirection, UserCursor, UserPage, UserSearchQuery, UserSortField,
+};
+use crate::modules::users::entities::{
+    device,
+    enums::AccountStatus,
+    notification_setting, referral, referral_reward, social,
+    status_transition::{self, StatusActorType},
+    user, verification,
+};
 use crate::modules::users::repository::UserRepository;
+use crate::modules::users::utils::is_legacy_user_id;
 use crate::shared::error::{AppError, AppResult};
 use crate::shared::infra::repository::{DbOrTxn, SeaOrmRepository};
 use crate::shared::repository::UnitOfWork;
@@ -37,7 +48,7 @@ impl_sea_orm_repo!(PostgresUserRepository, UserRepository, {
     }
 
     async fn find_by_uuid(&self, uuid: &str) -> AppResult<Option<user::Model>> {
-        let query = user::Entity::find().filter(user::Column::Uuid.eq(uuid));
+        let query = user::Entity::find().filter(uuid_filter(uuid));
         match &self.conn {
             DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
             DbOrTxn::Txn(mutex) => {
@@ -60,6 +71,30 @@ impl_sea_orm_repo!(PostgresUserRepository, UserRepository, {
         }
     }
 
+    async fn find_by_handle(&self, handle: &str) -> AppResult<Option<user::Model>> {
+        let query = user::Entity::find().filter(user::Column::Handle.eq(handle));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<user::Model>> {
+        let query = user::Entity::find().filter(user::Column::ReferralCode.eq(code));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
     async fn find_social(
         &self,
         provider: social::SocialProvider,
@@ -105,13 +140,29 @@ impl_sea_orm_repo!(PostgresUserRepository, UserRepository, {
     }
 
     async fn update_user(&self, user: user::ActiveModel) -> AppResult<user::Model> {
-        match &self.conn {
-            DbOrTxn::Conn(c) => user.update(c.as_ref()).await.map_err(AppError::DbError),
+        let status_change = match &user.account_status {
+            Set(to) => Some(to.clone()),
+            _ => None,
+        };
+        let mut update = user::Entity::update(user)
+            .validate()
+            .map_err(AppError::DbError)?;
+        // Guarded in the WHERE clause so a concurrent change cannot slip past.
+        if let Some(to) = &status_change {
+            update = update
+                .filter(user::Column::AccountStatus.is_in(to.allowed_sources().iter().cloned()));
+        }
+        let result = match &self.conn {
+            DbOrTxn::Conn(c) => update.exec(c.as_ref()).await,
             DbOrTxn::Txn(mutex) => {
                 let lock = mutex.lock().await;
                 let txn = lock.as_ref().expect("Active txn");
-                user.update(txn).await.map_err(AppError::DbError)
+                update.exec(txn).await
             }
+        };
+        match (result, status_change) {
+            (Err(DbErr::RecordNotUpdated), Some(to)) => Err(illegal_transition(None, &to)),
+            (result, _) => result.map_err(AppError::DbError),
         }
     }
 
@@ -142,8 +193,357 @@ impl_sea_orm_repo!(PostgresUserRepository, UserRepository, {
             }
         }
     }
+
+    async fn record_login(
+        &self,
+        user_id: i32,
+        provider: social::SocialProvider,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<user::Model> {
+        let update = user::Entity::update_many()
+            .col_expr(user::Column::LastLoginAt, Expr::val(at))
+            .col_expr(
+                user::Column::LoginCount,
+                Expr::col(user::Column::LoginCount).add(1),
+            )
+            .col_expr(
+                user::Column::LastLoginProvider,
+                Expr::val(provider.to_value()),
+            )
+            .filter(user::Column::Id.eq(user_id));
+        let updated = match &self.conn {
+            DbOrTxn::Conn(c) => update.exec_with_returning(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                update.exec_with_returning(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+
+        updated.into_iter().next().ok_or(AppError::NotFound)
+    }
+
+    async fn mark_dormant(
+        &self,
+        idle_before: chrono::NaiveDateTime,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<u64> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => {
+                let txn = c.begin().await.map_err(AppError::DbError)?;
+                let affected = Self::mark_dormant_internal(&txn, idle_before, at).await?;
+                txn.commit().await.map_err(AppError::DbError)?;
+                Ok(affected)
+            }
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                Self::mark_dormant_internal(txn, idle_before, at).await
+            }
+        }
+    }
+
+    async fn find_notification_setting(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Option<notification_setting::Model>> {
+        let query = notification_setting::Entity::find()
+            .filter(notification_setting::Column::UserId.eq(user_id));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn save_notification_setting(
+        &self,
+        setting: notification_setting::ActiveModel,
+    ) -> AppResult<notification_setting::Model> {
+        let insert = notification_setting::Entity::insert(setting).on_conflict(
+            sea_query::OnConflict::column(notification_setting::Column::UserId)
+                .update_columns([
+                    notification_setting::Column::PushEnabled,
+                    notification_setting::Column::SmsEnabled,
+                    notification_setting::Column::EmailEnabled,
+                    notification_setting::Column::AlimtalkEnabled,
+                    notification_setting::Column::OrderStatusEnabled,
+                    notification_setting::Column::MarketingNightOptIn,
+                    notification_setting::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        );
+        match &self.conn {
+            DbOrTxn::Conn(c) => insert
+                .exec_with_returning(c.as_ref())
+                .await
+                .map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                insert
+                    .exec_with_returning(txn)
+                    .await
+                    .map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_status_transition(
+        &self,
+        transition: status_transition::ActiveModel,
+    ) -> AppResult<status_transition::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => transition
+                .insert(c.as_ref())
+                .await
+                .map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                transition.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_status_transitions(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<status_transition::Model>> {
+        let query = status_transition::Entity::find()
+            .filter(status_transition::Column::UserId.eq(user_id))
+            .order_by_asc(status_transition::Column::CreatedAt)
+            .order_by_asc(status_transition::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn record_device(
+        &self,
+        user_id: i32,
+        device_id: &str,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<()> {
+        let insert = device::Entity::insert(device::ActiveModel {
+            user_id: Set(user_id),
+            device_id: Set(device_id.to_string()),
+            first_seen_at: Set(at),
+            last_seen_at: Set(at),
+            ..Default::default()
+        })
+        .on_conflict(
+            sea_query::OnConflict::columns([device::Column::UserId, device::Column::DeviceId])
+                .update_column(device::Column::LastSeenAt)
+                .to_owned(),
+        );
+        match &self.conn {
+            DbOrTxn::Conn(c) => insert.exec_without_returning(c.as_ref()).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                insert.exec_without_returning(txn).await
+            }
+        }
+        .map_err(AppError::DbError)?;
+        Ok(())
+    }
+
+    async fn find_shared_device(
+        &self,
+        user_id: i32,
+        other_user_id: i32,
+    ) -> AppResult<Option<String>> {
+        let query = device::Entity::find()
+            .select_only()
+            .column(device::Column::DeviceId)
+            .filter(device::Column::UserId.eq(user_id))
+            .filter(
+                device::Column::DeviceId.in_subquery(
+                    Query::select()
+                        .column(device::Column::DeviceId)
+                        .from(device::Entity)
+                        .and_where(device::Column::UserId.eq(other_user_id))
+                        .to_owned(),
+                ),
+            )
+            .into_tuple::<String>();
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_referral_by_referee(
+        &self,
+        referee_id: i32,
+    ) -> AppResult<Option<referral::Model>> {
+        let query = referral::Entity::find().filter(referral::Column::RefereeId.eq(referee_id));
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.one(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.one(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_referrals_by_referrer(
+        &self,
+        referrer_id: i32,
+    ) -> AppResult<Vec<referral::Model>> {
+        let query = referral::Entity::find()
+            .filter(referral::Column::ReferrerId.eq(referrer_id))
+            .order_by_asc(referral::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_referrals_created_between(
+        &self,
+        from: chrono::NaiveDateTime,
+        to: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<referral::Model>> {
+        let query = referral::Entity::find()
+            .filter(referral::Column::CreatedAt.gte(from))
+            .filter(referral::Column::CreatedAt.lt(to))
+            .order_by_asc(referral::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => referral.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                referral.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn update_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => referral.update(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                referral.update(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn create_referral_reward(
+        &self,
+        reward: referral_reward::ActiveModel,
+    ) -> AppResult<referral_reward::Model> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => reward.insert(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                reward.insert(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn find_referral_rewards_by_user(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<referral_reward::Model>> {
+        let query = referral_reward::Entity::find()
+            .filter(referral_reward::Column::UserId.eq(user_id))
+            .order_by_asc(referral_reward::Column::Id);
+        match &self.conn {
+            DbOrTxn::Conn(c) => query.all(c.as_ref()).await.map_err(AppError::DbError),
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                query.all(txn).await.map_err(AppError::DbError)
+            }
+        }
+    }
+
+    async fn search_users(&self, query: &UserSearchQuery) -> AppResult<UserPage> {
+        match &self.conn {
+            DbOrTxn::Conn(c) => Self::search_users_internal(c.as_ref(), query).await,
+            DbOrTxn::Txn(mutex) => {
+                let lock = mutex.lock().await;
+                let txn = lock.as_ref().expect("Active txn");
+                Self::search_users_internal(txn, query).await
+            }
+        }
+    }
 });
 
+/// Matches a public user id, falling back to the legacy column for
+/// pre-UUIDv7 identifiers still carried by old tokens and links.
+fn uuid_filter(uuid: &str) -> Expr {
+    if is_legacy_user_id(uuid) {
+        user::Column::LegacyUuid.eq(uuid)
+    } else {
+        user::Column::Uuid.eq(uuid)
+    }
+}
+
+/// Escapes LIKE wildcards so user input only ever matches literally.
+fn escape_like(fragment: &str) -> String {
+    fragment
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn illegal_transition(from: Option<&AccountStatus>, to: &AccountStatus) -> AppError {
+    match from {
+        Some(from) => AppError::Conflict(format!(
+            "Illegal account status transition from {:?} to {:?}",
+            from, to
+        )),
+        None => AppError::Conflict(format!("Illegal account status transition to {:?}", to)),
+    }
+}
+
+fn dormancy_transition(user_id: i32, at: chrono::NaiveDateTime) -> status_transition::ActiveModel {
+    status_transition::ActiveModel {
+        user_id: Set(user_id),
+        from_status: Set(AccountStatus::Active),
+        to_status: Set(AccountStatus::Dormant),
+        actor_type: Set(StatusActorType::System),
+        actor_id: Set(None),
+        reason: Set("Idle for a year".to_string()),
+        created_at: Set(at),
+        ..Default::default()
+    }
+}
+
 // Helper implementation for inner methods needs to appear outside macro
 impl SeaOrmRepository<user::Entity> {
     async fn create_user_internal<C>(
@@ -169,59 +569,193 @@ impl SeaOrmRepository<user::Entity> {
         Ok(created_user)
     }
 
+    /// The user and verification in one joined query, socials in a second.
     async fn find_details_internal<C>(db: &C, uuid: &str) -> AppResult<Option<user::Model>>
     where
         C: ConnectionTrait,
     {
-        let user_model = user::Entity::find()
-            .filter(user::Column::Uuid.eq(uuid))
+        let Some((mut u, verification)) = user::Entity::find()
+            .filter(uuid_filter(uuid))
+            .find_also_related(verification::Entity)
             .one(db)
             .await
-            .map_err(AppError::DbError)?;
+            .map_err(AppError::DbError)?
+        else {
+            return Ok(None);
+        };
 
-        match user_model {
-            Some(mut u) => {
-                let verification = u
-                    .find_related(verification::Entity)
-                    .one(db)
-                    .await
-                    .map_err(AppError::DbError)?;
+        u.socials = u
+            .find_related(social::Entity)
+            .all(db)
+            .await
+            .map_err(AppError::DbError)?;
+        u.verification = verification;
+        Ok(Some(u))
+    }
 
-                let socials = u
-                    .find_related(social::Entity)
-                    .all(db)
-                    .await
-                    .map_err(AppError::DbError)?;
+    async fn mark_dormant_internal<C>(
+        db: &C,
+        idle_before: chrono::NaiveDateTime,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<u64>
+    where
+        C: ConnectionTrait,
+    {
+        let last_activity = Func::coalesce([
+            Expr::col(user::Column::LastLoginAt),
+            Expr::col(user::Column::CreatedAt),
+        ]);
+        let dormant = user::Entity::update_many()
+            .col_expr(
+                user::Column::AccountStatus,
+                Expr::val(AccountStatus::Dormant.to_value()),
+            )
+            .col_expr(user::Column::DormantAt, Expr::val(at))
+            .filter(user::Column::AccountStatus.eq(AccountStatus::Active))
+            .filter(Expr::expr(last_activity).lt(idle_before))
+            .exec_with_returning(db)
+            .await
+            .map_err(AppError::DbError)?;
+        if dormant.is_empty() {
+            return Ok(0);
+        }
 
-                // Delivery is not strictly part of User aggregate in this read model necessarily,
-                // but if it was in the original code, we should check if we need it.
-                // The original code imported delivery entity.
-                // However, moving this to user module might create circular dependency if we import delivery entity here?
-                // `delivery` module likely depends on `user` module?
-                // Let's check original code:
-                // It was:
-                // let delivery = u.find_related(delivery_data::Entity).one(db)...
-                // We need to see if we can import delivery entity here without cycle.
-                // Usually entities are safe to import.
+        status_transition::Entity::insert_many(
+            dormant.iter().map(|u| dormancy_transition(u.id, at)),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(AppError::DbError)?;
 
-                // For now I will comment out delivery part or try to import it.
-                // The prompt was to split modules.
-                // If I import crate::modules::delivery::entities::delivery_data, it should be fine as long as `delivery` doesn't import `users::repository` (which it might).
-                // But entities are usually low level.
+        Ok(dormant.len() as u64)
+    }
 
-                // Wait, if I'm in `users` module, checking `delivery` table seems like a cross-module concern.
-                // But for `find_with_details_by_uuid`, maybe it returns a user with all mixed in data?
-                // The `user::Model` has a `delivery` field?
-                // Let's check `user::Model`.
+    async fn search_users_internal<C>(db: &C, query: &UserSearchQuery) -> AppResult<UserPage>
+    where
+        C: ConnectionTrait,
+    {
+        use sea_orm::sea_query::extension::postgres::PgExpr;
 
-                u.verification = verification;
-                u.socials = socials;
-                // u.delivery = delivery; // We will handle this in slightly different way or just import it.
+        let filter = &query.filter;
+        let sort_expr: Expr = match query.sort {
+            UserSortField::CreatedAt => Expr::col((user::Entity, user::Column::CreatedAt)),
+            UserSortField::LastLoginAt => Func::coalesce([
+                Expr::col((user::Entity, user::Column::LastLoginAt)),
+                Expr::val(UserCursor::NEVER),
+            ])
+            .into(),
+        };
 
-                Ok(Some(u))
+        let mut cond = Condition::all();
+        if let Some(status) = &filter.account_status {
+            cond = cond.add(user::Column::AccountStatus.eq(status.clone()));
+        }
+        if let Some(provider) = &filter.provider {
+            cond = cond.add(
+                user::Column::Id.in_subquery(
+                    Query::select()
+                        .column(social::Column::UserId)
+                        .from(social::Entity)
+                        .and_where(social::Column::Provider.eq(provider.clone()))
+                        .to_owned(),
+                ),
+            );
+        }
+        if filter.email_verified.is_some() || filter.phone_verified.is_some() {
+            let mut sub = Query::select();
+            sub.column(verification::Column::UserId)
+                .from(verification::Entity);
+            if let Some(v) = filter.email_verified {
+                sub.and_where(verification::Column::EmailVerified.eq(v));
+            }
+            if let Some(v) = filter.phone_verified {
+                sub.and_where(verification::Column::PhoneVerified.eq(v));
             }
-            None => Ok(None),
+            cond = cond.add(user::Column::Id.in_subquery(sub.to_owned()));
+        }
+        if let Some(email) = &filter.email {
+            cond = cond.add(
+                Expr::col((user::Entity, user::Column::Email))
+                    .ilike(format!("%{}%", escape_like(email))),
+            );
         }
+        if let Some(phone) = &filter.phone_number {
+            cond = cond.add(
+                Expr::col((user::Entity, user::Column::PhoneNumber))
+                    .like(format!("%{}%", escape_like(phone))),
+            );
+        }
+        if let Some(from) = filter.created_from {
+            cond = cond.add(user::Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = filter.created_to {
+            cond = cond.add(user::Column::CreatedAt.lt(to));
+        }
+        if let Some(from) = filter.last_login_from {
+            cond = cond.add(user::Column::LastLoginAt.gte(from));
+        }
+        if let Some(to) = filter.last_login_to {
+            cond = cond.add(user::Column::LastLoginAt.lt(to));
+        }
+
+        let order = match query.direction {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        };
+        if let Some(cursor) = query.cursor {
+            let (after_key, after_id) = match query.direction {
+                SortDirection::Asc => (
+                    sort_expr.clone().gt(cursor.sort_key),
+                    user::Column::Id.gt(cursor.id),
+                ),
+                SortDirection::Desc => (
+                    sort_expr.clone().lt(cursor.sort_key),
+                    user::Column::Id.lt(cursor.id),
+                ),
+            };
+            cond = cond.add(
+                Condition::any().add(after_key).add(
+                    Condition::all()
+                        .add(sort_expr.clone().eq(cursor.sort_key))
+                        .add(after_id),
+                ),
+            );
+        }
+
+        let mut users = user::Entity::find()
+            .filter(cond)
+            .order_by(sort_expr, order.clone())
+            .order_by(user::Column::Id, order)
+            .limit(query.limit + 1)
+            .all(db)
+            .await
+            .map_err(AppError::DbError)?;
+
+        // Attach details for the page in two batched queries.
+        let ids: Vec<i32> = users.iter().map(|u| u.id).collect();
+        let mut verifications: HashMap<i32, verification::Model> = verification::Entity::find()
+            .filter(verification::Column::UserId.is_in(ids.clone()))
+            .all(db)
+            .await
+            .map_err(AppError::DbError)?
+            .into_iter()
+            .map(|v| (v.user_id, v))
+            .collect();
+        let socials = social::Entity::find()
+            .filter(social::Column::UserId.is_in(ids))
+            .all(db)
+            .await
+            .map_err(AppError::DbError)?;
+        for u in users.iter_mut() {
+            u.verification = verifications.remove(&u.id);
+            u.socials = socials
+                .iter()
+                .filter(|s| s.user_id == u.id)
+                .cloned()
+                .collect();
+        }
+
+        Ok(UserPage::from_rows(users, query.limit, query.sort))
     }
 }
 
@@ -234,9 +768,35 @@ pub struct InMemoryUserRepository {
     users: Arc<Mutex<HashMap<i32, user::Model>>>,
     socials: Arc<Mutex<Vec<social::Model>>>,
     verifications: Arc<Mutex<HashMap<i32, verification::Model>>>,
+    notification_settings: Arc<Mutex<HashMap<i32, notification_setting::Model>>>,
+    status_transitions: Arc<Mutex<Vec<status_transition::Model>>>,
+    devices: Arc<Mutex<Vec<device::Model>>>,
+    referrals: Arc<Mutex<Vec<referral::Model>>>,
+    referral_rewards: Arc<Mutex<Vec<referral_reward::Model>>>,
     counter: Arc<Mutex<i32>>,
 }
 
+impl InMemoryUserRepository {
+    fn push_transition(
+        &self,
+        transition: status_transition::ActiveModel,
+    ) -> status_transition::Model {
+        let mut transitions = self.status_transitions.lock().unwrap();
+        let model = status_transition::Model {
+            id: transitions.len() as i32 + 1,
+            user_id: transition.user_id.unwrap(),
+            from_status: transition.from_status.unwrap(),
+            to_status: transition.to_status.unwrap(),
+            actor_type: transition.actor_type.unwrap(),
+            actor_id: transition.actor_id.unwrap(),
+            reason: transition.reason.unwrap(),
+            created_at: transition.created_at.unwrap(),
+        };
+        transitions.push(model.clone());
+        model
+    }
+}
+
 #[async_trait]
 impl UserRepository for InMemoryUserRepository {
     async fn find_by_id(&self, id: i32) -> AppResult<Option<user::Model>> {
@@ -245,12 +805,29 @@ impl UserRepository for InMemoryUserRepository {
     }
     async fn find_by_uuid(&self, uuid: &str) -> AppResult<Option<user::Model>> {
         let users = self.users.lock().unwrap();
-        Ok(users.values().find(|u| u.uuid == uuid).cloned())
+        Ok(users
+            .values()
+            .find(|u| u.uuid == uuid || u.legacy_uuid.as_deref() == Some(uuid))
+            .cloned())
     }
     async fn find_by_email(&self, email: &str) -> AppResult<Option<user::Model>> {
         let users = self.users.lock().unwrap();
         Ok(users.values().find(|u| u.email == email).cloned())
     }
+    async fn find_by_handle(&self, handle: &str) -> AppResult<Option<user::Model>> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .values()
+            .find(|u| u.handle.as_deref() == Some(handle))
+            .cloned())
+    }
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<user::Model>> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .values()
+            .find(|u| u.referral_code.as_deref() == Some(code))
+            .cloned())
+    }
     async fn find_social(
         &self,
         provider: social::SocialProvider,
@@ -281,17 +858,32 @@ impl UserRepository for InMemoryUserRepository {
         let model_user = user::Model {
             id: new_id,
             uuid: user.uuid.unwrap(),
+            legacy_uuid: user.legacy_uuid.unwrap(),
             username: user.username.unwrap(),
+            handle: user.handle.unwrap(),
+            handle_changed_at: user.handle_changed_at.unwrap(),
+            referral_code: user.referral_code.unwrap(),
+            avatar_key: user.avatar_key.unwrap(),
+            avatar_thumbnail_key: user.avatar_thumbnail_key.unwrap(),
+            avatar_updated_at: user.avatar_updated_at.unwrap(),
             email: user.email.unwrap(),
             country_code: user.country_code.unwrap(),
             phone_number: user.phone_number.unwrap(),
+            locale: user.locale.unwrap(),
             account_status: user.account_status.unwrap(),
+            role: user.role.unwrap(),
             created_at: user.created_at.unwrap(),
             updated_at: user.updated_at.unwrap(),
             last_login_at: user.last_login_at.unwrap(),
+            login_count: user.login_count.unwrap(),
+            last_login_provider: user.last_login_provider.unwrap(),
+            dormant_at: user.dormant_at.unwrap(),
+            banned_until: user.banned_until.unwrap(),
+            age_range: user.age_range.unwrap(),
+            birth_year: user.birth_year.unwrap(),
+            age_updated_at: user.age_updated_at.unwrap(),
             verification: None,
             socials: vec![],
-            delivery: vec![],
         };
 
         users.insert(new_id, model_user.clone());
@@ -358,19 +950,88 @@ impl UserRepository for InMemoryUserRepository {
         let id = user.id.unwrap();
 
         if let Some(existing) = users.get_mut(&id) {
-            if let Set(v) = user.account_status {
-                existing.account_status = v;
+            if let Set(to) = &user.account_status
+                && !existing.account_status.can_transition_to(to)
+            {
+                return Err(illegal_transition(Some(&existing.account_status), to));
+            }
+            if let Set(v) = user.username {
+                existing.username = v;
+            }
+            if let Set(v) = user.handle {
+                existing.handle = v;
+            }
+            if let Set(v) = user.handle_changed_at {
+                existing.handle_changed_at = v;
+            }
+            if let Set(v) = user.referral_code {
+                existing.referral_code = v;
+            }
+            if let Set(v) = user.avatar_key {
+                existing.avatar_key = v;
+            }
+            if let Set(v) = user.avatar_thumbnail_key {
+                existing.avatar_thumbnail_key = v;
+            }
+            if let Set(v) = user.avatar_updated_at {
+                existing.avatar_updated_at = v;
+            }
+            if let Set(v) = user.banned_until {
+                existing.banned_until = v;
             }
             if let Set(v) = user.email {
                 existing.email = v;
             }
+            if let Set(v) = user.country_code {
+                existing.country_code = v;
+            }
+            if let Set(v) = user.phone_number {
+                existing.phone_number = v;
+            }
+            if let Set(v) = user.locale {
+                existing.locale = v;
+            }
+            if let Set(v) = user.account_status {
+                existing.account_status = v;
+            }
+            if let Set(v) = user.role {
+                existing.role = v;
+            }
+            if let Set(v) = user.updated_at {
+                existing.updated_at = v;
+            }
+            if let Set(v) = user.last_login_at {
+                existing.last_login_at = v;
+            }
+            if let Set(v) = user.login_count {
+                existing.login_count = v;
+            }
+            if let Set(v) = user.last_login_provider {
+                existing.last_login_provider = v;
+            }
+            if let Set(v) = user.dormant_at {
+                existing.dormant_at = v;
+            }
+            if let Set(v) = user.age_range {
+                existing.age_range = v;
+            }
+            if let Set(v) = user.birth_year {
+                existing.birth_year = v;
+            }
+            if let Set(v) = user.age_updated_at {
+                existing.age_updated_at = v;
+            }
         }
         Ok(users.get(&id).unwrap().clone())
     }
 
     async fn find_with_details_by_uuid(&self, uuid: &str) -> AppResult<Option<user::Model>> {
         let users = self.users.lock().unwrap();
-        if let Some(mut user) = users.values().find(|u| u.uuid == uuid).cloned() {
+        if let Some(mut user) = users
+            .values()
+            .find(|u| u.uuid == uuid || u.legacy_uuid.as_deref() == Some(uuid))
+            .cloned()
+        {
             let verifications = self.verifications.lock().unwrap();
 
             let verification = verifications.get(&user.id).cloned();
@@ -407,13 +1068,392 @@ impl UserRepository for InMemoryUserRepository {
             if let Set(v) = verification.email_verified_at {
                 existing.email_verified_at = v;
             }
+            if let Set(v) = verification.phone_verified {
+                existing.phone_verified = v;
+            }
+            if let Set(v) = verification.phone_verified_at {
+                existing.phone_verified_at = v;
+            }
             Ok(existing.clone())
         } else {
             Err(AppError::NotFound)
         }
     }
 
+    async fn record_login(
+        &self,
+        user_id: i32,
+        provider: social::SocialProvider,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<user::Model> {
+        let mut users = self.users.lock().unwrap();
+        let user = users.get_mut(&user_id).ok_or(AppError::NotFound)?;
+        user.last_login_at = Some(at);
+        user.login_count += 1;
+        user.last_login_provider = Some(provider);
+        Ok(user.clone())
+    }
+
+    async fn mark_dormant(
+        &self,
+        idle_before: chrono::NaiveDateTime,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<u64> {
+        let mut users = self.users.lock().unwrap();
+        let mut dormant = Vec::new();
+        for user in users.values_mut().filter(|u| {
+            u.account_status == AccountStatus::Active
+                && u.last_login_at.unwrap_or(u.created_at) < idle_before
+        }) {
+            user.account_status = AccountStatus::Dormant;
+            user.dormant_at = Some(at);
+            dormant.push(user.id);
+        }
+        drop(users);
+
+        for user_id in &dormant {
+            self.push_transition(dormancy_transition(*user_id, at));
+        }
+        Ok(dormant.len() as u64)
+    }
+
+    async fn find_notification_setting(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Option<notification_setting::Model>> {
+        let settings = self.notification_settings.lock().unwrap();
+        Ok(settings.get(&user_id).cloned())
+    }
+
+    async fn save_notification_setting(
+        &self,
+        setting: notification_setting::ActiveModel,
+    ) -> AppResult<notification_setting::Model> {
+        let mut settings = self.notification_settings.lock().unwrap();
+        let user_id = setting.user_id.unwrap();
+        let id = settings
+            .get(&user_id)
+            .map_or(settings.len() as i32 + 1, |existing| existing.id);
+        let model = notification_setting::Model {
+            id,
+            user_id,
+            push_enabled: setting.push_enabled.unwrap(),
+            sms_enabled: setting.sms_enabled.unwrap(),
+            email_enabled: setting.email_enabled.unwrap(),
+            alimtalk_enabled: setting.alimtalk_enabled.unwrap(),
+            order_status_enabled: setting.order_status_enabled.unwrap(),
+            marketing_night_opt_in: setting.marketing_night_opt_in.unwrap(),
+            updated_at: setting.updated_at.unwrap(),
+        };
+        settings.insert(user_id, model.clone());
+        Ok(model)
+    }
+
+    async fn create_status_transition(
+        &self,
+        transition: status_transition::ActiveModel,
+    ) -> AppResult<status_transition::Model> {
+        Ok(self.push_transition(transition))
+    }
+
+    async fn find_status_transitions(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<status_transition::Model>> {
+        let transitions = self.status_transitions.lock().unwrap();
+        Ok(transitions
+            .iter()
+            .filter(|t| t.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn record_device(
+        &self,
+        user_id: i32,
+        device_id: &str,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<()> {
+        let mut devices = self.devices.lock().unwrap();
+        if let Some(existing) = devices
+            .iter_mut()
+            .find(|d| d.user_id == user_id && d.device_id == device_id)
+        {
+            existing.last_seen_at = at;
+        } else {
+            let id = devices.len() as i32 + 1;
+            devices.push(device::Model {
+                id,
+                user_id,
+                device_id: device_id.to_string(),
+                first_seen_at: at,
+                last_seen_at: at,
+            });
+        }
+        Ok(())
+    }
+
+    async fn find_shared_device(
+        &self,
+        user_id: i32,
+        other_user_id: i32,
+    ) -> AppResult<Option<String>> {
+        let devices = self.devices.lock().unwrap();
+        Ok(devices
+            .iter()
+            .filter(|d| d.user_id == user_id)
+            .find(|d| {
+                devices
+                    .iter()
+                    .any(|o| o.user_id == other_user_id && o.device_id == d.device_id)
+            })
+            .map(|d| d.device_id.clone()))
+    }
+
+    async fn find_referral_by_referee(
+        &self,
+        referee_id: i32,
+    ) -> AppResult<Option<referral::Model>> {
+        let referrals = self.referrals.lock().unwrap();
+        Ok(referrals
+            .iter()
+            .find(|r| r.referee_id == referee_id)
+            .cloned())
+    }
+
+    async fn find_referrals_by_referrer(
+        &self,
+        referrer_id: i32,
+    ) -> AppResult<Vec<referral::Model>> {
+        let referrals = self.referrals.lock().unwrap();
+        Ok(referrals
+            .iter()
+            .filter(|r| r.referrer_id == referrer_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_referrals_created_between(
+        &self,
+        from: chrono::NaiveDateTime,
+        to: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<referral::Model>> {
+        let referrals = self.referrals.lock().unwrap();
+        Ok(referrals
+            .iter()
+            .filter(|r| r.created_at >= from && r.created_at < to)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model> {
+        let mut referrals = self.referrals.lock().unwrap();
+        let referee_id = referral.referee_id.unwrap();
+        if referrals.iter().any(|r| r.referee_id == referee_id) {
+            return Err(AppError::Conflict(
+                "User already has a referrer".to_string(),
+            ));
+        }
+        let model = referral::Model {
+            id: referrals.len() as i32 + 1,
+            referrer_id: referral.referrer_id.unwrap(),
+            referee_id,
+            code: referral.code.unwrap(),
+            status: referral.status.unwrap(),
+            rejection_reason: referral.rejection_reason.unwrap(),
+            device_id: referral.device_id.unwrap(),
+            created_at: referral.created_at.unwrap(),
+            converted_at: referral.converted_at.unwrap(),
+        };
+        referrals.push(model.clone());
+        Ok(model)
+    }
+
+    async fn update_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model> {
+        let mut referrals = self.referrals.lock().unwrap();
+        let id = referral.id.unwrap();
+        let existing = referrals
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or(AppError::NotFound)?;
+        if let Set(v) = referral.status {
+            existing.status = v;
+        }
+        if let Set(v) = referral.rejection_reason {
+            existing.rejection_reason = v;
+        }
+        if let Set(v) = referral.converted_at {
+            existing.converted_at = v;
+        }
+        Ok(existing.clone())
+    }
+
+    async fn create_referral_reward(
+        &self,
+        reward: referral_reward::ActiveModel,
+    ) -> AppResult<referral_reward::Model> {
+        let mut rewards = self.referral_rewards.lock().unwrap();
+        let model = referral_reward::Model {
+            id: rewards.len() as i32 + 1,
+            referral_id: reward.referral_id.unwrap(),
+            user_id: reward.user_id.unwrap(),
+            role: reward.role.unwrap(),
+            points: reward.points.unwrap(),
+            created_at: reward.created_at.unwrap(),
+        };
+        rewards.push(model.clone());
+        Ok(model)
+    }
+
+    async fn find_referral_rewards_by_user(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<referral_reward::Model>> {
+        let rewards = self.referral_rewards.lock().unwrap();
+        Ok(rewards
+            .iter()
+            .filter(|r| r.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn search_users(&self, query: &UserSearchQuery) -> AppResult<UserPage> {
+        let users = self.users.lock().unwrap();
+        let verifications = self.verifications.lock().unwrap();
+        let socials = self.socials.lock().unwrap();
+        let filter = &query.filter;
+
+        let mut rows: Vec<user::Model> = users
+            .values()
+            .filter(|u| {
+                let verification = verifications.get(&u.id);
+                filter
+                    .account_status
+                    .as_ref()
+                    .is_none_or(|s| &u.account_status == s)
+                    && filter.provider.as_ref().is_none_or(|p| {
+                        socials
+                            .iter()
+                            .any(|s| s.user_id == u.id && &s.provider == p)
+                    })
+                    && filter
+                        .email_verified
+                        .is_none_or(|v| verification.is_some_and(|x| x.email_verified == v))
+                    && filter
+                        .phone_verified
+                        .is_none_or(|v| verification.is_some_and(|x| x.phone_verified == v))
+                    && filter
+                        .email
+                        .as_ref()
+                        .is_none_or(|e| u.email.to_lowercase().contains(&e.to_lowercase()))
+                    && filter
+                        .phone_number
+                        .as_ref()
+                        .is_none_or(|p| u.phone_number.contains(p.as_str()))
+                    && filter.created_from.is_none_or(|t| u.created_at >= t)
+                    && filter.created_to.is_none_or(|t| u.created_at < t)
+                    && filter
+                        .last_login_from
+                        .is_none_or(|t| u.last_login_at.is_some_and(|l| l >= t))
+                    && filter
+                        .last_login_to
+                        .is_none_or(|t| u.last_login_at.is_some_and(|l| l < t))
+            })
+            .cloned()
+            .collect();
+
+        let key = |u: &user::Model| (UserCursor::sort_key_of(u, query.sort), u.id);
+        rows.sort_by_key(key);
+        if query.direction == SortDirection::Desc {
+            rows.reverse();
+        }
+        if let Some(cursor) = query.cursor {
+            let position = (cursor.sort_key, cursor.id);
+            rows.retain(|u| match query.direction {
+                SortDirection::Asc => key(u) > position,
+                SortDirection::Desc => key(u) < position,
+            });
+        }
+        rows.truncate(query.limit as usize + 1);
+
+        for u in rows.iter_mut() {
+            u.verification = verifications.get(&u.id).cloned();
+            u.socials = socials
+                .iter()
+                .filter(|s| s.user_id == u.id)
+                .cloned()
+                .collect();
+        }
+
+        Ok(UserPage::from_rows(rows, query.limit, query.sort))
+    }
+
     fn with_transaction(&self, _uow: &dyn UnitOfWork) -> Option<Box<dyn UserRepository>> {
         Some(Box::new(self.clone()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::dtos::{SocialLoginDto, UserSearchFilter};
+    use crate::modules::users::infra::fixtures::{kakao_login, login_with};
+
+    async fn seed(repo: &InMemoryUserRepository, n: usize) {
+        for i in 0..n {
+            login_with(
+                repo,
+                SocialLoginDto {
+                    email: Some(format!("user{}@gimme.com", i)),
+                    ..kakao_login(&format!("kakao-{}", i))
+                },
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_users_pages_without_gaps() {
+        let repo = InMemoryUserRepository::default();
+        seed(&repo, 5).await;
+
+        let mut query = UserSearchQuery {
+            limit: 2,
+            ..Default::default()
+        };
+        let mut seen = vec![];
+        loop {
+            let page = repo.search_users(&query).await.unwrap();
+            assert!(page.items.len() <= 2);
+            assert!(page.items.iter().all(|u| u.verification.is_some()));
+            seen.extend(page.items.iter().map(|u| u.id));
+            match page.next_cursor {
+                Some(c) => query.cursor = UserCursor::decode(&c),
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec![5, 4, 3, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_search_users_filters_by_email_fragment() {
+        let repo = InMemoryUserRepository::default();
+        seed(&repo, 3).await;
+
+        let page = repo
+            .search_users(&UserSearchQuery {
+                filter: UserSearchFilter {
+                    email: Some("USER1@".to_string()),
+                    email_verified: Some(false),
+                    ..Default::default()
+                },
+                limit: 20,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].email, "user1@gimme.com");
+        assert!(page.next_cursor.is_none());
+    }
+}
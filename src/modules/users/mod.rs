@@ -1,8 +1,16 @@
+pub mod age_gate;
+pub mod avatar;
+pub mod dormancy;
 pub mod dtos;
 pub mod entities;
+pub mod handle;
 pub mod handlers;
 pub mod infra;
+pub mod notification;
+pub mod onboarding;
+pub mod referral;
 pub mod repository;
 pub mod router;
 pub mod service;
+pub mod status;
 pub mod utils;
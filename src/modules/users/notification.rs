@@ -0,0 +1,366 @@
+use chrono::{FixedOffset, NaiveTime, Timelike};
+use sea_orm::ActiveValue::Set;
+use serde::{Deserialize, Serialize};
+
+use super::entities::notification_setting;
+use super::repository::UserRepository;
+use crate::modules::terms::entities::terms_document::TermsKind;
+use crate::modules::terms::repository::TermsRepository;
+use crate::modules::terms::service::{DocumentConsent, TermsService};
+use crate::shared::error::AppResult;
+
+/// 정보통신망법 §50: no advertising between 21:00 and 08:00 without a
+/// separate night-time consent. Evaluated in KST regardless of the user's locale.
+pub const MARKETING_QUIET_START_HOUR: u32 = 21;
+pub const MARKETING_QUIET_END_HOUR: u32 = 8;
+const KST_OFFSET_SECS: i32 = 9 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationChannel {
+    Push,
+    Sms,
+    Email,
+    /// 카카오 알림톡
+    Alimtalk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationCategory {
+    OrderStatus,
+    Marketing,
+    /// Login alerts, email changes and the like. Cannot be turned off.
+    Security,
+}
+
+/// Marketing consent per channel: the user's agreement to the current
+/// `MARKETING_*` terms versions. Withdrawing it under terms is what stops
+/// marketing; there is no second switch. 알림톡 cannot carry advertising,
+/// so it has no consent document and never gets marketing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarketingConsent {
+    pub push: bool,
+    pub sms: bool,
+    pub email: bool,
+}
+
+impl MarketingConsent {
+    pub fn from_terms(status: &[DocumentConsent]) -> Self {
+        let agreed = |kind| {
+            status
+                .iter()
+                .any(|s| s.document.kind == kind && s.is_agreed())
+        };
+        Self {
+            push: agreed(TermsKind::MarketingPush),
+            sms: agreed(TermsKind::MarketingSms),
+            email: agreed(TermsKind::MarketingEmail),
+        }
+    }
+
+    fn covers(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Push => self.push,
+            NotificationChannel::Sms => self.sms,
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Alimtalk => false,
+        }
+    }
+}
+
+/// Effective settings for a user, with defaults applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationSettings {
+    pub push: bool,
+    pub sms: bool,
+    pub email: bool,
+    pub alimtalk: bool,
+    pub order_status: bool,
+    pub marketing: MarketingConsent,
+    pub marketing_night_opt_in: bool,
+}
+
+impl Default for NotificationSettings {
+    /// All channels and order updates on; marketing is strictly opt-in.
+    fn default() -> Self {
+        Self {
+            push: true,
+            sms: true,
+            email: true,
+            alimtalk: true,
+            order_status: true,
+            marketing: MarketingConsent::default(),
+            marketing_night_opt_in: false,
+        }
+    }
+}
+
+/// Partial update; `None` leaves a switch unchanged.
+#[derive(Debug, Default)]
+pub struct UpdateNotificationSettingsDto {
+    pub push: Option<bool>,
+    pub sms: Option<bool>,
+    pub email: Option<bool>,
+    pub alimtalk: Option<bool>,
+    pub order_status: Option<bool>,
+    pub marketing_night_opt_in: Option<bool>,
+}
+
+impl NotificationSettings {
+    fn new(stored: Option<notification_setting::Model>, marketing: MarketingConsent) -> Self {
+        match stored {
+            Some(m) => Self {
+                push: m.push_enabled,
+                sms: m.sms_enabled,
+                email: m.email_enabled,
+                alimtalk: m.alimtalk_enabled,
+                order_status: m.order_status_enabled,
+                marketing,
+                marketing_night_opt_in: m.marketing_night_opt_in,
+            },
+            None => Self {
+                marketing,
+                ..Self::default()
+            },
+        }
+    }
+
+    fn channel_enabled(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Push => self.push,
+            NotificationChannel::Sms => self.sms,
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Alimtalk => self.alimtalk,
+        }
+    }
+
+    /// Pure decision for a message sent at `at` (UTC).
+    /// Security messages always go out; everything else needs its channel
+    /// enabled. Order updates need their switch, marketing needs consent for
+    /// that channel and respects quiet hours.
+    pub fn allows(
+        &self,
+        channel: NotificationChannel,
+        category: NotificationCategory,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        match category {
+            NotificationCategory::Security => true,
+            NotificationCategory::OrderStatus => self.channel_enabled(channel) && self.order_status,
+            NotificationCategory::Marketing => {
+                self.channel_enabled(channel)
+                    && self.marketing.covers(channel)
+                    && (self.marketing_night_opt_in || !Self::is_marketing_quiet_hours(at))
+            }
+        }
+    }
+
+    pub fn is_marketing_quiet_hours(at: chrono::DateTime<chrono::Utc>) -> bool {
+        let kst = FixedOffset::east_opt(KST_OFFSET_SECS).expect("valid KST offset");
+        let hour = at.with_timezone(&kst).time().hour();
+        !(MARKETING_QUIET_END_HOUR..MARKETING_QUIET_START_HOUR).contains(&hour)
+    }
+
+    pub fn quiet_hours() -> (NaiveTime, NaiveTime) {
+        (
+            NaiveTime::from_hms_opt(MARKETING_QUIET_START_HOUR, 0, 0).expect("valid hour"),
+            NaiveTime::from_hms_opt(MARKETING_QUIET_END_HOUR, 0, 0).expect("valid hour"),
+        )
+    }
+}
+
+pub struct NotificationService;
+
+impl NotificationService {
+    pub async fn settings_for(
+        repo: &dyn UserRepository,
+        terms_repo: &dyn TermsRepository,
+        user_id: i32,
+    ) -> AppResult<NotificationSettings> {
+        let marketing =
+            MarketingConsent::from_terms(&TermsService::status_for(terms_repo, user_id).await?);
+        Ok(NotificationSettings::new(
+            repo.find_notification_setting(user_id).await?,
+            marketing,
+        ))
+    }
+
+    /// Marketing consent itself is given or withdrawn through terms.
+    pub async fn update(
+        repo: &dyn UserRepository,
+        terms_repo: &dyn TermsRepository,
+        user_id: i32,
+        dto: UpdateNotificationSettingsDto,
+    ) -> AppResult<NotificationSettings> {
+        let current = Self::settings_for(repo, terms_repo, user_id).await?;
+        let saved = repo
+            .save_notification_setting(notification_setting::ActiveModel {
+                user_id: Set(user_id),
+                push_enabled: Set(dto.push.unwrap_or(current.push)),
+                sms_enabled: Set(dto.sms.unwrap_or(current.sms)),
+                email_enabled: Set(dto.email.unwrap_or(current.email)),
+                alimtalk_enabled: Set(dto.alimtalk.unwrap_or(current.alimtalk)),
+                order_status_enabled: Set(dto.order_status.unwrap_or(current.order_status)),
+                marketing_night_opt_in: Set(dto
+                    .marketing_night_opt_in
+                    .unwrap_or(current.marketing_night_opt_in)),
+                updated_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(NotificationSettings::new(Some(saved), current.marketing))
+    }
+
+    /// Entry point for sending code: may `user_id` receive this message now?
+    pub async fn is_allowed(
+        repo: &dyn UserRepository,
+        terms_repo: &dyn TermsRepository,
+        user_id: i32,
+        channel: NotificationChannel,
+        category: NotificationCategory,
+    ) -> AppResult<bool> {
+        Ok(Self::settings_for(repo, terms_repo, user_id).await?.allows(
+            channel,
+            category,
+            chrono::Utc::now(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::terms::infra::persistence::InMemoryTermsRepository;
+    use crate::modules::terms::service::ConsentSubmission;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+    use chrono::TimeZone;
+
+    fn kst(hour: u32) -> chrono::DateTime<chrono::Utc> {
+        // KST is UTC+9 with no DST.
+        chrono::Utc
+            .with_ymd_and_hms(2024, 3, 1, 0, 0, 0)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::hours(hour as i64 - 9))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_marketing_respects_quiet_hours_in_kst() {
+        let settings = NotificationSettings {
+            marketing: MarketingConsent {
+                push: true,
+                sms: true,
+                email: true,
+            },
+            ..Default::default()
+        };
+        let push = NotificationChannel::Push;
+
+        assert!(settings.allows(push, NotificationCategory::Marketing, kst(12)));
+        assert!(!settings.allows(push, NotificationCategory::Marketing, kst(21)));
+        assert!(!settings.allows(push, NotificationCategory::Marketing, kst(7)));
+        assert!(settings.allows(push, NotificationCategory::Marketing, kst(8)));
+        assert!(settings.allows(push, NotificationCategory::OrderStatus, kst(23)));
+
+        let night = NotificationSettings {
+            marketing_night_opt_in: true,
+            ..settings
+        };
+        assert!(night.allows(push, NotificationCategory::Marketing, kst(23)));
+    }
+
+    #[test]
+    fn test_defaults_and_channel_switches() {
+        let defaults = NotificationSettings::default();
+        assert!(!defaults.allows(
+            NotificationChannel::Email,
+            NotificationCategory::Marketing,
+            kst(12)
+        ));
+
+        let no_sms = NotificationSettings {
+            sms: false,
+            ..Default::default()
+        };
+        assert!(!no_sms.allows(
+            NotificationChannel::Sms,
+            NotificationCategory::OrderStatus,
+            kst(12)
+        ));
+        assert!(no_sms.allows(
+            NotificationChannel::Sms,
+            NotificationCategory::Security,
+            kst(3)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_marketing_follows_terms_consent_per_channel() {
+        let repo = InMemoryUserRepository::default();
+        let terms_repo = InMemoryTermsRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let sms = TermsService::publish(
+            &terms_repo,
+            TermsKind::MarketingSms,
+            "SMS 수신",
+            "/sms/1",
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        TermsService::publish(
+            &terms_repo,
+            TermsKind::MarketingPush,
+            "푸시 수신",
+            "/push/1",
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        let submit = |agreed| {
+            TermsService::submit(
+                &terms_repo,
+                &manager,
+                1,
+                vec![ConsentSubmission {
+                    terms_document_id: sms.id,
+                    agreed,
+                }],
+            )
+        };
+
+        submit(true).await.unwrap();
+        let settings = NotificationService::settings_for(&repo, &terms_repo, 1)
+            .await
+            .unwrap();
+        let marketing = NotificationCategory::Marketing;
+        assert!(settings.allows(NotificationChannel::Sms, marketing, kst(12)));
+        assert!(!settings.allows(NotificationChannel::Push, marketing, kst(12)));
+        assert!(!settings.allows(NotificationChannel::Alimtalk, marketing, kst(12)));
+
+        // Saving other switches keeps the consent-derived permission.
+        let settings = NotificationService::update(
+            &repo,
+            &terms_repo,
+            1,
+            UpdateNotificationSettingsDto {
+                marketing_night_opt_in: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(settings.allows(NotificationChannel::Sms, marketing, kst(23)));
+
+        submit(false).await.unwrap();
+        let settings = NotificationService::settings_for(&repo, &terms_repo, 1)
+            .await
+            .unwrap();
+        assert!(!settings.allows(NotificationChannel::Sms, marketing, kst(12)));
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::entities::user;
+
+/// Steps a user still has to complete before onboarding is finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OnboardingStep {
+    EmailVerification,
+    PhoneVerification,
+    TermsAgreement,
+}
+
+impl OnboardingStep {
+    /// Derives the remaining steps from the user's verification record and
+    /// whether required terms still await agreement (see
+    /// `TermsService::pending_required`). A user without a loaded
+    /// verification record has nothing verified yet.
+    pub fn remaining_for(user: &user::Model, terms_pending: bool) -> Vec<OnboardingStep> {
+        let (email_verified, phone_verified) = user
+            .verification
+            .as_ref()
+            .map(|v| (v.email_verified, v.phone_verified))
+            .unwrap_or((false, false));
+
+        let mut steps = Vec::new();
+        if !email_verified {
+            steps.push(OnboardingStep::EmailVerification);
+        }
+        if !phone_verified {
+            steps.push(OnboardingStep::PhoneVerification);
+        }
+        if terms_pending {
+            steps.push(OnboardingStep::TermsAgreement);
+        }
+        steps
+    }
+}
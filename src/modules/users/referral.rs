@@ -0,0 +1,581 @@
+use rand::Rng;
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::SqlErr;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::entities::{
+    referral::{self, ReferralRejection, ReferralStatus},
+    referral_reward::{self, RewardRole},
+    user,
+};
+use super::repository::UserRepository;
+use crate::shared::error::{AppError, AppResult};
+
+pub const REFERRAL_CODE_LEN: usize = 8;
+/// No 0/O or 1/I, so codes survive being read out loud.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+/// How long after sign-up a code can still be entered during onboarding.
+pub const REDEEM_WINDOW_DAYS: i64 = 7;
+pub const REFERRER_REWARD_POINTS: i32 = 3000;
+pub const REFEREE_REWARD_POINTS: i32 = 3000;
+pub const REPORT_TOP_REFERRERS: usize = 20;
+/// Bounds the ancestor walk when checking for cycles.
+const MAX_TREE_DEPTH: usize = 64;
+
+#[derive(Debug, Serialize)]
+pub struct ReferralSummary {
+    pub code: String,
+    pub invited: usize,
+    pub converted: usize,
+    pub points_earned: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReferrerStats {
+    pub user_uuid: String,
+    pub attributed: u64,
+    pub converted: u64,
+}
+
+/// Conversion numbers for referrals attributed in `[from, to)`.
+#[derive(Debug, Serialize)]
+pub struct ReferralReport {
+    pub from: chrono::NaiveDateTime,
+    pub to: chrono::NaiveDateTime,
+    pub attributed: u64,
+    pub pending: u64,
+    pub converted: u64,
+    pub rejected_same_device: u64,
+    pub rejected_same_phone: u64,
+    /// Converted over attributed, rejected referrals included.
+    pub conversion_rate: f64,
+    pub top_referrers: Vec<ReferrerStats>,
+}
+
+pub struct ReferralService;
+
+impl ReferralService {
+    pub fn generate_code() -> String {
+        let mut rng = rand::rng();
+        (0..REFERRAL_CODE_LEN)
+            .map(|_| CODE_ALPHABET[rng.random_range(0..CODE_ALPHABET.len())] as char)
+            .collect()
+    }
+
+    /// Codes are case-insensitive and may be typed with spaces or dashes.
+    pub fn normalize_code(raw: &str) -> String {
+        raw.chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect::<String>()
+            .to_ascii_uppercase()
+    }
+
+    /// The user's code, assigning one to accounts created before codes existed.
+    pub async fn code_for(repo: &dyn UserRepository, user: &user::Model) -> AppResult<String> {
+        if let Some(code) = &user.referral_code {
+            return Ok(code.clone());
+        }
+
+        for _ in 0..3 {
+            let code = Self::generate_code();
+            let assigned = repo
+                .update_user(user::ActiveModel {
+                    id: Unchanged(user.id),
+                    referral_code: Set(Some(code.clone())),
+                    ..Default::default()
+                })
+                .await;
+            match assigned {
+                Ok(_) => return Ok(code),
+                Err(AppError::DbError(e))
+                    if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+                {
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(AppError::InternalServerError(
+            "Failed to assign a unique referral code".to_string(),
+        ))
+    }
+
+    /// Links `referee` to the owner of `raw_code`. Suspected self-referrals
+    /// (shared device or phone) are still recorded, as `REJECTED`, so they
+    /// show up in reporting and cannot be retried with another code.
+    /// Devices come from the login history, never from the request.
+    pub async fn attribute(
+        repo: &dyn UserRepository,
+        referee: &user::Model,
+        raw_code: &str,
+    ) -> AppResult<referral::Model> {
+        let code = Self::normalize_code(raw_code);
+        let referrer = repo
+            .find_by_referral_code(&code)
+            .await?
+            .ok_or(AppError::BadRequest("Unknown referral code".to_string()))?;
+        if referrer.id == referee.id {
+            return Err(AppError::BadRequest(
+                "You cannot use your own referral code".to_string(),
+            ));
+        }
+        if repo.find_referral_by_referee(referee.id).await?.is_some() {
+            return Err(AppError::Conflict(
+                "A referral code was already applied".to_string(),
+            ));
+        }
+        if Self::is_ancestor(repo, referee.id, referrer.id).await? {
+            return Err(AppError::BadRequest(
+                "You cannot use the code of someone you invited".to_string(),
+            ));
+        }
+
+        let shared_device = repo.find_shared_device(referee.id, referrer.id).await?;
+        let rejection = Self::detect_fraud(&referrer, referee, shared_device.is_some());
+        let created = repo
+            .create_referral(referral::ActiveModel {
+                referrer_id: Set(referrer.id),
+                referee_id: Set(referee.id),
+                code: Set(code),
+                status: Set(if rejection.is_some() {
+                    ReferralStatus::Rejected
+                } else {
+                    ReferralStatus::Pending
+                }),
+                rejection_reason: Set(rejection),
+                device_id: Set(shared_device),
+                created_at: Set(chrono::Utc::now().naive_utc()),
+                converted_at: Set(None),
+                ..Default::default()
+            })
+            .await;
+        match created {
+            Err(AppError::DbError(e))
+                if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+            {
+                Err(AppError::Conflict(
+                    "A referral code was already applied".to_string(),
+                ))
+            }
+            other => other,
+        }
+    }
+
+    /// Onboarding entry point for users who did not sign up through a link.
+    pub async fn redeem(
+        repo: &dyn UserRepository,
+        uuid: &str,
+        raw_code: &str,
+    ) -> AppResult<referral::Model> {
+        let user = repo.find_by_uuid(uuid).await?.ok_or(AppError::NotFound)?;
+        let deadline = user.created_at + chrono::Duration::days(REDEEM_WINDOW_DAYS);
+        if chrono::Utc::now().naive_utc() >= deadline {
+            return Err(AppError::BadRequest(format!(
+                "Referral codes can only be entered within {} days of sign-up",
+                REDEEM_WINDOW_DAYS
+            )));
+        }
+        Self::attribute(repo, &user, raw_code).await
+    }
+
+    /// To be called by ordering when an invitee's first order completes.
+    /// Fraud checks run again, since a phone number or a shared device may
+    /// have been added since sign-up. Returns `None` when there is no pending referral.
+    /// Pass a transactional repo so the rewards commit with the conversion.
+    pub async fn complete_first_order(
+        repo: &dyn UserRepository,
+        referee_id: i32,
+    ) -> AppResult<Option<referral::Model>> {
+        let Some(pending) = repo
+            .find_referral_by_referee(referee_id)
+            .await?
+            .filter(|r| r.status == ReferralStatus::Pending)
+        else {
+            return Ok(None);
+        };
+        let (Some(referrer), Some(referee)) = (
+            repo.find_by_id(pending.referrer_id).await?,
+            repo.find_by_id(pending.referee_id).await?,
+        ) else {
+            return Ok(None);
+        };
+
+        let now = chrono::Utc::now().naive_utc();
+        let shares_device = repo
+            .find_shared_device(referee.id, referrer.id)
+            .await?
+            .is_some();
+        if let Some(reason) = Self::detect_fraud(&referrer, &referee, shares_device) {
+            tracing::warn!(
+                "Referral {} rejected at conversion: {:?}",
+                pending.id,
+                reason
+            );
+            return repo
+                .update_referral(referral::ActiveModel {
+                    id: Unchanged(pending.id),
+                    status: Set(ReferralStatus::Rejected),
+                    rejection_reason: Set(Some(reason)),
+                    ..Default::default()
+                })
+                .await
+                .map(Some);
+        }
+
+        let converted = repo
+            .update_referral(referral::ActiveModel {
+                id: Unchanged(pending.id),
+                status: Set(ReferralStatus::Converted),
+                converted_at: Set(Some(now)),
+                ..Default::default()
+            })
+            .await?;
+        for (user_id, role, points) in [
+            (referrer.id, RewardRole::Referrer, REFERRER_REWARD_POINTS),
+            (referee.id, RewardRole::Referee, REFEREE_REWARD_POINTS),
+        ] {
+            repo.create_referral_reward(referral_reward::ActiveModel {
+                referral_id: Set(converted.id),
+                user_id: Set(user_id),
+                role: Set(role),
+                points: Set(points),
+                created_at: Set(now),
+                ..Default::default()
+            })
+            .await?;
+        }
+        Ok(Some(converted))
+    }
+
+    pub async fn summary(
+        repo: &dyn UserRepository,
+        user: &user::Model,
+    ) -> AppResult<ReferralSummary> {
+        let code = Self::code_for(repo, user).await?;
+        let invitees = repo.find_referrals_by_referrer(user.id).await?;
+        let rewards = repo.find_referral_rewards_by_user(user.id).await?;
+
+        Ok(ReferralSummary {
+            code,
+            invited: invitees
+                .iter()
+                .filter(|r| r.status != ReferralStatus::Rejected)
+                .count(),
+            converted: invitees
+                .iter()
+                .filter(|r| r.status == ReferralStatus::Converted)
+                .count(),
+            points_earned: rewards.iter().map(|r| r.points as i64).sum(),
+        })
+    }
+
+    pub async fn report(
+        repo: &dyn UserRepository,
+        from: chrono::NaiveDateTime,
+        to: chrono::NaiveDateTime,
+    ) -> AppResult<ReferralReport> {
+        if from >= to {
+            return Err(AppError::BadRequest(
+                "`from` must be before `to`".to_string(),
+            ));
+        }
+        let referrals = repo.find_referrals_created_between(from, to).await?;
+
+        let count = |pred: &dyn Fn(&referral::Model) -> bool| {
+            referrals.iter().filter(|r| pred(r)).count() as u64
+        };
+        let attributed = referrals.len() as u64;
+        let converted = count(&|r| r.status == ReferralStatus::Converted);
+
+        let mut per_referrer: HashMap<i32, (u64, u64)> = HashMap::new();
+        for r in referrals
+            .iter()
+            .filter(|r| r.status != ReferralStatus::Rejected)
+        {
+            let entry = per_referrer.entry(r.referrer_id).or_default();
+            entry.0 += 1;
+            if r.status == ReferralStatus::Converted {
+                entry.1 += 1;
+            }
+        }
+        let mut ranked: Vec<_> = per_referrer.into_iter().collect();
+        ranked.sort_by(|(a_id, a), (b_id, b)| (b.1, b.0).cmp(&(a.1, a.0)).then(a_id.cmp(b_id)));
+        let mut top_referrers = Vec::new();
+        for (referrer_id, (attributed, converted)) in ranked.into_iter().take(REPORT_TOP_REFERRERS)
+        {
+            if let Some(user) = repo.find_by_id(referrer_id).await? {
+                top_referrers.push(ReferrerStats {
+                    user_uuid: user.uuid,
+                    attributed,
+                    converted,
+                });
+            }
+        }
+
+        Ok(ReferralReport {
+            from,
+            to,
+            attributed,
+            pending: count(&|r| r.status == ReferralStatus::Pending),
+            converted,
+            rejected_same_device: count(&|r| {
+                r.rejection_reason == Some(ReferralRejection::SameDevice)
+            }),
+            rejected_same_phone: count(&|r| {
+                r.rejection_reason == Some(ReferralRejection::SamePhone)
+            }),
+            conversion_rate: if attributed == 0 {
+                0.0
+            } else {
+                converted as f64 / attributed as f64
+            },
+            top_referrers,
+        })
+    }
+
+    fn detect_fraud(
+        referrer: &user::Model,
+        referee: &user::Model,
+        shares_device: bool,
+    ) -> Option<ReferralRejection> {
+        if !referee.phone_number.is_empty() && referee.phone_number == referrer.phone_number {
+            return Some(ReferralRejection::SamePhone);
+        }
+        if shares_device {
+            return Some(ReferralRejection::SameDevice);
+        }
+        None
+    }
+
+    /// Whether `ancestor_id` is up the referral tree from `user_id`.
+    async fn is_ancestor(
+        repo: &dyn UserRepository,
+        ancestor_id: i32,
+        user_id: i32,
+    ) -> AppResult<bool> {
+        let mut current = user_id;
+        for _ in 0..MAX_TREE_DEPTH {
+            match repo.find_referral_by_referee(current).await? {
+                Some(edge) if edge.referrer_id == ancestor_id => return Ok(true),
+                Some(edge) => current = edge.referrer_id,
+                None => return Ok(false),
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::dtos::SocialLoginDto;
+    use crate::modules::users::infra::fixtures::kakao_login;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::modules::users::service::UserService;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    fn login(id: &str, phone: Option<&str>, device: &str, code: Option<String>) -> SocialLoginDto {
+        SocialLoginDto {
+            phone_number: phone.map(str::to_string),
+            device_id: Some(device.to_string()),
+            referral_code: code,
+            ..kakao_login(id)
+        }
+    }
+
+    #[test]
+    fn test_codes_are_readable() {
+        let code = ReferralService::generate_code();
+        assert_eq!(code.len(), REFERRAL_CODE_LEN);
+        assert!(!code.contains(['0', 'O', '1', 'I']));
+        assert_eq!(ReferralService::normalize_code(" abcd-2345 "), "ABCD2345");
+    }
+
+    #[tokio::test]
+    async fn test_signup_attribution_and_conversion() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let referrer =
+            UserService::handle_social_login(&repo, &manager, login("1", None, "d1", None))
+                .await
+                .unwrap()
+                .user;
+        let code = referrer.referral_code.clone().unwrap();
+
+        let referee = UserService::handle_social_login(
+            &repo,
+            &manager,
+            login("2", None, "d2", Some(code.to_lowercase())),
+        )
+        .await
+        .unwrap()
+        .user;
+        let pending = repo
+            .find_referral_by_referee(referee.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pending.referrer_id, referrer.id);
+        assert_eq!(pending.status, ReferralStatus::Pending);
+
+        // The invitee cannot turn the tree into a cycle.
+        assert!(matches!(
+            ReferralService::redeem(
+                &repo,
+                &referrer.uuid,
+                referee.referral_code.as_deref().unwrap()
+            )
+            .await,
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            ReferralService::redeem(&repo, &referee.uuid, &code).await,
+            Err(AppError::Conflict(_))
+        ));
+
+        let converted = ReferralService::complete_first_order(&repo, referee.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(converted.status, ReferralStatus::Converted);
+        assert!(
+            ReferralService::complete_first_order(&repo, referee.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let summary = ReferralService::summary(&repo, &referrer).await.unwrap();
+        assert_eq!((summary.invited, summary.converted), (1, 1));
+        assert_eq!(summary.points_earned, REFERRER_REWARD_POINTS as i64);
+        assert_eq!(
+            ReferralService::summary(&repo, &referee)
+                .await
+                .unwrap()
+                .points_earned,
+            REFEREE_REWARD_POINTS as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_device_or_phone_is_rejected() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let referrer = UserService::handle_social_login(
+            &repo,
+            &manager,
+            login("1", Some("010-1234-5678"), "d1", None),
+        )
+        .await
+        .unwrap()
+        .user;
+        let code = referrer.referral_code.clone().unwrap();
+
+        let same_device = UserService::handle_social_login(
+            &repo,
+            &manager,
+            login("2", None, "d1", Some(code.clone())),
+        )
+        .await
+        .unwrap()
+        .user;
+        let same_phone = UserService::handle_social_login(
+            &repo,
+            &manager,
+            login("3", Some("01012345678"), "d3", Some(code.clone())),
+        )
+        .await
+        .unwrap()
+        .user;
+
+        let reasons = [
+            repo.find_referral_by_referee(same_device.id).await.unwrap(),
+            repo.find_referral_by_referee(same_phone.id).await.unwrap(),
+        ]
+        .map(|r| r.map(|r| (r.status, r.rejection_reason)));
+        assert_eq!(
+            reasons,
+            [
+                Some((
+                    ReferralStatus::Rejected,
+                    Some(ReferralRejection::SameDevice)
+                )),
+                Some((ReferralStatus::Rejected, Some(ReferralRejection::SamePhone))),
+            ]
+        );
+        assert!(
+            ReferralService::complete_first_order(&repo, same_device.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let now = chrono::Utc::now().naive_utc();
+        let report = ReferralService::report(
+            &repo,
+            now - chrono::Duration::hours(1),
+            now + chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.attributed, 2);
+        assert_eq!(
+            (report.rejected_same_device, report.rejected_same_phone),
+            (1, 1)
+        );
+        assert!(report.top_referrers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redeem_checks_recorded_devices() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let referrer =
+            UserService::handle_social_login(&repo, &manager, login("1", None, "d1", None))
+                .await
+                .unwrap()
+                .user;
+        let code = referrer.referral_code.clone().unwrap();
+
+        // Signed up elsewhere, then logged in on the referrer's phone.
+        let referee =
+            UserService::handle_social_login(&repo, &manager, login("2", None, "d2", None))
+                .await
+                .unwrap()
+                .user;
+        UserService::handle_social_login(&repo, &manager, login("2", None, "d1", None))
+            .await
+            .unwrap();
+        let redeemed = ReferralService::redeem(&repo, &referee.uuid, &code)
+            .await
+            .unwrap();
+        assert_eq!(redeemed.status, ReferralStatus::Rejected);
+        assert_eq!(
+            redeemed.rejection_reason,
+            Some(ReferralRejection::SameDevice)
+        );
+        assert_eq!(redeemed.device_id.as_deref(), Some("d1"));
+
+        // A device shared after attribution is caught at conversion.
+        let late = UserService::handle_social_login(
+            &repo,
+            &manager,
+            login("3", None, "d3", Some(code.clone())),
+        )
+        .await
+        .unwrap()
+        .user;
+        UserService::handle_social_login(&repo, &manager, login("1", None, "d3", None))
+            .await
+            .unwrap();
+        let rejected = ReferralService::complete_first_order(&repo, late.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            rejected.rejection_reason,
+            Some(ReferralRejection::SameDevice)
+        );
+    }
+}
@@ -1,10 +1,16 @@
-use super::entities::{social, user, verification};
+use super::dtos::{UserPage, UserSearchQuery};
+use super::entities::{
+    notification_setting, referral, referral_reward, social, status_transition, user, verification,
+};
 use crate::shared::error::AppResult;
 
 crate::define_repo!(UserRepository, {
     async fn find_by_id(&self, id: i32) -> AppResult<Option<user::Model>>;
     async fn find_by_uuid(&self, uuid: &str) -> AppResult<Option<user::Model>>;
     async fn find_by_email(&self, email: &str) -> AppResult<Option<user::Model>>;
+    /// Looks up a canonical handle (see `HandleService::normalize`).
+    async fn find_by_handle(&self, handle: &str) -> AppResult<Option<user::Model>>;
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<user::Model>>;
     async fn find_social(
         &self,
         provider: social::SocialProvider,
@@ -18,6 +24,9 @@ crate::define_repo!(UserRepository, {
         verification: verification::ActiveModel,
     ) -> AppResult<user::Model>;
 
+    /// Writes the set columns. A set `account_status` must be a legal
+    /// transition from the stored one (see `AccountStatus::allowed_sources`),
+    /// otherwise nothing is written and `Conflict` is returned.
     async fn update_user(&self, user: user::ActiveModel) -> AppResult<user::Model>;
 
     async fn find_with_details_by_uuid(&self, uuid: &str) -> AppResult<Option<user::Model>>;
@@ -26,4 +35,89 @@ crate::define_repo!(UserRepository, {
         &self,
         verification: verification::ActiveModel,
     ) -> AppResult<verification::Model>;
+
+    /// Stamps a successful login and bumps the counter atomically.
+    async fn record_login(
+        &self,
+        user_id: i32,
+        provider: social::SocialProvider,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<user::Model>;
+
+    /// Moves active users whose last activity is before `idle_before` to
+    /// dormant, recording a system transition for each. Returns how many
+    /// accounts were affected.
+    async fn mark_dormant(
+        &self,
+        idle_before: chrono::NaiveDateTime,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<u64>;
+
+    async fn find_notification_setting(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Option<notification_setting::Model>>;
+
+    /// Inserts the user's settings row, or replaces it if one exists.
+    async fn save_notification_setting(
+        &self,
+        setting: notification_setting::ActiveModel,
+    ) -> AppResult<notification_setting::Model>;
+
+    async fn create_status_transition(
+        &self,
+        transition: status_transition::ActiveModel,
+    ) -> AppResult<status_transition::Model>;
+
+    /// Oldest first.
+    async fn find_status_transitions(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<status_transition::Model>>;
+
+    /// Adds the device to the user's list, or bumps its `last_seen_at`.
+    async fn record_device(
+        &self,
+        user_id: i32,
+        device_id: &str,
+        at: chrono::NaiveDateTime,
+    ) -> AppResult<()>;
+
+    /// A device both users have signed in on, if any.
+    async fn find_shared_device(
+        &self,
+        user_id: i32,
+        other_user_id: i32,
+    ) -> AppResult<Option<String>>;
+
+    async fn find_referral_by_referee(&self, referee_id: i32)
+    -> AppResult<Option<referral::Model>>;
+
+    /// Direct invitees of a user, oldest first.
+    async fn find_referrals_by_referrer(&self, referrer_id: i32)
+    -> AppResult<Vec<referral::Model>>;
+
+    /// Referrals attributed in `[from, to)`.
+    async fn find_referrals_created_between(
+        &self,
+        from: chrono::NaiveDateTime,
+        to: chrono::NaiveDateTime,
+    ) -> AppResult<Vec<referral::Model>>;
+
+    async fn create_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model>;
+
+    async fn update_referral(&self, referral: referral::ActiveModel) -> AppResult<referral::Model>;
+
+    async fn create_referral_reward(
+        &self,
+        reward: referral_reward::ActiveModel,
+    ) -> AppResult<referral_reward::Model>;
+
+    async fn find_referral_rewards_by_user(
+        &self,
+        user_id: i32,
+    ) -> AppResult<Vec<referral_reward::Model>>;
+
+    /// Filtered, keyset-paginated listing for admin tooling.
+    async fn search_users(&self, query: &UserSearchQuery) -> AppResult<UserPage>;
 });
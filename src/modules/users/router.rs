@@ -1,13 +1,56 @@
+use crate::modules::users::avatar::AVATAR_MAX_BYTES;
 use crate::shared::{middleware::require_email_verified, state::AppState};
-use axum::{Router, middleware};
+use axum::{Router, extract::DefaultBodyLimit, middleware};
+
+/// Room for multipart boundaries and headers around the image itself.
+const AVATAR_BODY_LIMIT: usize = AVATAR_MAX_BYTES + 64 * 1024;
 
 pub fn router(state: AppState) -> Router {
     Router::new()
         .route(
             "/me",
-            axum::routing::get(super::handlers::get_me).route_layer(
+            axum::routing::patch(super::handlers::update_me).route_layer(
                 middleware::from_fn_with_state(state.clone(), require_email_verified),
             ),
         )
+        .route(
+            "/me/handle",
+            axum::routing::put(super::handlers::update_my_handle).route_layer(
+                middleware::from_fn_with_state(state.clone(), require_email_verified),
+            ),
+        )
+        .route(
+            "/me/avatar",
+            axum::routing::put(super::handlers::upload_my_avatar)
+                .delete(super::handlers::delete_my_avatar)
+                .layer(DefaultBodyLimit::max(AVATAR_BODY_LIMIT))
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_email_verified,
+                )),
+        )
+        .route(
+            "/me/notification-settings",
+            axum::routing::get(super::handlers::get_my_notification_settings)
+                .put(super::handlers::update_my_notification_settings)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_email_verified,
+                )),
+        )
+        .route(
+            "/me/referral",
+            axum::routing::get(super::handlers::get_my_referral)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_email_verified,
+                ))
+                .post(super::handlers::redeem_referral),
+        )
+        .route(
+            "/handles/:handle/availability",
+            axum::routing::get(super::handlers::check_handle_availability),
+        )
+        .route("/:uuid", axum::routing::get(super::handlers::get_user))
         .with_state(state)
 }
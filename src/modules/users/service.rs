@@ -1,63 +1,95 @@
-use crate::modules::users::dtos::SocialLoginDto;
+use crate::modules::users::dtos::{SocialLoginDto, SocialLoginResult, UpdateProfileDto};
 use crate::modules::users::entities::{
+    enums::{AccountStatus, UserRole},
     social::{self},
-    user,
+    user, verification,
 };
+use crate::modules::users::referral::ReferralService;
 use crate::modules::users::repository::UserRepository;
+use crate::modules::users::status::{AccountStatusService, StatusActor, StatusChange};
+use crate::modules::users::utils::{
+    normalize_country_code, normalize_locale, normalize_phone_e164,
+};
 use crate::shared::error::{AppError, AppResult};
-use sea_orm::ActiveValue::Set;
+use crate::shared::repository::RepositoryManager;
+use sea_orm::ActiveValue::{Set, Unchanged};
+
+pub const DEFAULT_LOCALE: &str = "ko-KR";
+pub const USERNAME_MAX_CHARS: usize = 20;
+/// Longer device ids are ignored rather than stored.
+pub const DEVICE_ID_MAX_LEN: usize = 128;
 
 pub struct UserService;
 
 impl UserService {
     pub async fn handle_social_login(
         repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
         login_dto: SocialLoginDto,
-    ) -> AppResult<user::Model> {
+    ) -> AppResult<SocialLoginResult> {
+        let device_id = login_dto
+            .device_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty() && d.len() <= DEVICE_ID_MAX_LEN)
+            .map(str::to_string);
+
         // 1. Check if Social Account exists
         let social_account = repo
             .find_social(login_dto.provider.clone(), &login_dto.provider_id)
             .await?;
 
         if let Some(social) = social_account {
-            let user =
-                repo.find_by_id(social.user_id)
-                    .await?
-                    .ok_or(AppError::InternalServerError(
-                        "User not found for social account".to_string(),
-                    ))?;
+            let user = repo
+                .record_login(
+                    social.user_id,
+                    login_dto.provider.clone(),
+                    chrono::Utc::now().naive_utc(),
+                )
+                .await?;
+            if let Some(device_id) = &device_id {
+                repo.record_device(user.id, device_id, chrono::Utc::now().naive_utc())
+                    .await?;
+            }
+            if matches!(
+                user.account_status,
+                AccountStatus::Dormant | AccountStatus::Banned
+            ) {
+                Self::activate_on_login(repo, repo_manager, user.clone()).await?;
+            }
+            let mut user = repo.find_with_details_by_uuid(&user.uuid).await?.ok_or(
+                AppError::InternalServerError("User not found for social account".to_string()),
+            )?;
+            Self::refresh_age(repo, &mut user, &login_dto).await?;
+            let email_verified = user.verification.as_ref().is_some_and(|v| v.email_verified);
 
             // Logic a: Check status
-            match user.account_status {
-                crate::modules::users::entities::enums::AccountStatus::Active => return Ok(user),
-                crate::modules::users::entities::enums::AccountStatus::Pending => {
-                    // TODO: Re-trigger Email Verification Logic here
-                    // For now, just return user to allow login/proceed (or maybe we shouldn't login?)
-                    // The requirement says: "별도의 DB 삽입없이 Email 인증만 새로 생성하고"
-                    // implies we might just want to send email again.
-                    // But if this is "Login", we probably return the user so they can theoretically get a token?
-                    // OR if the flow is Strict Registration, maybe we return OK but frontend handles it?
-                    // Assuming we return User for now.
-                    return Ok(user);
+            // Pending users resume email verification without any DB insert.
+            // The code goes to the address on file, which is the one being
+            // verified, even if the provider now reports another.
+            let verification_email = match user.account_status {
+                AccountStatus::Pending if !email_verified => {
+                    Some(user.email.clone()).filter(|e| !e.is_empty())
                 }
-                _ => return Ok(user), // Inactive/Suspended?
-            }
+                _ => None,
+            };
+
+            return Ok(SocialLoginResult {
+                user,
+                verification_email,
+            });
         }
 
         // 2. Create new User
-        // UUID Logic: SHA512(provider_id|unix|JAY|PROP|connected_at)
-        let connected_at = login_dto
-            .connected_at
-            .clone()
-            .ok_or(AppError::BadRequest("Missing connected_at".to_string()))?;
-        let new_uuid =
-            crate::modules::users::utils::generate_user_uuid(&login_dto.provider_id, &connected_at);
+        let new_uuid = crate::modules::users::utils::generate_user_uuid();
 
         let now = chrono::Utc::now().naive_utc();
+        let birth_year = Self::parse_birth_year(&login_dto);
+        let has_age = login_dto.age_range.is_some() || birth_year.is_some();
 
         // Prepare User ActiveModel
         let username = login_dto.name.unwrap_or_else(|| "User".to_string());
-        let email = login_dto.email.unwrap_or_else(|| "".to_string());
+        let email = login_dto.email.unwrap_or_default();
 
         if username == "User" {
             return Err(AppError::BadRequest(
@@ -68,14 +100,33 @@ impl UserService {
         // Prepare User ActiveModel
         let new_user = user::ActiveModel {
             uuid: Set(new_uuid),
+            legacy_uuid: Set(None),
             username: Set(username),
+            handle: Set(None),
+            handle_changed_at: Set(None),
+            referral_code: Set(Some(ReferralService::generate_code())),
+            avatar_key: Set(None),
+            avatar_thumbnail_key: Set(None),
+            avatar_updated_at: Set(None),
             email: Set(email),
             country_code: Set("".to_string()),
-            phone_number: Set(login_dto.phone_number.unwrap_or_else(|| "".to_string())),
-            account_status: Set(crate::modules::users::entities::enums::AccountStatus::Pending),
+            phone_number: Set(login_dto
+                .phone_number
+                .map(|p| normalize_phone_e164(&p).unwrap_or(p))
+                .unwrap_or_default()),
+            locale: Set(DEFAULT_LOCALE.to_string()),
+            account_status: Set(AccountStatus::Pending),
+            role: Set(UserRole::User),
             created_at: Set(now),
             updated_at: Set(now),
             last_login_at: Set(Some(now)),
+            login_count: Set(1),
+            last_login_provider: Set(Some(login_dto.provider.clone())),
+            dormant_at: Set(None),
+            banned_until: Set(None),
+            age_range: Set(login_dto.age_range),
+            birth_year: Set(birth_year),
+            age_updated_at: Set(has_age.then_some(now)),
             ..Default::default()
         };
 
@@ -102,8 +153,381 @@ impl UserService {
             .create_user_with_verification(new_user.clone(), Some(new_social), new_verification)
             .await?;
 
-        // TODO: Send Verification Email here
+        if let Some(device_id) = &device_id {
+            repo.record_device(created_user.id, device_id, now).await?;
+        }
+        // A bad invite code must not cost us the sign-up; the user can still
+        // enter a valid one during onboarding.
+        if let Some(code) = login_dto.referral_code.filter(|c| !c.trim().is_empty())
+            && let Err(e) = ReferralService::attribute(repo, &created_user, &code).await
+        {
+            tracing::warn!("Referral code {} not applied at sign-up: {}", code, e);
+        }
+
+        let verification_email = Some(created_user.email.clone()).filter(|e| !e.is_empty());
+
+        Ok(SocialLoginResult {
+            user: created_user,
+            verification_email,
+        })
+    }
+
+    /// Brings a dormant (휴면) account, or one whose ban has run out, back
+    /// to active on login. The change and its audit row commit together. If
+    /// a concurrent login already activated the account, that is success.
+    async fn activate_on_login(
+        repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        user: user::Model,
+    ) -> AppResult<()> {
+        let user_id = user.id;
+        let uow = repo_manager.begin().await?;
+        let tx_repo = repo
+            .with_transaction(&*uow)
+            .ok_or(AppError::InternalServerError(
+                "Failed to start transaction for user repo".to_string(),
+            ))?;
+        let result = match user.account_status {
+            AccountStatus::Dormant => {
+                AccountStatusService::transition(
+                    tx_repo.as_ref(),
+                    user,
+                    StatusChange::new(
+                        AccountStatus::Active,
+                        StatusActor::User(user_id),
+                        "Logged in while dormant",
+                    ),
+                )
+                .await
+            }
+            _ => AccountStatusService::lift_expired_ban(tx_repo.as_ref(), user).await,
+        };
+        match result {
+            Ok(_) => uow.commit().await,
+            Err(e) => {
+                uow.rollback().await?;
+                match e {
+                    AppError::Conflict(_)
+                        if repo
+                            .find_by_id(user_id)
+                            .await?
+                            .is_some_and(|u| u.account_status == AccountStatus::Active) =>
+                    {
+                        Ok(())
+                    }
+                    e => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Validates and applies a self-service profile edit in one transaction.
+    /// Changing the phone number drops its verification.
+    pub async fn update_profile(
+        repo: &dyn UserRepository,
+        repo_manager: &dyn RepositoryManager,
+        uuid: &str,
+        dto: UpdateProfileDto,
+    ) -> AppResult<user::Model> {
+        let user = repo
+            .find_with_details_by_uuid(uuid)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let mut user_active = user::ActiveModel {
+            id: Unchanged(user.id),
+            ..Default::default()
+        };
+        let mut changed = false;
+
+        if let Some(username) = dto.username {
+            let username = username.trim().to_string();
+            let len = username.chars().count();
+            if !(2..=USERNAME_MAX_CHARS).contains(&len) || username.chars().any(char::is_control) {
+                return Err(AppError::BadRequest(format!(
+                    "Username must be 2-{} characters",
+                    USERNAME_MAX_CHARS
+                )));
+            }
+            if username != user.username {
+                user_active.username = Set(username);
+                changed = true;
+            }
+        }
+
+        if let Some(country_code) = dto.country_code {
+            let country_code = normalize_country_code(&country_code)
+                .ok_or(AppError::BadRequest("Invalid country code".to_string()))?;
+            if country_code != user.country_code {
+                user_active.country_code = Set(country_code);
+                changed = true;
+            }
+        }
+
+        if let Some(locale) = dto.locale {
+            let locale = normalize_locale(&locale)
+                .ok_or(AppError::BadRequest("Invalid locale".to_string()))?;
+            if locale != user.locale {
+                user_active.locale = Set(locale);
+                changed = true;
+            }
+        }
+
+        let mut verification_reset = None;
+        if let Some(phone_number) = dto.phone_number {
+            let phone_number = normalize_phone_e164(&phone_number)
+                .ok_or(AppError::BadRequest("Invalid phone number".to_string()))?;
+            if phone_number != user.phone_number {
+                user_active.phone_number = Set(phone_number);
+                changed = true;
+
+                if let Some(v) = user.verification.clone() {
+                    let mut v: verification::ActiveModel = v.into();
+                    v.phone_verified = Set(false);
+                    v.phone_verified_at = Set(None);
+                    verification_reset = Some(v);
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(user);
+        }
+        user_active.updated_at = Set(chrono::Utc::now().naive_utc());
+
+        let uow = repo_manager.begin().await?;
+        let tx_user_repo = repo
+            .with_transaction(&*uow)
+            .ok_or(AppError::InternalServerError(
+                "Failed to start transaction for user repo".to_string(),
+            ))?;
+
+        let result = async {
+            tx_user_repo.update_user(user_active).await?;
+            if let Some(v) = verification_reset {
+                tx_user_repo.update_verification(v).await?;
+            }
+            Ok::<_, AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => uow.commit().await?,
+            Err(e) => {
+                uow.rollback().await?;
+                return Err(e);
+            }
+        }
+
+        repo.find_with_details_by_uuid(uuid)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    fn parse_birth_year(login_dto: &SocialLoginDto) -> Option<i32> {
+        login_dto
+            .birthyear
+            .as_deref()
+            .and_then(|y| y.trim().parse().ok())
+    }
+
+    /// Overwrites the stored age data with whatever the provider reported on
+    /// this login. Values the provider did not send are kept as they are.
+    async fn refresh_age(
+        repo: &dyn UserRepository,
+        user: &mut user::Model,
+        login_dto: &SocialLoginDto,
+    ) -> AppResult<()> {
+        let birth_year = Self::parse_birth_year(login_dto);
+        if login_dto.age_range.is_none() && birth_year.is_none() {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let age_range = login_dto.age_range.clone().or(user.age_range.clone());
+        let birth_year = birth_year.or(user.birth_year);
+
+        repo.update_user(user::ActiveModel {
+            id: Unchanged(user.id),
+            age_range: Set(age_range.clone()),
+            birth_year: Set(birth_year),
+            age_updated_at: Set(Some(now)),
+            ..Default::default()
+        })
+        .await?;
+
+        user.age_range = age_range;
+        user.birth_year = birth_year;
+        user.age_updated_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::infra::fixtures;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+    use crate::modules::users::onboarding::OnboardingStep;
+    use crate::shared::infra::repository::InMemoryRepositoryManager;
+
+    fn login(email: Option<&str>) -> SocialLoginDto {
+        SocialLoginDto {
+            email: email.map(str::to_string),
+            age_range: Some("20~29".to_string()),
+            ..fixtures::kakao_login("12345")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_user_requests_verification_email() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+
+        let result = UserService::handle_social_login(&repo, &manager, login(Some("a@gimme.com")))
+            .await
+            .unwrap();
+
+        assert_eq!(result.user.account_status, AccountStatus::Pending);
+        assert_eq!(result.verification_email.as_deref(), Some("a@gimme.com"));
+        assert_eq!(
+            OnboardingStep::remaining_for(&result.user, false),
+            vec![
+                OnboardingStep::EmailVerification,
+                OnboardingStep::PhoneVerification
+            ]
+        );
+        assert_eq!(
+            OnboardingStep::remaining_for(&result.user, true).last(),
+            Some(&OnboardingStep::TermsAgreement)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pending_user_resumes_verification_on_login() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        UserService::handle_social_login(&repo, &manager, login(Some("a@gimme.com")))
+            .await
+            .unwrap();
+
+        // The stored address is verified, not whatever the provider reports now.
+        let again = UserService::handle_social_login(&repo, &manager, login(Some("b@gimme.com")))
+            .await
+            .unwrap();
+        assert_eq!(again.verification_email.as_deref(), Some("a@gimme.com"));
+        let again = UserService::handle_social_login(&repo, &manager, login(None))
+            .await
+            .unwrap();
+        assert_eq!(again.verification_email.as_deref(), Some("a@gimme.com"));
+
+        let no_email_login = || SocialLoginDto {
+            provider_id: "67890".to_string(),
+            ..login(None)
+        };
+        UserService::handle_social_login(&repo, &manager, no_email_login())
+            .await
+            .unwrap();
+        let without_email = UserService::handle_social_login(&repo, &manager, no_email_login())
+            .await
+            .unwrap();
+        assert!(without_email.verification_email.is_none());
+        assert!(
+            OnboardingStep::remaining_for(&without_email.user, false)
+                .contains(&OnboardingStep::EmailVerification)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_normalizes_and_resets_phone_verification() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let created = UserService::handle_social_login(&repo, &manager, login(Some("a@gimme.com")))
+            .await
+            .unwrap()
+            .user;
+
+        let mut verification: verification::ActiveModel = repo
+            .find_with_details_by_uuid(&created.uuid)
+            .await
+            .unwrap()
+            .unwrap()
+            .verification
+            .unwrap()
+            .into();
+        verification.phone_verified = Set(true);
+        repo.update_verification(verification).await.unwrap();
+
+        let updated = UserService::update_profile(
+            &repo,
+            &manager,
+            &created.uuid,
+            UpdateProfileDto {
+                username: Some(" 김기미 ".to_string()),
+                country_code: Some("kr".to_string()),
+                locale: Some("en_us".to_string()),
+                phone_number: Some("010-1234-5678".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated.username, "김기미");
+        assert_eq!(updated.country_code, "KR");
+        assert_eq!(updated.locale, "en-US");
+        assert_eq!(updated.phone_number, "+821012345678");
+        assert!(!updated.verification.unwrap().phone_verified);
+
+        let invalid = UserService::update_profile(
+            &repo,
+            &manager,
+            &created.uuid,
+            UpdateProfileDto {
+                username: None,
+                country_code: None,
+                locale: Some("korean".to_string()),
+                phone_number: None,
+            },
+        )
+        .await;
+        assert!(matches!(invalid, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_login_reactivation_is_audited_and_tolerates_a_racing_login() {
+        let repo = InMemoryUserRepository::default();
+        let manager = InMemoryRepositoryManager::new();
+        let created = fixtures::sign_up(&repo, "dormant").await;
+        let active = AccountStatusService::transition(
+            &repo,
+            created,
+            StatusChange::new(AccountStatus::Active, StatusActor::System, "verified"),
+        )
+        .await
+        .unwrap();
+        let stale = AccountStatusService::transition(
+            &repo,
+            active,
+            StatusChange::new(AccountStatus::Dormant, StatusActor::System, "idle"),
+        )
+        .await
+        .unwrap();
+
+        let result =
+            UserService::handle_social_login(&repo, &manager, fixtures::kakao_login("dormant"))
+                .await
+                .unwrap();
+        assert_eq!(result.user.account_status, AccountStatus::Active);
+        let audited = repo.find_status_transitions(stale.id).await.unwrap();
+        assert_eq!(audited.len(), 3);
 
-        Ok(created_user)
+        // A second login that read the account while it was still dormant.
+        UserService::activate_on_login(&repo, &manager, stale.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find_status_transitions(stale.id).await.unwrap().len(),
+            3
+        );
     }
 }
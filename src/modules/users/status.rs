@@ -0,0 +1,256 @@
+use sea_orm::ActiveValue::{Set, Unchanged};
+
+use super::entities::{
+    enums::AccountStatus,
+    status_transition::{self, StatusActorType},
+    user,
+};
+use super::repository::UserRepository;
+use crate::shared::error::{AppError, AppResult};
+
+/// Who caused a status change, recorded with every transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusActor {
+    System,
+    User(i32),
+    Admin(i32),
+}
+
+impl StatusActor {
+    fn parts(self) -> (StatusActorType, Option<i32>) {
+        match self {
+            Self::System => (StatusActorType::System, None),
+            Self::User(id) => (StatusActorType::User, Some(id)),
+            Self::Admin(id) => (StatusActorType::Admin, Some(id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusChange {
+    pub to: AccountStatus,
+    pub actor: StatusActor,
+    pub reason: String,
+    /// Required when `to` is `Banned`, ignored otherwise.
+    pub banned_until: Option<chrono::NaiveDateTime>,
+}
+
+impl StatusChange {
+    pub fn new(to: AccountStatus, actor: StatusActor, reason: impl Into<String>) -> Self {
+        Self {
+            to,
+            actor,
+            reason: reason.into(),
+            banned_until: None,
+        }
+    }
+}
+
+pub struct AccountStatusService;
+
+impl AccountStatusService {
+    /// The only way account status should change outside bulk jobs.
+    /// Checks the transition (a temporary ban ends only once it has expired),
+    /// maintains `dormant_at`/`banned_until` and appends an audit row.
+    /// Pass a transactional repo to make the audit row atomic with the change.
+    pub async fn transition(
+        repo: &dyn UserRepository,
+        user: user::Model,
+        change: StatusChange,
+    ) -> AppResult<user::Model> {
+        let now = chrono::Utc::now().naive_utc();
+        let from = user.account_status.clone();
+        let to = change.to;
+
+        let reason = change.reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::BadRequest(
+                "A reason is required for status changes".to_string(),
+            ));
+        }
+        if !from.can_transition_to(&to) {
+            return Err(AppError::Conflict(format!(
+                "Illegal account status transition from {:?} to {:?}",
+                from, to
+            )));
+        }
+        if from == AccountStatus::Banned
+            && to == AccountStatus::Active
+            && let Some(until) = user.banned_until.filter(|until| *until > now)
+        {
+            return Err(AppError::Conflict(format!(
+                "Ban lasts until {}",
+                until.format("%Y-%m-%d %H:%M UTC")
+            )));
+        }
+        let banned_until = match to {
+            AccountStatus::Banned => Some(change.banned_until.filter(|until| *until > now).ok_or(
+                AppError::BadRequest("A temporary ban needs an end in the future".to_string()),
+            )?),
+            _ => None,
+        };
+
+        let user_active = user::ActiveModel {
+            id: Unchanged(user.id),
+            account_status: Set(to.clone()),
+            banned_until: Set(banned_until),
+            dormant_at: Set((to == AccountStatus::Dormant).then_some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let mut updated = repo.update_user(user_active).await?;
+
+        let (actor_type, actor_id) = change.actor.parts();
+        repo.create_status_transition(status_transition::ActiveModel {
+            user_id: Set(user.id),
+            from_status: Set(from.clone()),
+            to_status: Set(to.clone()),
+            actor_type: Set(actor_type),
+            actor_id: Set(actor_id),
+            reason: Set(reason.to_string()),
+            created_at: Set(now),
+            ..Default::default()
+        })
+        .await?;
+        tracing::info!(
+            "User {} status {:?} -> {:?} by {:?}: {}",
+            user.uuid,
+            from,
+            to,
+            change.actor,
+            reason
+        );
+
+        updated.verification = user.verification;
+        updated.socials = user.socials;
+        Ok(updated)
+    }
+
+    /// Lifts a temporary ban whose end has passed; other users are returned as is.
+    pub async fn lift_expired_ban(
+        repo: &dyn UserRepository,
+        user: user::Model,
+    ) -> AppResult<user::Model> {
+        let now = chrono::Utc::now().naive_utc();
+        if user.account_status != AccountStatus::Banned
+            || user.banned_until.is_none_or(|until| until > now)
+        {
+            return Ok(user);
+        }
+        Self::transition(
+            repo,
+            user,
+            StatusChange::new(AccountStatus::Active, StatusActor::System, "Ban expired"),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::users::infra::fixtures::sign_up;
+    use crate::modules::users::infra::persistence::InMemoryUserRepository;
+
+    #[test]
+    fn test_transition_table() {
+        use AccountStatus::*;
+        assert!(Pending.can_transition_to(&Active));
+        assert!(Active.can_transition_to(&Banned));
+        assert!(Banned.can_transition_to(&Active));
+        assert!(Dormant.can_transition_to(&PermBanned));
+        assert!(!Banned.can_transition_to(&Pending));
+        assert!(!Pending.can_transition_to(&Banned));
+        assert!(!PermBanned.can_transition_to(&Active));
+        assert!(!Active.can_transition_to(&Active));
+    }
+
+    #[tokio::test]
+    async fn test_transitions_are_enforced_and_audited() {
+        let repo = InMemoryUserRepository::default();
+        let user = sign_up(&repo, "status").await;
+        let admin = StatusActor::Admin(99);
+
+        let user = AccountStatusService::transition(
+            &repo,
+            user,
+            StatusChange::new(AccountStatus::Active, StatusActor::System, "verified"),
+        )
+        .await
+        .unwrap();
+
+        let until = chrono::Utc::now().naive_utc() + chrono::Duration::days(7);
+        let banned = AccountStatusService::transition(
+            &repo,
+            user,
+            StatusChange {
+                banned_until: Some(until),
+                ..StatusChange::new(AccountStatus::Banned, admin, "spam")
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(banned.banned_until, Some(until));
+
+        // The ban has not run out yet, and raw writes cannot bypass the rules.
+        assert!(matches!(
+            AccountStatusService::transition(
+                &repo,
+                banned.clone(),
+                StatusChange::new(AccountStatus::Active, admin, "appeal"),
+            )
+            .await,
+            Err(AppError::Conflict(_))
+        ));
+        let mut raw: user::ActiveModel = banned.clone().into();
+        raw.account_status = Set(AccountStatus::Pending);
+        assert!(matches!(
+            repo.update_user(raw).await,
+            Err(AppError::Conflict(_))
+        ));
+        assert_eq!(
+            AccountStatusService::lift_expired_ban(&repo, banned.clone())
+                .await
+                .unwrap()
+                .account_status,
+            AccountStatus::Banned
+        );
+
+        let expired = user::Model {
+            banned_until: Some(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1)),
+            ..banned
+        };
+        let lifted = AccountStatusService::lift_expired_ban(&repo, expired)
+            .await
+            .unwrap();
+        assert_eq!(lifted.account_status, AccountStatus::Active);
+        assert!(lifted.banned_until.is_none());
+
+        let history = repo.find_status_transitions(lifted.id).await.unwrap();
+        let steps: Vec<_> = history
+            .iter()
+            .map(|t| (t.from_status.clone(), t.to_status.clone(), t.actor_type))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    AccountStatus::Pending,
+                    AccountStatus::Active,
+                    StatusActorType::System
+                ),
+                (
+                    AccountStatus::Active,
+                    AccountStatus::Banned,
+                    StatusActorType::Admin
+                ),
+                (
+                    AccountStatus::Banned,
+                    AccountStatus::Active,
+                    StatusActorType::System
+                ),
+            ]
+        );
+        assert_eq!(history[1].actor_id, Some(99));
+    }
+}
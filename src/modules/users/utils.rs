@@ -1,10 +1,110 @@
-use sha2::{Digest, Sha512};
-
-pub fn generate_user_uuid(provider_id: &str, connected_at: &str) -> String {
-    let now_unix = chrono::Utc::now().timestamp();
-    let raw = format!("{}|{}|JAY|PROP|{}", provider_id, now_unix, connected_at);
-    let mut hasher = Sha512::new();
-    hasher.update(raw);
-    let result = hasher.finalize();
-    format!("{:x}", result)
+/// Public user identifier. UUIDv7 keeps ids compact and time-ordered,
+/// which is friendlier to the `users.uuid` index and to JWT size.
+pub fn generate_user_uuid() -> String {
+    uuid::Uuid::now_v7().to_string()
+}
+
+/// Identifiers issued before UUIDv7 were 128-char SHA-512 hex digests.
+/// They stay resolvable through `users.legacy_uuid` during the transition.
+pub fn is_legacy_user_id(id: &str) -> bool {
+    id.len() == 128 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Normalizes a phone number to E.164 (`+821012345678`).
+/// Numbers without a `+` prefix are read as Korean national numbers
+/// (`010-1234-5678`), which is the only national format we accept.
+pub fn normalize_phone_e164(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+    if trimmed
+        .chars()
+        .any(|c| !(c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '(' | ')')))
+    {
+        return None;
+    }
+
+    let e164_digits = if trimmed.starts_with('+') {
+        digits
+    } else if let Some(national) = digits.strip_prefix('0') {
+        format!("82{}", national)
+    } else {
+        return None;
+    };
+
+    // E.164: at most 15 digits, country code never starts with 0.
+    if !(8..=15).contains(&e164_digits.len()) || e164_digits.starts_with('0') {
+        return None;
+    }
+
+    Some(format!("+{}", e164_digits))
+}
+
+/// Uppercases and checks an ISO 3166-1 alpha-2 country code.
+pub fn normalize_country_code(raw: &str) -> Option<String> {
+    let code = raw.trim().to_ascii_uppercase();
+    (code.len() == 2 && code.chars().all(|c| c.is_ascii_uppercase())).then_some(code)
+}
+
+/// Normalizes a `language[-REGION]` locale tag such as `ko-KR` or `en`.
+pub fn normalize_locale(raw: &str) -> Option<String> {
+    let mut parts = raw.trim().split(['-', '_']);
+    let language = parts.next()?.to_ascii_lowercase();
+    let region = parts.next().map(|r| r.to_ascii_uppercase());
+    if parts.next().is_some()
+        || !(2..=3).contains(&language.len())
+        || !language.chars().all(|c| c.is_ascii_lowercase())
+    {
+        return None;
+    }
+
+    match region {
+        Some(region) if region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()) => {
+            Some(format!("{}-{}", language, region))
+        }
+        Some(_) => None,
+        None => Some(language),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_user_uuid_is_time_ordered_v7() {
+        let first = generate_user_uuid();
+        let second = generate_user_uuid();
+        let parsed = uuid::Uuid::parse_str(&first).unwrap();
+        assert_eq!(parsed.get_version_num(), 7);
+        assert_eq!(first.len(), 36);
+        assert!(first < second);
+        assert!(!is_legacy_user_id(&first));
+        assert!(is_legacy_user_id(&"ab".repeat(64)));
+    }
+
+    #[test]
+    fn test_normalize_phone_e164() {
+        assert_eq!(
+            normalize_phone_e164("+82 10-1234-5678").as_deref(),
+            Some("+821012345678")
+        );
+        assert_eq!(
+            normalize_phone_e164("010-1234-5678").as_deref(),
+            Some("+821012345678")
+        );
+        assert_eq!(
+            normalize_phone_e164("+1 (415) 555-2671").as_deref(),
+            Some("+14155552671")
+        );
+        assert_eq!(normalize_phone_e164("1234"), None);
+        assert_eq!(normalize_phone_e164("+82 10-1234-567a"), None);
+    }
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("ko_kr").as_deref(), Some("ko-KR"));
+        assert_eq!(normalize_locale("EN").as_deref(), Some("en"));
+        assert_eq!(normalize_locale("korean"), None);
+        assert_eq!(normalize_locale("ko-KOR"), None);
+    }
 }
@@ -15,9 +15,31 @@ pub struct Config {
     pub app_env: String,
     pub kakao_client_id: String,
     pub kakao_redirect_uri: String,
+    pub kakao_auth_base_url: String,
+    pub kakao_api_base_url: String,
+    pub oauth_http_timeout_ms: u64,
+    pub oauth_http_max_retries: u32,
     pub gmail_user: String,
     pub gmail_app_password: String,
     pub redis_url: String,
+    pub public_base_url: String,
+    pub storage_backend: String,
+    pub storage_local_root: String,
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key: String,
+    pub s3_secret_key: String,
+    pub s3_public_base_url: String,
+    pub field_encryption_keys: String,
+    pub field_encryption_active_key: String,
+    pub address_lookup_backend: String,
+    pub juso_api_base_url: String,
+    pub juso_api_key: String,
+    pub geocoder_backend: String,
+    pub kakao_local_base_url: String,
+    pub carrier_backend: String,
+    pub carrier_webhook_secrets: String,
 }
 
 impl Config {
@@ -36,6 +58,11 @@ impl Config {
         // Kakao Config (Optional in dev if not used, but good to enforce if feature is active)
         let kakao_client_id = env::var("KAKAO_CLIENT_ID").unwrap_or_else(|_| "".to_string());
         let kakao_redirect_uri = env::var("KAKAO_REDIRECT_URI").unwrap_or_else(|_| "".to_string());
+        // Overridable so the OAuth flow can run against a local mock
+        let kakao_auth_base_url = env::var("KAKAO_AUTH_BASE_URL")
+            .unwrap_or_else(|_| "https://kauth.kakao.com".to_string());
+        let kakao_api_base_url =
+            env::var("KAKAO_API_BASE_URL").unwrap_or_else(|_| "https://kapi.kakao.com".to_string());
 
         // Gmail Config
         let gmail_user = env::var("GMAIL_USER").unwrap_or_else(|_| "".to_string());
@@ -45,6 +72,48 @@ impl Config {
         let redis_url =
             env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
 
+        // Public URL used for links in outgoing mail
+        let public_base_url = env::var("PUBLIC_BASE_URL")
+            .unwrap_or_else(|_| format!("http://{}:{}", server_host, server_port));
+
+        // File storage: "local" (served from /files) or "s3" (any S3-compatible store)
+        let storage_backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+        let storage_local_root =
+            env::var("STORAGE_LOCAL_ROOT").unwrap_or_else(|_| "./storage".to_string());
+        let s3_endpoint = env::var("S3_ENDPOINT")
+            .unwrap_or_else(|_| "https://s3.ap-northeast-2.amazonaws.com".to_string());
+        let s3_bucket = env::var("S3_BUCKET").unwrap_or_else(|_| "".to_string());
+        let s3_region = env::var("S3_REGION").unwrap_or_else(|_| "ap-northeast-2".to_string());
+        let s3_access_key = env::var("S3_ACCESS_KEY").unwrap_or_else(|_| "".to_string());
+        let s3_secret_key = env::var("S3_SECRET_KEY").unwrap_or_else(|_| "".to_string());
+        let s3_public_base_url = env::var("S3_PUBLIC_BASE_URL").unwrap_or_else(|_| "".to_string());
+
+        // Field encryption KEKs as `id:base64key,...`; old ids stay listed until re-encrypted away
+        let field_encryption_keys =
+            env::var("FIELD_ENCRYPTION_KEYS").unwrap_or_else(|_| "".to_string());
+        let field_encryption_active_key =
+            env::var("FIELD_ENCRYPTION_ACTIVE_KEY").unwrap_or_else(|_| "".to_string());
+
+        // Address lookup: "juso" (도로명주소 API) or "fixture" (built-in sample addresses)
+        let address_lookup_backend =
+            env::var("ADDRESS_LOOKUP_BACKEND").unwrap_or_else(|_| "juso".to_string());
+        let juso_api_base_url = env::var("JUSO_API_BASE_URL")
+            .unwrap_or_else(|_| "https://business.juso.go.kr".to_string());
+        let juso_api_key = env::var("JUSO_API_KEY").unwrap_or_else(|_| "".to_string());
+
+        // Geocoding: "kakao" (Kakao Local, keyed by KAKAO_CLIENT_ID) or "fixture"
+        let geocoder_backend = env::var("GEOCODER_BACKEND").unwrap_or_else(|_| "kakao".to_string());
+        let kakao_local_base_url = env::var("KAKAO_LOCAL_BASE_URL")
+            .unwrap_or_else(|_| "https://dapi.kakao.com".to_string());
+
+        // Courier tracking: "mock" (dev/test only) or "none" until real carrier
+        // APIs are contracted.
+        let carrier_backend = env::var("CARRIER_BACKEND").unwrap_or_else(|_| "none".to_string());
+        // Courier push updates: "CJ:secret,HANJIN:secret". Carriers without a
+        // secret are polled instead.
+        let carrier_webhook_secrets =
+            env::var("CARRIER_WEBHOOK_SECRETS").unwrap_or_else(|_| "".to_string());
+
         Self {
             database_url,
             database_max_connections: env::var("DATABASE_MAX_CONNECTIONS")
@@ -69,9 +138,82 @@ impl Config {
             app_env,
             kakao_client_id,
             kakao_redirect_uri,
+            kakao_auth_base_url,
+            kakao_api_base_url,
+            oauth_http_timeout_ms: env::var("OAUTH_HTTP_TIMEOUT_MS")
+                .unwrap_or_else(|_| "3000".to_string())
+                .parse::<u64>()
+                .expect("OAUTH_HTTP_TIMEOUT_MS must be a valid number"),
+            oauth_http_max_retries: env::var("OAUTH_HTTP_MAX_RETRIES")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse::<u32>()
+                .expect("OAUTH_HTTP_MAX_RETRIES must be a valid number"),
             gmail_user,
             gmail_app_password,
             redis_url,
+            public_base_url,
+            storage_backend,
+            storage_local_root,
+            s3_endpoint,
+            s3_bucket,
+            s3_region,
+            s3_access_key,
+            s3_secret_key,
+            s3_public_base_url,
+            field_encryption_keys,
+            field_encryption_active_key,
+            address_lookup_backend,
+            juso_api_base_url,
+            juso_api_key,
+            geocoder_backend,
+            kakao_local_base_url,
+            carrier_backend,
+            carrier_webhook_secrets,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    /// Config with empty external endpoints, for unit tests.
+    pub fn for_test() -> Self {
+        Self {
+            database_url: "".to_string(),
+            database_max_connections: 100,
+            database_min_connections: 5,
+            database_connect_timeout: 8,
+            database_idle_timeout: 8,
+            server_host: "localhost".to_string(),
+            server_port: 3000,
+            rust_log: "info".to_string(),
+            app_env: "test".to_string(),
+            kakao_client_id: "".to_string(),
+            kakao_redirect_uri: "".to_string(),
+            kakao_auth_base_url: "".to_string(),
+            kakao_api_base_url: "".to_string(),
+            oauth_http_timeout_ms: 3000,
+            oauth_http_max_retries: 2,
+            gmail_user: "".to_string(),
+            gmail_app_password: "".to_string(),
+            redis_url: "".to_string(),
+            public_base_url: "".to_string(),
+            storage_backend: "local".to_string(),
+            storage_local_root: "".to_string(),
+            s3_endpoint: "".to_string(),
+            s3_bucket: "".to_string(),
+            s3_region: "".to_string(),
+            s3_access_key: "".to_string(),
+            s3_secret_key: "".to_string(),
+            s3_public_base_url: "".to_string(),
+            field_encryption_keys: "test:QUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUE=".to_string(),
+            field_encryption_active_key: "test".to_string(),
+            address_lookup_backend: "fixture".to_string(),
+            juso_api_base_url: "".to_string(),
+            juso_api_key: "".to_string(),
+            geocoder_backend: "fixture".to_string(),
+            kakao_local_base_url: "".to_string(),
+            carrier_backend: "mock".to_string(),
+            carrier_webhook_secrets: "".to_string(),
         }
     }
 }
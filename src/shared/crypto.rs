@@ -0,0 +1,274 @@
+//! Envelope encryption for sensitive columns.
+//!
+//! Each record gets its own random data key (DEK) that encrypts its fields.
+//! The DEK is stored next to the record, wrapped by a key-encryption key (KEK)
+//! from `Config`. Every wrapped DEK carries the id of the KEK that wrapped it,
+//! so old keys stay usable until retired. After a rotation the delivery
+//! re-encryption job opens each record still on an old KEK and re-seals all of
+//! its fields under a fresh DEK wrapped by the active one.
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, AeadCore, OsRng, Payload},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::shared::config::Config;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+pub const KEY_ID_MAX_LEN: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum CryptoError {
+    /// The record was sealed with a key that is no longer configured.
+    #[error("Unknown key id: {0}")]
+    UnknownKey(String),
+
+    /// Wrong key, tampered ciphertext or a value moved between fields.
+    #[error("Decryption failed")]
+    Decrypt,
+
+    #[error("Malformed ciphertext")]
+    Malformed,
+
+    #[error("Invalid key configuration: {0}")]
+    InvalidConfig(String),
+}
+
+pub type CryptoResult<T> = Result<T, CryptoError>;
+
+/// Holds the configured KEKs. Cheap to share behind an `Arc`.
+pub struct FieldCipher {
+    keys: HashMap<String, Aes256Gcm>,
+    active_key_id: String,
+}
+
+impl FieldCipher {
+    /// Parses `FIELD_ENCRYPTION_KEYS` (`id:base64key,...`, 32-byte keys) and
+    /// picks `FIELD_ENCRYPTION_ACTIVE_KEY` for new data keys.
+    pub fn new(keys: &str, active_key_id: &str) -> CryptoResult<Self> {
+        let mut parsed = HashMap::new();
+        for entry in keys.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (id, encoded) = entry.split_once(':').ok_or(CryptoError::InvalidConfig(
+                "Expected entries of the form id:base64key".to_string(),
+            ))?;
+            let id = id.trim();
+            if id.is_empty() || id.len() > KEY_ID_MAX_LEN {
+                return Err(CryptoError::InvalidConfig(format!(
+                    "Key ids must be 1-{} characters",
+                    KEY_ID_MAX_LEN
+                )));
+            }
+            let bytes = STANDARD
+                .decode(encoded.trim())
+                .map_err(|_| CryptoError::InvalidConfig(format!("Key {} is not base64", id)))?;
+            if bytes.len() != KEY_LEN {
+                return Err(CryptoError::InvalidConfig(format!(
+                    "Key {} must be {} bytes",
+                    id, KEY_LEN
+                )));
+            }
+            let cipher = Aes256Gcm::new_from_slice(&bytes)
+                .map_err(|_| CryptoError::InvalidConfig(format!("Key {} is invalid", id)))?;
+            if parsed.insert(id.to_string(), cipher).is_some() {
+                return Err(CryptoError::InvalidConfig(format!(
+                    "Duplicate key id {}",
+                    id
+                )));
+            }
+        }
+        if !parsed.contains_key(active_key_id) {
+            return Err(CryptoError::InvalidConfig(format!(
+                "Active key {:?} is not among the configured keys",
+                active_key_id
+            )));
+        }
+        Ok(Self {
+            keys: parsed,
+            active_key_id: active_key_id.to_string(),
+        })
+    }
+
+    /// Outside dev the keys must be configured. In dev a throwaway key is
+    /// generated, which is fine because dev data lives in memory.
+    pub fn from_config(config: &Config) -> Self {
+        if config.field_encryption_keys.is_empty() && config.app_env == "dev" {
+            tracing::warn!("FIELD_ENCRYPTION_KEYS not set, using an ephemeral dev key");
+            let key = Aes256Gcm::generate_key(OsRng);
+            return Self::new(&format!("dev:{}", STANDARD.encode(key)), "dev")
+                .expect("Ephemeral key is valid");
+        }
+        Self::new(
+            &config.field_encryption_keys,
+            &config.field_encryption_active_key,
+        )
+        .expect("FIELD_ENCRYPTION_KEYS / FIELD_ENCRYPTION_ACTIVE_KEY are invalid")
+    }
+
+    pub fn active_key_id(&self) -> &str {
+        &self.active_key_id
+    }
+
+    /// A fresh data key plus its wrapped form under the active KEK.
+    pub fn new_data_key(&self) -> CryptoResult<(DataKey, WrappedKey)> {
+        let key = Aes256Gcm::generate_key(OsRng);
+        let wrapped = self.wrap(&self.active_key_id, &key)?;
+        Ok((DataKey::from_bytes(&key)?, wrapped))
+    }
+
+    pub fn open_data_key(&self, wrapped: &WrappedKey) -> CryptoResult<DataKey> {
+        let kek = self
+            .keys
+            .get(&wrapped.key_id)
+            .ok_or_else(|| CryptoError::UnknownKey(wrapped.key_id.clone()))?;
+        let bytes = open(kek, &wrapped.wrapped, wrapped.key_id.as_bytes())?;
+        if bytes.len() != KEY_LEN {
+            return Err(CryptoError::Malformed);
+        }
+        DataKey::from_bytes(&bytes)
+    }
+
+    fn wrap(&self, key_id: &str, key: &[u8]) -> CryptoResult<WrappedKey> {
+        let kek = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| CryptoError::UnknownKey(key_id.to_string()))?;
+        Ok(WrappedKey {
+            key_id: key_id.to_string(),
+            // Binding the key id stops a wrapped key being relabelled.
+            wrapped: seal(kek, key, key_id.as_bytes())?,
+        })
+    }
+}
+
+/// A data key as stored: the KEK id and the base64 `nonce || ciphertext`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedKey {
+    pub key_id: String,
+    pub wrapped: String,
+}
+
+/// Plain per-record key. Never stored unwrapped.
+pub struct DataKey(Aes256Gcm);
+
+impl DataKey {
+    fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        Aes256Gcm::new_from_slice(bytes)
+            .map(Self)
+            .map_err(|_| CryptoError::Malformed)
+    }
+
+    /// `field` is bound as associated data, so a value copied into another
+    /// column fails to decrypt.
+    pub fn encrypt(&self, field: &str, plaintext: &str) -> CryptoResult<String> {
+        seal(&self.0, plaintext.as_bytes(), field.as_bytes())
+    }
+
+    pub fn decrypt(&self, field: &str, ciphertext: &str) -> CryptoResult<String> {
+        String::from_utf8(open(&self.0, ciphertext, field.as_bytes())?)
+            .map_err(|_| CryptoError::Malformed)
+    }
+}
+
+fn seal(cipher: &Aes256Gcm, plaintext: &[u8], aad: &[u8]) -> CryptoResult<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| CryptoError::Malformed)?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(out))
+}
+
+fn open(cipher: &Aes256Gcm, encoded: &str, aad: &[u8]) -> CryptoResult<Vec<u8>> {
+    let bytes = STANDARD
+        .decode(encoded)
+        .map_err(|_| CryptoError::Malformed)?;
+    if bytes.len() <= NONCE_LEN {
+        return Err(CryptoError::Malformed);
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| CryptoError::Malformed)?;
+    cipher
+        .decrypt(
+            &Nonce::from(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| CryptoError::Decrypt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K1: &str = "k1:QUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUE=";
+    const K2: &str = "k2:QkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkI=";
+
+    #[test]
+    fn test_round_trip_and_field_binding() {
+        let cipher = FieldCipher::new(K1, "k1").unwrap();
+        let (key, wrapped) = cipher.new_data_key().unwrap();
+        assert_eq!(wrapped.key_id, "k1");
+
+        let sealed = key.encrypt("entrance_password", "#1234*").unwrap();
+        assert_ne!(sealed, "#1234*");
+        // Fresh nonce every time.
+        assert_ne!(sealed, key.encrypt("entrance_password", "#1234*").unwrap());
+
+        let reopened = cipher.open_data_key(&wrapped).unwrap();
+        assert_eq!(
+            reopened.decrypt("entrance_password", &sealed).unwrap(),
+            "#1234*"
+        );
+        assert!(matches!(
+            reopened.decrypt("detail_address", &sealed),
+            Err(CryptoError::Decrypt)
+        ));
+    }
+
+    #[test]
+    fn test_old_keys_open_until_removed() {
+        let old = FieldCipher::new(K1, "k1").unwrap();
+        let (_, wrapped) = old.new_data_key().unwrap();
+
+        let rotated = FieldCipher::new(&format!("{},{}", K1, K2), "k2").unwrap();
+        assert!(rotated.open_data_key(&wrapped).is_ok());
+        assert_eq!(rotated.new_data_key().unwrap().1.key_id, "k2");
+
+        let retired = FieldCipher::new(K2, "k2").unwrap();
+        assert!(matches!(
+            retired.open_data_key(&wrapped),
+            Err(CryptoError::UnknownKey(_))
+        ));
+
+        // A wrapped key relabelled with another id does not open.
+        let relabelled = WrappedKey {
+            key_id: "k2".to_string(),
+            ..wrapped
+        };
+        assert!(matches!(
+            rotated.open_data_key(&relabelled),
+            Err(CryptoError::Decrypt)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_bad_config() {
+        assert!(FieldCipher::new(K1, "k2").is_err());
+        assert!(FieldCipher::new("k1:c2hvcnQ=", "k1").is_err());
+        assert!(FieldCipher::new(&format!("{},{}", K1, K1), "k1").is_err());
+        assert!(FieldCipher::new("nocolon", "nocolon").is_err());
+    }
+}
@@ -6,6 +6,13 @@ use axum::{
 use serde_json::json;
 use thiserror::Error;
 
+use crate::modules::auth::providers::error::OAuthError;
+use crate::modules::delivery::lookup::AddressLookupError;
+use crate::modules::shipment::carriers::CarrierError;
+use crate::shared::crypto::CryptoError;
+use crate::shared::geocoding::GeocodeError;
+use crate::shared::storage::StorageError;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -28,6 +35,33 @@ pub enum AppError {
 
     #[error("Forbidden: {0}")]
     Forbidden(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("OAuth error: {0}")]
+    OAuth(#[from] OAuthError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Address lookup error: {0}")]
+    AddressLookup(#[from] AddressLookupError),
+
+    #[error("Geocode error: {0}")]
+    Geocode(#[from] GeocodeError),
+
+    #[error("Carrier error: {0}")]
+    Carrier(#[from] CarrierError),
 }
 
 impl IntoResponse for AppError {
@@ -73,6 +107,154 @@ impl IntoResponse for AppError {
             AppError::Forbidden(msg) => {
                 (StatusCode::FORBIDDEN, msg, "403".to_string(), "FORBIDDEN")
             }
+            AppError::TooManyRequests(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                msg,
+                "429".to_string(),
+                "TOO_MANY_REQUESTS",
+            ),
+            AppError::PayloadTooLarge(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                msg,
+                "413".to_string(),
+                "PAYLOAD_TOO_LARGE",
+            ),
+            AppError::UnsupportedMediaType(msg) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                msg,
+                "415".to_string(),
+                "UNSUPPORTED_MEDIA_TYPE",
+            ),
+            AppError::Storage(err) => {
+                tracing::error!("Storage error: {}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Storage error".to_string(),
+                    "500".to_string(),
+                    "STORAGE_ERROR",
+                )
+            }
+            AppError::Crypto(err) => {
+                tracing::error!("Crypto error: {}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                    "500".to_string(),
+                    "INTERNAL_SERVER_ERROR",
+                )
+            }
+            AppError::AddressLookup(err) => match err {
+                AddressLookupError::InvalidQuery(msg) => (
+                    StatusCode::BAD_REQUEST,
+                    msg,
+                    "400".to_string(),
+                    "ADDRESS_INVALID_QUERY",
+                ),
+                AddressLookupError::Upstream(msg) => {
+                    tracing::error!("Address lookup upstream error: {}", msg);
+                    (
+                        StatusCode::BAD_GATEWAY,
+                        "Address lookup error".to_string(),
+                        "502".to_string(),
+                        "ADDRESS_LOOKUP_ERROR",
+                    )
+                }
+                AddressLookupError::Unavailable(msg) => {
+                    tracing::warn!("Address lookup unavailable: {}", msg);
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "Address lookup unavailable".to_string(),
+                        "503".to_string(),
+                        "ADDRESS_LOOKUP_UNAVAILABLE",
+                    )
+                }
+            },
+            AppError::Geocode(err) => {
+                let status = match err {
+                    GeocodeError::Upstream(_) => StatusCode::BAD_GATEWAY,
+                    GeocodeError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+                };
+                tracing::warn!("Geocode error: {}", err);
+                (
+                    status,
+                    "Geocoding unavailable".to_string(),
+                    status.as_u16().to_string(),
+                    "GEOCODE_ERROR",
+                )
+            }
+            AppError::Carrier(err) => match err {
+                CarrierError::InvalidSignature => (
+                    StatusCode::UNAUTHORIZED,
+                    err.to_string(),
+                    "401".to_string(),
+                    "CARRIER_INVALID_SIGNATURE",
+                ),
+                CarrierError::WebhooksDisabled(_) => (
+                    StatusCode::NOT_FOUND,
+                    err.to_string(),
+                    "404".to_string(),
+                    "CARRIER_WEBHOOKS_DISABLED",
+                ),
+                CarrierError::MalformedPayload(msg) => (
+                    StatusCode::BAD_REQUEST,
+                    msg,
+                    "400".to_string(),
+                    "CARRIER_MALFORMED_PAYLOAD",
+                ),
+                CarrierError::Upstream(msg) => {
+                    tracing::warn!("Carrier upstream error: {}", msg);
+                    (
+                        StatusCode::BAD_GATEWAY,
+                        "Carrier tracking error".to_string(),
+                        "502".to_string(),
+                        "CARRIER_ERROR",
+                    )
+                }
+                CarrierError::Unavailable(msg) => {
+                    tracing::warn!("Carrier unavailable: {}", msg);
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "Carrier tracking unavailable".to_string(),
+                        "503".to_string(),
+                        "CARRIER_UNAVAILABLE",
+                    )
+                }
+            },
+            AppError::OAuth(err) => {
+                let message = err.to_string();
+                match err {
+                    OAuthError::InvalidGrant(_) => (
+                        StatusCode::UNAUTHORIZED,
+                        message,
+                        "401".to_string(),
+                        "OAUTH_INVALID_GRANT",
+                    ),
+                    OAuthError::ConsentMissing(_) => (
+                        StatusCode::FORBIDDEN,
+                        message,
+                        "403".to_string(),
+                        "OAUTH_CONSENT_MISSING",
+                    ),
+                    OAuthError::UpstreamError(_) => {
+                        tracing::error!("OAuth upstream error: {}", message);
+                        (
+                            StatusCode::BAD_GATEWAY,
+                            "OAuth provider error".to_string(),
+                            "502".to_string(),
+                            "OAUTH_UPSTREAM_ERROR",
+                        )
+                    }
+                    OAuthError::UpstreamUnavailable(_) => {
+                        tracing::warn!("OAuth upstream unavailable: {}", message);
+                        (
+                            StatusCode::SERVICE_UNAVAILABLE,
+                            "OAuth provider unavailable".to_string(),
+                            "503".to_string(),
+                            "OAUTH_UPSTREAM_UNAVAILABLE",
+                        )
+                    }
+                }
+            }
         };
 
         (
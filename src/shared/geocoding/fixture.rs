@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use super::{Coordinates, GeocodeResult, Geocoder};
+
+/// Looks addresses up in a fixed table, for tests and for dev without a
+/// geocoding key. Matching is exact after trimming.
+pub struct FixtureGeocoder {
+    positions: HashMap<String, Coordinates>,
+}
+
+impl FixtureGeocoder {
+    pub fn new(positions: impl IntoIterator<Item = (String, Coordinates)>) -> Self {
+        Self {
+            positions: positions.into_iter().collect(),
+        }
+    }
+}
+
+/// Covers the sample addresses of `FixtureAddressLookup`, in both forms.
+impl Default for FixtureGeocoder {
+    fn default() -> Self {
+        let entries = [
+            ("서울특별시 강남구 테헤란로 152", 37.5001, 127.0365),
+            ("서울특별시 강남구 역삼동 737", 37.5001, 127.0365),
+            ("서울특별시 강남구 테헤란로 142", 37.4994, 127.0353),
+            ("서울특별시 강남구 역삼동 736-1", 37.4994, 127.0353),
+            ("서울특별시 중구 세종대로 110", 37.5663, 126.9779),
+            ("서울특별시 중구 태평로1가 31", 37.5663, 126.9779),
+            ("경기도 성남시 분당구 판교역로 235", 37.4021, 127.1086),
+            ("경기도 성남시 분당구 삼평동 681", 37.4021, 127.1086),
+            ("부산광역시 해운대구 해운대해변로 264", 35.1587, 129.1604),
+            ("부산광역시 해운대구 우동 1411-1", 35.1587, 129.1604),
+        ];
+        Self::new(entries.into_iter().map(|(address, latitude, longitude)| {
+            (address.to_string(), Coordinates::new(latitude, longitude))
+        }))
+    }
+}
+
+#[async_trait]
+impl Geocoder for FixtureGeocoder {
+    async fn geocode(&self, address: &str) -> GeocodeResult<Option<Coordinates>> {
+        Ok(self.positions.get(address.trim()).copied())
+    }
+}
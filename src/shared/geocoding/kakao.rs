@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::{Coordinates, GeocodeError, GeocodeResult, Geocoder};
+use crate::shared::config::Config;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const SEARCH_PATH: &str = "/v2/local/search/address.json";
+
+#[derive(Clone, Debug)]
+pub struct KakaoGeocoderConfig {
+    /// e.g. `https://dapi.kakao.com`, or a local mock.
+    pub base_url: String,
+    /// The app's REST API key, the same one used as the login client id.
+    pub rest_api_key: String,
+}
+
+impl KakaoGeocoderConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            base_url: config
+                .kakao_local_base_url
+                .trim_end_matches('/')
+                .to_string(),
+            rest_api_key: config.kakao_client_id.clone(),
+        }
+    }
+}
+
+/// Kakao Local address search, which returns WGS84 coordinates.
+pub struct KakaoGeocoder {
+    config: KakaoGeocoderConfig,
+    client: Client,
+}
+
+impl KakaoGeocoder {
+    pub fn new(config: KakaoGeocoderConfig) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build Kakao Local HTTP client");
+        Self { config, client }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    documents: Vec<Document>,
+}
+
+/// `x` is the longitude and `y` the latitude, both as strings.
+#[derive(Deserialize)]
+struct Document {
+    x: String,
+    y: String,
+}
+
+#[async_trait]
+impl Geocoder for KakaoGeocoder {
+    async fn geocode(&self, address: &str) -> GeocodeResult<Option<Coordinates>> {
+        let res = self
+            .client
+            .get(format!("{}{}", self.config.base_url, SEARCH_PATH))
+            .header(
+                "Authorization",
+                format!("KakaoAK {}", self.config.rest_api_key),
+            )
+            .query(&[("query", address), ("size", "1")])
+            .send()
+            .await
+            .map_err(|e| GeocodeError::Unavailable(format!("kakao local: {}", e)))?;
+        if res.status().is_server_error() || res.status().as_u16() == 429 {
+            return Err(GeocodeError::Unavailable(format!(
+                "kakao local responded {}",
+                res.status()
+            )));
+        }
+        if !res.status().is_success() {
+            return Err(GeocodeError::Upstream(format!(
+                "kakao local responded {}",
+                res.status()
+            )));
+        }
+        let body: SearchResponse = res
+            .json()
+            .await
+            .map_err(|e| GeocodeError::Upstream(format!("kakao local: {}", e)))?;
+
+        let Some(document) = body.documents.into_iter().next() else {
+            return Ok(None);
+        };
+        let parse = |v: &str| {
+            v.parse::<f64>()
+                .map_err(|_| GeocodeError::Upstream(format!("kakao local: bad coordinate {}", v)))
+        };
+        Ok(Some(Coordinates::new(
+            parse(&document.y)?,
+            parse(&document.x)?,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Json, Router, extract::Query, http::HeaderMap, http::StatusCode as AxumStatus,
+        response::IntoResponse, routing::get,
+    };
+    use std::collections::HashMap;
+
+    /// Knows only 테헤란로 152 and requires the REST API key "test-key".
+    async fn spawn_mock_kakao_local() -> String {
+        async fn search(
+            headers: HeaderMap,
+            Query(params): Query<HashMap<String, String>>,
+        ) -> axum::response::Response {
+            if headers.get("authorization").and_then(|v| v.to_str().ok())
+                != Some("KakaoAK test-key")
+            {
+                return AxumStatus::UNAUTHORIZED.into_response();
+            }
+            let documents = match params.get("query").map(String::as_str) {
+                Some("서울특별시 강남구 테헤란로 152") => serde_json::json!([{
+                    "address_name": "서울 강남구 역삼동 737",
+                    "x": "127.036508620542",
+                    "y": "37.5000242405515"
+                }]),
+                _ => serde_json::json!([]),
+            };
+            Json(serde_json::json!({ "documents": documents, "meta": {} })).into_response()
+        }
+
+        let app = Router::new().route(SEARCH_PATH, get(search));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_geocode_against_mock_kakao_local() {
+        let base_url = spawn_mock_kakao_local().await;
+        let geocoder = KakaoGeocoder::new(KakaoGeocoderConfig {
+            base_url: base_url.clone(),
+            rest_api_key: "test-key".to_string(),
+        });
+
+        assert_eq!(
+            geocoder
+                .geocode("서울특별시 강남구 테헤란로 152")
+                .await
+                .unwrap(),
+            Some(Coordinates::new(37.5000242405515, 127.036508620542))
+        );
+        assert_eq!(geocoder.geocode("없는 주소").await.unwrap(), None);
+
+        let unauthorized = KakaoGeocoder::new(KakaoGeocoderConfig {
+            base_url,
+            rest_api_key: "wrong".to_string(),
+        });
+        assert!(matches!(
+            unauthorized.geocode("서울특별시 강남구 테헤란로 152").await,
+            Err(GeocodeError::Upstream(_))
+        ));
+    }
+}
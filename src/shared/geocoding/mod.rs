@@ -0,0 +1,82 @@
+pub mod fixture;
+pub mod kakao;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+
+pub use fixture::FixtureGeocoder;
+pub use kakao::{KakaoGeocoder, KakaoGeocoderConfig};
+
+/// Mean Earth radius (IUGG).
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Failures geocoding an address. An address the geocoder cannot place is
+/// `Ok(None)`, not an error. Address saves and the place backfill log these
+/// and carry on without coordinates; only `locate` reports them.
+#[derive(Error, Debug)]
+pub enum GeocodeError {
+    /// Kakao Local refused the call (a 4xx such as a revoked REST API key)
+    /// or returned a document or coordinate that does not parse.
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+
+    /// Kakao Local could not be reached, answered 5xx or throttled us (429).
+    #[error("Upstream unavailable: {0}")]
+    Unavailable(String),
+}
+
+pub type GeocodeResult<T> = Result<T, GeocodeError>;
+
+/// WGS84 position in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Great-circle (haversine) distance. Good to a few metres at city scale,
+    /// which is all routing to the nearest center needs.
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// Turns a road-name or jibun address into coordinates.
+#[async_trait]
+pub trait Geocoder: Send + Sync {
+    /// `None` when the address is not known to the geocoder.
+    async fn geocode(&self, address: &str) -> GeocodeResult<Option<Coordinates>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_km() {
+        let city_hall = Coordinates::new(37.5663, 126.9779);
+        let gangnam = Coordinates::new(37.5001, 127.0365);
+        let haeundae = Coordinates::new(35.1587, 129.1604);
+
+        assert_eq!(city_hall.distance_km(&city_hall), 0.0);
+        assert!((city_hall.distance_km(&gangnam) - 9.0).abs() < 0.1);
+        assert!((city_hall.distance_km(&haeundae) - 331.4).abs() < 0.1);
+        assert_eq!(
+            gangnam.distance_km(&haeundae),
+            haeundae.distance_km(&gangnam)
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::shared::error::AppResult;
+use crate::shared::repository::RepositoryManager;
+
+/// Runs `job` every `period` for the lifetime of the process, the first time
+/// right away. The repository `R` is looked up on each tick; if it is not
+/// registered the job stops. Errors are logged and the job runs again on the
+/// next tick, so `job` only has to report what it did.
+pub fn spawn_interval<R, F, Fut>(
+    name: &'static str,
+    period: Duration,
+    repo_manager: Arc<dyn RepositoryManager>,
+    job: F,
+) -> tokio::task::JoinHandle<()>
+where
+    R: Clone + Send + Sync + 'static,
+    F: Fn(R) -> Fut + Send + 'static,
+    Fut: Future<Output = AppResult<()>> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let Some(repo) = repo_manager.get::<R>().cloned() else {
+                tracing::error!(
+                    "{} stopped: {} not registered",
+                    name,
+                    std::any::type_name::<R>()
+                );
+                return;
+            };
+            if let Err(e) = job(repo).await {
+                tracing::error!("{} failed: {}", name, e);
+            }
+        }
+    })
+}
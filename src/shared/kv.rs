@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool_redis::redis::AsyncCommands;
+
+use crate::shared::error::{AppError, AppResult};
+
+/// Short-lived string values (pending codes, one-time tokens) that expire
+/// on their own. Redis in every deployed environment; the in-memory store
+/// keeps services that depend on it testable.
+#[async_trait]
+pub trait ExpiringStore: Send + Sync {
+    async fn set_ex(&self, key: &str, value: &str, ttl_secs: u64) -> AppResult<()>;
+
+    async fn get(&self, key: &str) -> AppResult<Option<String>>;
+
+    /// Removes the value; deleting a missing key is not an error.
+    async fn del(&self, key: &str) -> AppResult<()>;
+
+    /// Reads and removes the value in one step, so a one-time token can
+    /// only ever be redeemed once.
+    async fn take(&self, key: &str) -> AppResult<Option<String>>;
+
+    /// Adds one to a counter, (re)starting its TTL, and returns the new
+    /// value. A missing key counts from zero.
+    async fn incr(&self, key: &str, ttl_secs: u64) -> AppResult<u64>;
+}
+
+#[async_trait]
+impl ExpiringStore for deadpool_redis::Pool {
+    async fn set_ex(&self, key: &str, value: &str, ttl_secs: u64) -> AppResult<()> {
+        let mut conn = self
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        conn.set_ex(key, value, ttl_secs)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<String>> {
+        let mut conn = self
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        conn.get(key)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+
+    async fn del(&self, key: &str) -> AppResult<()> {
+        let mut conn = self
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        conn.del(key)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+
+    async fn take(&self, key: &str) -> AppResult<Option<String>> {
+        let mut conn = self
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        conn.get_del(key)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))
+    }
+
+    async fn incr(&self, key: &str, ttl_secs: u64) -> AppResult<u64> {
+        let mut conn = self
+            .get()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        let (count,): (u64,) = deadpool_redis::redis::pipe()
+            .atomic()
+            .incr(key, 1)
+            .expire(key, ttl_secs as i64)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        Ok(count)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryExpiringStore {
+    entries: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl InMemoryExpiringStore {
+    fn live(entries: &mut HashMap<String, (String, Instant)>, key: &str) -> Option<String> {
+        match entries.get(key) {
+            Some((_, expires_at)) if *expires_at <= Instant::now() => {
+                entries.remove(key);
+                None
+            }
+            entry => entry.map(|(value, _)| value.clone()),
+        }
+    }
+}
+
+#[async_trait]
+impl ExpiringStore for InMemoryExpiringStore {
+    async fn set_ex(&self, key: &str, value: &str, ttl_secs: u64) -> AppResult<()> {
+        let expires_at = Instant::now() + Duration::from_secs(ttl_secs);
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (value.to_string(), expires_at));
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<String>> {
+        Ok(Self::live(&mut self.entries.lock().unwrap(), key))
+    }
+
+    async fn del(&self, key: &str) -> AppResult<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn take(&self, key: &str) -> AppResult<Option<String>> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = Self::live(&mut entries, key);
+        entries.remove(key);
+        Ok(value)
+    }
+
+    async fn incr(&self, key: &str, ttl_secs: u64) -> AppResult<u64> {
+        let mut entries = self.entries.lock().unwrap();
+        let count = Self::live(&mut entries, key)
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0)
+            + 1;
+        let expires_at = Instant::now() + Duration::from_secs(ttl_secs);
+        entries.insert(key.to_string(), (count.to_string(), expires_at));
+        Ok(count)
+    }
+}
@@ -1,8 +1,13 @@
 pub mod config;
+pub mod crypto;
 pub mod db;
 pub mod error;
+pub mod geocoding;
 pub mod handlers;
 pub mod infra;
+pub mod jobs;
+pub mod kv;
 pub mod middleware;
 pub mod repository;
 pub mod state;
+pub mod storage;
@@ -1,6 +1,11 @@
 use crate::modules::auth::registry::OAuthProviderRegistry;
+use crate::modules::delivery::lookup::AddressLookup;
+use crate::modules::shipment::carriers::CarrierRegistry;
 use crate::shared::config::Config;
+use crate::shared::crypto::FieldCipher;
+use crate::shared::geocoding::Geocoder;
 use crate::shared::repository::RepositoryManager;
+use crate::shared::storage::FileStorage;
 use std::sync::Arc;
 
 use crate::modules::auth::providers::email::EmailProvider;
@@ -12,4 +17,9 @@ pub struct AppState {
     pub repo_manager: Arc<dyn RepositoryManager>,
     pub email_provider: Arc<dyn EmailProvider>,
     pub redis_pool: deadpool_redis::Pool,
+    pub storage: Arc<dyn FileStorage>,
+    pub field_cipher: Arc<FieldCipher>,
+    pub address_lookup: Arc<dyn AddressLookup>,
+    pub geocoder: Arc<dyn Geocoder>,
+    pub carriers: CarrierRegistry,
 }
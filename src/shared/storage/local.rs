@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use super::{FileStorage, StorageResult, validate_key};
+
+/// Stores objects as plain files under `root`, e.g. for dev and single-node setups.
+/// Writes go to a temporary sibling first so readers never see a partial file.
+pub struct LocalFileStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.into(),
+        }
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> StorageResult<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl FileStorage for LocalFileStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> StorageResult<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, bytes).await?;
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_base_url.trim_end_matches('/'), key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_round_trip() {
+        let root = std::env::temp_dir().join(format!("gimme-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalFileStorage::new(&root, "http://localhost:3000/files/");
+
+        storage
+            .put("avatars/u1/a.jpg", b"jpeg".to_vec(), "image/jpeg")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get("avatars/u1/a.jpg").await.unwrap().as_deref(),
+            Some(&b"jpeg"[..])
+        );
+        assert_eq!(
+            storage.public_url("avatars/u1/a.jpg"),
+            "http://localhost:3000/files/avatars/u1/a.jpg"
+        );
+
+        storage.delete("avatars/u1/a.jpg").await.unwrap();
+        storage.delete("avatars/u1/a.jpg").await.unwrap();
+        assert!(storage.get("avatars/u1/a.jpg").await.unwrap().is_none());
+        assert!(storage.get("../outside").await.is_err());
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+}
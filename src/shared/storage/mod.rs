@@ -0,0 +1,74 @@
+pub mod local;
+pub mod s3;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+pub use local::LocalFileStorage;
+pub use s3::{S3Config, S3FileStorage};
+
+/// Failures reading or writing stored objects, surfaced through `AppError::Storage`.
+#[derive(Error, Debug)]
+pub enum StorageError {
+    /// The key is empty, absolute or tries to escape its prefix.
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The object store answered with an error or could not be reached.
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// Blob store for user-facing files (avatars now, product and place images later).
+/// Keys are `/`-separated relative paths such as `avatars/<uuid>/<id>.jpg`.
+#[async_trait]
+pub trait FileStorage: Send + Sync {
+    /// Stores `bytes` under `key`, replacing any existing object.
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> StorageResult<()>;
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>>;
+
+    /// Removes the object; deleting a missing key is not an error.
+    async fn delete(&self, key: &str) -> StorageResult<()>;
+
+    /// Public URL clients should load the object from.
+    fn public_url(&self, key: &str) -> String;
+}
+
+/// Rejects keys that could escape the bucket or storage root.
+pub fn validate_key(key: &str) -> StorageResult<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && !key.contains('\\')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+/// Where the local backend's files are served from when it is active.
+pub const LOCAL_FILES_ROUTE: &str = "/files";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("avatars/abc/1.jpg").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("avatars/../../etc/passwd").is_err());
+        assert!(validate_key("avatars//1.jpg").is_err());
+        assert!(validate_key("avatars\\1.jpg").is_err());
+    }
+}